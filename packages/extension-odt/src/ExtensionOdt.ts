@@ -99,8 +99,9 @@ export class ExtensionOdt extends Extension {
           filesMap[k] = Uint8Array.from(files.get(k));
         }
 
-        const stylesTree = odtWasm.parse_styles(files.get('styles.xml'));
-        const contentTree = odtWasm.parse_content(files.get('content.xml'));
+        const document = new odtWasm.OdtDocument(buffer);
+        const stylesTree = document.styles();
+        const contentTree = document.content();
 
         if (this.config.debug) {
           const event = new CustomEvent('odt:parsed', {
@@ -116,7 +117,18 @@ export class ExtensionOdt extends Extension {
         const parser = new OdtParser(editor.schema, this.config);
         parser.filesMap = filesMap;
 
-        const doc = parser.parse({ ...filesMap, contentTree, stylesTree });
+        let doc;
+        try {
+          doc = parser.parse({
+            ...filesMap,
+            contentTree,
+            stylesTree,
+            document,
+            odtWasm,
+          });
+        } finally {
+          document.free();
+        }
 
         if (this.config.debug) {
           const event = new CustomEvent('odt:pmdoc', {
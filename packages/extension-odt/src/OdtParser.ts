@@ -16,63 +16,116 @@ export interface OdtElement {
 export type NodeHandler = (ctx: OdtStashContext, value: any) => void;
 
 export interface ListLLevelLabelAlignment {
-  '@margin-left'?: string;
+  '@fo:margin-left'?: string;
 }
 
 export interface ListLevelProperties {
-  'list-level-label-alignment': ListLLevelLabelAlignment;
+  'style:list-level-label-alignment': ListLLevelLabelAlignment;
 }
 
 export interface ListLevelStyleBullet {
-  '@level': number;
-  'list-level-properties': ListLevelProperties;
+  '@text:level': number;
+  'style:list-level-properties': ListLevelProperties;
 }
 
 export interface ListLevelStyleNumber {
-  '@level': number;
-  '@start-value'?: number;
-  '@num-format': string;
-  'list-level-properties': ListLevelProperties;
+  '@text:level': number;
+  '@text:start-value'?: number;
+  '@style:num-format': string;
+  'style:list-level-properties': ListLevelProperties;
 }
 
 export interface ListStyle {
-  '@name'?: string;
-  'list-level-style-bullet': ListLevelStyleBullet[];
-  'list-level-style-number': ListLevelStyleNumber[];
+  '@style:name'?: string;
+  'text:list-level-style-bullet': ListLevelStyleBullet[];
+  'text:list-level-style-number': ListLevelStyleNumber[];
 }
 
 export interface TextProperty {
-  '@font-name'?: string;
-  '@font-weight'?: string;
-  '@font-style'?: string;
-  '@font-size'?: string;
-  '@text-underline-style'?: string;
-  '@color'?: string;
+  '@style:font-name'?: string;
+  '@style:font-name-asian'?: string;
+  '@style:font-name-complex'?: string;
+  '@fo:font-weight'?: string;
+  '@fo:font-style'?: string;
+  '@fo:font-size'?: string;
+  '@fo:font-variant'?: string;
+  '@fo:text-transform'?: string;
+  '@style:text-underline-style'?: string;
+  '@style:text-underline-type'?: string;
+  '@style:text-underline-width'?: string;
+  '@style:text-underline-color'?: string;
+  '@style:text-line-through-style'?: string;
+  '@style:text-line-through-type'?: string;
+  '@style:text-position'?: string;
+  '@fo:color'?: string;
+  '@fo:background-color'?: string;
+  '@fo:language'?: string;
+  '@fo:country'?: string;
 }
 
 export interface ParagraphProperty {
-  '@break-before'?: string;
-  '@break-after'?: string;
-  '@margin-left'?: string;
+  '@fo:break-before'?: string;
+  '@fo:break-after'?: string;
+  '@fo:margin-left'?: string;
+  '@fo:margin-right'?: string;
+  '@fo:margin-top'?: string;
+  '@fo:margin-bottom'?: string;
+  '@fo:text-align'?: string;
+  '@fo:text-indent'?: string;
+  '@fo:line-height'?: string;
+  '@fo:background-color'?: string;
+  '@fo:border'?: string;
+  '@fo:border-top'?: string;
+  '@fo:border-bottom'?: string;
+  '@fo:border-left'?: string;
+  '@fo:border-right'?: string;
+  '@fo:padding'?: string;
+  '@fo:padding-top'?: string;
+  '@fo:padding-bottom'?: string;
+  '@fo:padding-left'?: string;
+  '@fo:padding-right'?: string;
+  '@fo:keep-with-next'?: string;
+  '@fo:keep-together'?: string;
+  '@style:writing-mode'?: string;
 }
 
 export interface Style {
-  '@name'?: string;
-  '@parent-style-name'?: string;
+  '@style:name'?: string;
+  '@style:parent-style-name'?: string;
+  '@style:master-page-name'?: string;
   styles: string[];
-  'text-properties'?: TextProperty;
-  'paragraph-properties'?: TextProperty;
+  'style:text-properties'?: TextProperty;
+  'style:paragraph-properties'?: ParagraphProperty;
 }
 
 export interface StylesTree {
-  styles: {
-    'list-style': Array<ListStyle>;
-    'style': Array<Style>;
+  'office:styles': {
+    'text:list-style': Array<ListStyle>;
+    'style:style': Array<Style>;
+  };
+  'office:automatic-styles'?: AutomaticStyles;
+  'office:master-styles'?: {
+    'style:master-page': Array<MasterPage>;
   };
 }
 
 export interface AutomaticStyles {
-  'style': Array<Style>;
+  'style:style': Array<Style>;
+}
+
+export interface HeaderFooter {
+  '@style:display'?: boolean;
+  $value: unknown[];
+}
+
+export interface MasterPage {
+  '@style:name'?: string;
+  'style:header'?: HeaderFooter;
+  'style:header-left'?: HeaderFooter;
+  'style:header-first'?: HeaderFooter;
+  'style:footer'?: HeaderFooter;
+  'style:footer-left'?: HeaderFooter;
+  'style:footer-first'?: HeaderFooter;
 }
 
 export function resolveListStyle(
@@ -82,67 +135,47 @@ export function resolveListStyle(
 ): ListStyle {
   let style: ListStyle | undefined;
 
-  style = stylesTree.styles['list-style'].find((item) =>
-    item['@name'] === name
+  style = stylesTree['office:styles']['text:list-style'].find((item) =>
+    item['@style:name'] === name
   );
 
   if (!style) {
     style = {
-      '@name': name,
-      'list-level-style-number': [],
-      'list-level-style-bullet': [],
+      '@style:name': name,
+      'text:list-level-style-number': [],
+      'text:list-level-style-bullet': [],
     };
   }
 
   return style;
 }
 
-export function resolveStyle(
-  stylesTree: StylesTree,
-  automaticStyles: AutomaticStyles,
-  name: string,
-): Style {
-  let style: Style | undefined;
-
-  if (!style) {
-    style = stylesTree.styles['style'].find((item) => item['@name'] === name);
-  }
-  if (!style) {
-    style = automaticStyles.style.find((item) => item['@name'] === name);
-  }
-
-  if (!style) {
-    style = {
-      '@name': name,
-      styles: [],
-    };
-  }
+// Effective properties of a style, as returned by `resolve_style` of the
+// wasm `OdtDocument`.
+export interface ResolvedStyle {
+  name: string;
+  family: string;
+  chain: string[];
+  list_style_name?: string;
+  master_page_name?: string;
+  text_properties: TextProperty;
+  paragraph_properties: ParagraphProperty;
+}
 
-  style['styles'] = [name];
+export type StyleLookup = (name: string, family: string) => ResolvedStyle;
 
-  if (style['@parent-style-name']) {
-    const parentStyle = resolveStyle(
-      stylesTree,
-      automaticStyles,
-      style['@parent-style-name'],
-    );
-    if (parentStyle) {
-      const styles = [...style['styles'], ...parentStyle['styles']];
-      for (const key of Object.keys(style) as (keyof Style)[]) {
-        if (typeof style[key] === 'undefined') {
-          delete style[key];
-        }
-      }
-      style = {
-        ...parentStyle,
-        ...style,
-        styles,
-      };
-    }
-  }
-  return style;
+// A table expanded into rows of equal length, as returned by `table_grid` of
+// the wasm module.
+export interface TableGrid {
+  header_rows: number;
+  rows: GridCell[][];
 }
 
+export type GridCell =
+  | { Anchor: { cell: any; colspan: number; rowspan: number } }
+  | { Covered: { row: number; column: number } }
+  | 'Empty';
+
 export interface TaggedEnum {
   tag: string;
   value: any;
@@ -208,7 +241,9 @@ export class OdtStashContext {
     readonly schema: Schema,
     handlers: Record<string, NodeHandler>,
     readonly stylesTree: StylesTree,
-    readonly automaticStyles: AutomaticStyles,
+    public automaticStyles: AutomaticStyles,
+    private lookupStyle: StyleLookup,
+    readonly tableGrid: (table: any) => TableGrid,
   ) {
     this.currentCtx = {
       handlers,
@@ -287,22 +322,47 @@ export class OdtStashContext {
 
   public styleToMarks(style: Style): Mark[] {
     const marks: Mark[] = [];
-    const textProperties = style && style['text-properties'] || {};
+    const textProperties = style && style['style:text-properties'] || {};
 
-    if (COURIER_FONTS.indexOf(textProperties['@font-name'] || '') > -1) {
+    if (COURIER_FONTS.indexOf(textProperties['@style:font-name'] || '') > -1) {
       const markType = this.schema.mark('code');
       marks.push(markType);
     }
 
-    if (textProperties['@font-style'] === 'italic') {
+    if (textProperties['@fo:font-style'] === 'italic') {
       const markType = this.schema.mark('em');
       marks.push(markType);
     }
-    if (textProperties['@font-weight'] === 'bold') {
+    const fontWeight = textProperties['@fo:font-weight'];
+    if (fontWeight === 'bold' || parseInt(fontWeight || '') >= 600) {
       const markType = this.schema.mark('strong');
       marks.push(markType);
     }
 
+    const lineThroughStyle = textProperties['@style:text-line-through-style'];
+    if (
+      this.schema.marks.strike && lineThroughStyle &&
+      lineThroughStyle !== 'none' &&
+      textProperties['@style:text-line-through-type'] !== 'none'
+    ) {
+      marks.push(this.schema.mark('strike'));
+    }
+
+    // "super 58%", "sub" or a percentage, negative when lowered.
+    const shift = (textProperties['@style:text-position'] || '').split(' ')[0];
+    if (
+      this.schema.marks.superscript &&
+      (shift === 'super' || parseFloat(shift) > 0)
+    ) {
+      marks.push(this.schema.mark('superscript'));
+    }
+    if (
+      this.schema.marks.subscript &&
+      (shift === 'sub' || parseFloat(shift) < 0)
+    ) {
+      marks.push(this.schema.mark('subscript'));
+    }
+
     return marks;
   }
 
@@ -317,18 +377,36 @@ export class OdtStashContext {
     this.current.handlers[nodeType](this, value);
   }
 
-  public getElementStyle(element: any): Style {
-    const style = ('object' === typeof element && element['@style-name'])
-      ? resolveStyle(
-        this.stylesTree,
-        this.automaticStyles,
-        element['@style-name'],
-      )
-      : {
-        styles: [],
-      };
+  // The style of a paragraph (`family` "paragraph") or span ("text").
+  public getElementStyle(element: any, family = 'paragraph'): Style {
+    const name = 'object' === typeof element && element['@text:style-name'];
+    if (!name) {
+      return { styles: [] };
+    }
+    const resolved = this.lookupStyle(name, family);
+    return {
+      '@style:name': name,
+      '@style:master-page-name': resolved.master_page_name,
+      styles: resolved.chain,
+      'style:text-properties': resolved.text_properties,
+      'style:paragraph-properties': resolved.paragraph_properties,
+    };
+  }
 
-    return style;
+  // Handles an element whose style names refer to other automatic styles,
+  // as headers and footers use the ones of styles.xml.
+  public handleWithStyles(
+    lookupStyle: StyleLookup,
+    nodeType: string,
+    value: any,
+  ) {
+    const previous = this.lookupStyle;
+    this.lookupStyle = lookupStyle;
+    try {
+      this.handle(nodeType, value);
+    } finally {
+      this.lookupStyle = previous;
+    }
   }
 
   get current() {
@@ -347,13 +425,16 @@ export class OdtParser {
   parse(files: any) {
     const contentTree = files.contentTree;
     const stylesTree = files.stylesTree;
+    // The wasm `OdtDocument` the trees come from, resolving style names.
+    const document = files.document;
+    const odtWasm = files.odtWasm;
 
     const handlers: Record<string, NodeHandler> = {
       ...getInlineNodesHandlers(),
       ...getBasicNodesHandlers(),
       ...getListNodesHandlers(),
       ...getTableNodesHandlers(),
-      'g': () => { // Test is: embedded-diagram-example.odt
+      'draw:g': () => { // Test is: embedded-diagram-example.odt
         // DrawG draw:g
         const node = ctx.createText(
           'INSTEAD OF EMBEDDED DIAGRAM ABOVE USE EMBEDDED DIAGRAM FROM DRIVE AND PUT LINK TO IT IN THE DESCRIPTION. See: https://github.com/mieweb/wikiGDrive/issues/353',
@@ -362,9 +443,11 @@ export class OdtParser {
           ctx.current.content.push(node);
         }
       },
-      'frame': (ctx: OdtStashContext, odtElement: any) => {
-        if (odtElement.object && odtElement.object['@href']) {
-          const fullPath = odtElement.object['@href'].replace(/^\.\//, '') +
+      'draw:frame': (ctx: OdtStashContext, odtElement: any) => {
+        const object = odtElement['draw:object'];
+        const image = odtElement['draw:image'];
+        if (object && object['@xlink:href']) {
+          const fullPath = object['@xlink:href'].replace(/^\.\//, '') +
             '/content.xml';
           if (files[fullPath]) {
             const content = new TextDecoder().decode(files[fullPath]);
@@ -376,9 +459,9 @@ export class OdtParser {
             return;
           }
         }
-        if (odtElement.image && odtElement.image['@href']) { // TODO links rewrite
-          const alt = odtElement.desc?.['$value'] || '';
-          const src = odtElement.image['@href'];
+        if (image && image['@xlink:href']) { // TODO links rewrite
+          const alt = odtElement['svg:desc']?.['$value'] || '';
+          const src = image['@xlink:href'];
           ctx.openNode();
           ctx.closeNode('image', {
             src,
@@ -387,42 +470,126 @@ export class OdtParser {
         }
       },
 
-      'annotation': () => {
+      'office:annotation': () => {
+        // ignore: true,
+      },
+      'office:annotation-end': () => {
         // ignore: true,
       },
-      'note': (ctx: OdtStashContext, value: any) => {
-        const citation = value['note-citation'];
-        const body = value['note-body'];
+      'text:note': (ctx: OdtStashContext, value: any) => {
+        const citation = value['text:note-citation'];
+        const body = value['text:note-body'];
 
         if (!citation || !body) {
           return;
         }
 
-        ctx.handle('text', { $value: [{ $text: citation.$value }] });
-        for (const para of body.p) {
+        ctx.handle('office:text', { $value: [{ $text: citation.$value }] });
+        iterateChildren(body.$value, (block) => {
+          // Lists and tables go between the paragraphs the note is split
+          // into.
+          if (block.tag !== 'text:p' && block.tag !== 'text:h') {
+            if (ctx.current.content.length > 0) {
+              ctx.closeNode('paragraph');
+            } else {
+              ctx.dropNode();
+            }
+            ctx.handle(block.tag, block.value);
+            ctx.openNode();
+            return;
+          }
           iterateChildren(
-            para.$value,
+            block.value.$value,
             (child) => ctx.handle(child.tag, child.value),
           );
           ctx.closeNode('paragraph');
           ctx.openNode();
-        }
+        });
       },
     };
 
+    const body = contentTree['office:body'];
+    const [first, ...switches]: MasterPageStart[] =
+      document.master_page_starts();
+    // Body items that start another master page. Their handlers close the
+    // footer of the previous page and open the header of the new one.
+    const startsAt = new Map<unknown, MasterPage>();
+    for (const start of switches) {
+      const item = bodyItem(body, start.path);
+      if (item !== undefined) {
+        startsAt.set(item, start.master_page);
+      }
+    }
+    let masterPage = first?.master_page;
+    for (const [tag, handler] of Object.entries(handlers)) {
+      handlers[tag] = (ctx: OdtStashContext, value: any) => {
+        const next = startsAt.get(value);
+        if (next) {
+          handleFooter(ctx, masterPage);
+          masterPage = next;
+          handleHeader(ctx, masterPage);
+        }
+        handler(ctx, value);
+      };
+    }
+
     const ctx = new OdtStashContext(
       this.schema,
       handlers,
       stylesTree,
-      contentTree['automatic-styles'],
+      contentTree['office:automatic-styles'],
+      (name, family) => document.resolve_style(name, family),
+      (table) => odtWasm.table_grid(table),
     );
+    // Headers and footers use the automatic styles of styles.xml only, whose
+    // names (P1, T1...) may also be used by content.xml.
+    const masterPageStyles: StyleLookup = (name, family) =>
+      document.resolve_master_page_style(name, family);
+    // A master page starts on a new page: it opens with its first page
+    // header, and the page before it closes with the usual footer.
+    const handleHeader = (ctx: OdtStashContext, page?: MasterPage) => {
+      const header = page?.['style:header-first'] ?? page?.['style:header'];
+      if (header) {
+        ctx.handleWithStyles(masterPageStyles, 'style:header', header);
+      }
+    };
+    const handleFooter = (ctx: OdtStashContext, page?: MasterPage) => {
+      const footer = page?.['style:footer'] ?? page?.['style:footer-first'];
+      if (footer) {
+        ctx.handleWithStyles(masterPageStyles, 'style:footer', footer);
+      }
+    };
 
     ctx.openNode();
-    ctx.handle('body', contentTree.body);
+    handleHeader(ctx, masterPage);
+    ctx.handle('office:body', body);
+    handleFooter(ctx, masterPage);
     return ctx.closeNode('doc');
   }
 }
 
+// Where a master page starts, as returned by `master_page_starts` of the
+// wasm `OdtDocument`.
+export interface MasterPageStart {
+  block: number;
+  path: number[];
+  master_page: MasterPage;
+}
+
+// The element of the body item at `path`, through `office:text` and the
+// sections it is nested in.
+function bodyItem(body: any, path: number[]): unknown {
+  let item: any = body['office:text'];
+  for (const index of path) {
+    const child = item?.$value?.[index];
+    if (child === undefined || child === 'Unknown') {
+      return undefined;
+    }
+    item = tagEnum(child).value;
+  }
+  return item;
+}
+
 function uniqMarks(marks: Mark[]): Mark[] {
   const retVal: Mark[] = [];
   for (const mark of marks) {
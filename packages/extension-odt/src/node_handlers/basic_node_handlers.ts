@@ -41,85 +41,177 @@ export function getInlineNodesHandlers(): Record<string, NodeHandler> {
         ctx.current.content.push(node);
       }
     },
-    's': (ctx: OdtStashContext, odtElement: any) => {
-      const chars = odtElement['@c'] || 1;
+    'text:s': (ctx: OdtStashContext, odtElement: any) => {
+      const chars = odtElement['@text:c'] || 1;
       const text = ' '.repeat(chars);
       const node = ctx.createText(text);
       if (node) {
         ctx.current.content.push(node);
       }
     },
-    'tab': (ctx: OdtStashContext, odtElement: any) => {
+    'text:tab': (ctx: OdtStashContext, odtElement: any) => {
       const node = ctx.createText('\t');
       if (node) {
         ctx.current.content.push(node);
       }
     },
-    'rect': (ctx: OdtStashContext, odtElement: any) => {
-      if (odtElement['@rel-width'] === '100%') {
+    'draw:rect': (ctx: OdtStashContext, odtElement: any) => {
+      if (odtElement['@style:rel-width'] === '100%') {
         ctx.openNode();
         ctx.closeNode('hr');
       }
     },
-    'line-break': (ctx: OdtStashContext, odtElement: any) => {
+    'text:line-break': (ctx: OdtStashContext, odtElement: any) => {
       ctx.openNode();
       ctx.closeNode('br');
     },
-    'soft-page-break': (ctx: OdtStashContext, odtElement: any) => {
+    'text:soft-page-break': (ctx: OdtStashContext, odtElement: any) => {
       ctx.openNode();
       ctx.closeNode('softbreak');
     },
-    'bookmark': (ctx: OdtStashContext, element: any) => { // bookmark for parent para
+    'text:bookmark': (ctx: OdtStashContext, element: any) => { // bookmark for parent para
       ctx.openNode();
       ctx.closeNode('node_bookmark', {
-        id: element['@name'],
+        id: element['@text:name'],
       });
     },
-    'bookmark-start': (ctx: OdtStashContext, element: any) => {
+    'text:bookmark-start': (ctx: OdtStashContext, element: any) => {
       // state.textMarks.add({
       //   markName: 'bookmark',
       //   markAttributes: {
-      //     id: element['@name'],
+      //     id: element['@text:name'],
       //   },
       // });
     },
-    'bookmark-end': (ctx: OdtStashContext, element: any) => {
+    'text:bookmark-end': (ctx: OdtStashContext, element: any) => {
       // state.textMarks.forEach((x) =>
       //   x.markName === 'bookmark' &&
-      //     x.markAttributes.id === element['@name']
+      //     x.markAttributes.id === element['@text:name']
       //     ? state.textMarks.delete(x)
       //     : x
       // );
     },
-    'change-start': (ctx: OdtStashContext, element: any) => {
+    'text:change-start': (ctx: OdtStashContext, element: any) => {
       ctx.openNode();
       ctx.closeNode('comment', {
-        id: element['@change-id'],
+        id: element['@text:change-id'],
         nesting: NESTING_OPENING,
       });
     },
-    'change-end': (ctx: OdtStashContext, element: any) => {
+    'text:change-end': (ctx: OdtStashContext, element: any) => {
       ctx.openNode();
       ctx.closeNode('comment', {
-        id: element['@change-id'],
+        id: element['@text:change-id'],
         nesting: NESTING_CLOSING,
       });
     },
+    'text:change': () => {
+      // Deleted text, kept in text:tracked-changes.
+    },
+    ...getFieldHandlers(),
   };
 }
 
+// Indexes other than the table of contents show their paragraphs as they
+// were generated.
+function handleIndexBody(ctx: OdtStashContext, value: any) {
+  const body = value['text:index-body'];
+  const title = body['text:index-title'];
+  for (const pElem of [...(title?.['text:p'] ?? []), ...body['text:p']]) {
+    ctx.handle('text:p', pElem);
+  }
+}
+
+// Fields show the value they had when the document was saved.
+function getFieldHandlers(): Record<string, NodeHandler> {
+  const showValue = (ctx: OdtStashContext, value: any) => {
+    if (value.$text) {
+      ctx.handle('$text', value.$text);
+    }
+  };
+  // Cross-references link to their bookmark or sequence.
+  const showReference = (ctx: OdtStashContext, value: any) => {
+    if (!value['@text:ref-name']) {
+      showValue(ctx, value);
+      return;
+    }
+    ctx.stash();
+    ctx.current.marks = [
+      ...ctx.current.marks,
+      ctx.schema.mark('link', { href: '#' + value['@text:ref-name'] }),
+    ];
+    showValue(ctx, value);
+    ctx.unstash();
+  };
+
+  return {
+    'text:page-number': showValue,
+    'text:date': showValue,
+    'text:time': showValue,
+    'text:author-name': showValue,
+    'text:title': showValue,
+    'text:chapter': showValue,
+    'text:variable-set': (ctx: OdtStashContext, value: any) => {
+      if (value['@text:display'] !== 'none') {
+        showValue(ctx, value);
+      }
+    },
+    'text:variable-get': showValue,
+    'text:user-field-get': showValue,
+    'text:sequence': (ctx: OdtStashContext, value: any) => {
+      if (value['@text:ref-name']) {
+        ctx.openNode();
+        ctx.closeNode('node_bookmark', { id: value['@text:ref-name'] });
+      }
+      showValue(ctx, value);
+    },
+    'text:bookmark-ref': showReference,
+    'text:sequence-ref': showReference,
+  };
+}
+
+// Headers and footers of master pages, without the empty paragraphs
+// documents keep there when they have none.
+function handleHeaderFooter(ctx: OdtStashContext, value: any) {
+  if (value['@style:display'] === false) {
+    return;
+  }
+  iterateChildren(value.$value, (child) => {
+    if (
+      (child.tag === 'text:p' || child.tag === 'text:h') &&
+      !child.value.$value?.length
+    ) {
+      return;
+    }
+    ctx.handle(child.tag, child.value);
+  });
+}
+
 export function getBasicNodesHandlers(): Record<string, NodeHandler> {
   return {
-    'body': (ctx: OdtStashContext, value: any) => {
-      ctx.handle('text', value.text);
+    'office:body': (ctx: OdtStashContext, value: any) => {
+      ctx.handle('office:text', value['office:text']);
     },
-    'text': (ctx: OdtStashContext, value: any) => {
+    'style:header': handleHeaderFooter,
+    'style:header-left': handleHeaderFooter,
+    'style:header-first': handleHeaderFooter,
+    'style:footer': handleHeaderFooter,
+    'style:footer-left': handleHeaderFooter,
+    'style:footer-first': handleHeaderFooter,
+    'office:text': (ctx: OdtStashContext, value: any) => {
       iterateChildren(
         value.$value,
         (child) => ctx.handle(child.tag, child.value),
       );
     },
-    'p': (ctx: OdtStashContext, value: any) => {
+    'text:section': (ctx: OdtStashContext, value: any) => {
+      // The content of a section flows with the body.
+      iterateChildren(
+        value.$value,
+        (child) => ctx.handle(child.tag, child.value),
+      );
+    },
+    'text:p': (ctx: OdtStashContext, value: any) => {
       const attrs: Record<string, any> = {};
 
       const style = ctx.getElementStyle(value);
@@ -147,17 +239,36 @@ export function getBasicNodesHandlers(): Record<string, NodeHandler> {
 
       ctx.closeNode(nodeType, attrs);
     },
+    'text:h': (ctx: OdtStashContext, value: any) => {
+      const attrs = {
+        level: parseInt(value['@text:outline-level']) || 1,
+      };
+
+      const style = ctx.getElementStyle(value);
+
+      ctx.openNode();
+      const marks = ctx.styleToMarks(style);
+      if (marks.length > 0) {
+        ctx.current.marks = [...ctx.current.marks, ...marks];
+      }
+
+      iterateChildren(value.$value, (child) => {
+        ctx.handle(child.tag, child.value);
+      });
+
+      ctx.closeNode('heading', attrs);
+    },
 
-    'table-of-content': (ctx: OdtStashContext, value: any) => {
+    'text:table-of-content': (ctx: OdtStashContext, value: any) => {
       const levels: number[] = [];
 
       let prevMarginLeft = -1;
-      for (const pElem of value['index-body']['p']) {
+      for (const pElem of value['text:index-body']['text:p']) {
         const style = ctx.getElementStyle(pElem) as any;
         let marginLeft = 0;
-        if ('paragraph-properties' in style) {
+        if ('style:paragraph-properties' in style) {
           marginLeft = inchesToMm(
-            style['paragraph-properties']['@margin-left'],
+            style['style:paragraph-properties']['@fo:margin-left'],
           );
         }
 
@@ -181,7 +292,7 @@ export function getBasicNodesHandlers(): Record<string, NodeHandler> {
           }
         }
         ctx.openNode();
-        ctx.handle('p', pElem);
+        ctx.handle('text:p', pElem);
         ctx.closeNode('list_item');
 
         prevMarginLeft = marginLeft;
@@ -200,9 +311,13 @@ export function getBasicNodesHandlers(): Record<string, NodeHandler> {
         }
       }
     },
+    'text:alphabetical-index': handleIndexBody,
+    'text:illustration-index': handleIndexBody,
+    'text:table-index': handleIndexBody,
+    'text:bibliography': handleIndexBody,
 
-    'span': (ctx: OdtStashContext, value: any) => {
-      const style = ctx.getElementStyle(value);
+    'text:span': (ctx: OdtStashContext, value: any) => {
+      const style = ctx.getElementStyle(value, 'text');
 
       const marks = ctx.styleToMarks(style);
       if (marks.length > 0) {
@@ -218,10 +333,10 @@ export function getBasicNodesHandlers(): Record<string, NodeHandler> {
         ctx.unstash();
       }
     },
-    'a': (ctx: OdtStashContext, value: any) => {
+    'text:a': (ctx: OdtStashContext, value: any) => {
       const attrs = {
-        href: value['@href'],
-        // title: tok.attrGet('title') || null,
+        href: value['@xlink:href'],
+        title: value['@office:title'] ?? undefined,
       };
 
       const markType = ctx.schema.mark('link', attrs);
@@ -26,7 +26,7 @@ function processListStyle(ctx: OdtStashContext, level: number) {
 
   for (let i = listTracker.listStack.length - 1; i >= 0; i--) {
     const list = listTracker.listStack[i];
-    if (!style['@name']) {
+    if (!style['@style:name']) {
       style = resolveListStyle(
         ctx.stylesTree,
         ctx.automaticStyles,
@@ -37,31 +37,29 @@ function processListStyle(ctx: OdtStashContext, level: number) {
 
   let nodeTypeName = 'bullet_list';
   if (style) {
-    const numLevelStyle = style['list-level-style-number'].find(
-      (levelStyle) => parseInt(String(levelStyle['@level'])) === level,
+    const numLevelStyle = style['text:list-level-style-number'].find(
+      (levelStyle) => parseInt(String(levelStyle['@text:level'])) === level,
     );
     if (numLevelStyle) {
-      attrs['type'] = numLevelStyle['@num-format'] || '1';
-      if (numLevelStyle['@start-value']) {
-        attrs['start'] = String(numLevelStyle['@start-value']);
+      attrs['type'] = numLevelStyle['@style:num-format'] || '1';
+      if (numLevelStyle['@text:start-value']) {
+        attrs['start'] = String(numLevelStyle['@text:start-value']);
       }
       nodeTypeName = 'ordered_list';
-      const marginLeft =
-        numLevelStyle['list-level-properties']['list-level-label-alignment'][
-          '@margin-left'
-        ];
+      const marginLeft = numLevelStyle['style:list-level-properties'][
+        'style:list-level-label-alignment'
+      ]['@fo:margin-left'];
       if (marginLeft) {
         attrs['odtMarginLeft'] = inchesToMm(marginLeft);
       }
     }
-    const bulletLevelStyle = style['list-level-style-bullet'].find(
-      (levelStyle) => parseInt(String(levelStyle['@level'])) === level,
+    const bulletLevelStyle = style['text:list-level-style-bullet'].find(
+      (levelStyle) => parseInt(String(levelStyle['@text:level'])) === level,
     );
     if (bulletLevelStyle) {
-      const marginLeft =
-        bulletLevelStyle['list-level-properties']['list-level-label-alignment'][
-          '@margin-left'
-        ];
+      const marginLeft = bulletLevelStyle['style:list-level-properties'][
+        'style:list-level-label-alignment'
+      ]['@fo:margin-left'];
       if (marginLeft) {
         attrs['odtMarginLeft'] = inchesToMm(marginLeft);
       }
@@ -77,9 +75,12 @@ function processListStyle(ctx: OdtStashContext, level: number) {
 // https://docs.oasis-open.org/office/OpenDocument/v1.4/OpenDocument-v1.4-part3-schema.html#element-text_list
 export function getListNodesHandlers(): Record<string, NodeHandler> {
   return {
-    'list': (ctx: OdtStashContext, odtElement) => {
+    'text:list': (ctx: OdtStashContext, odtElement) => {
       const listTracker = ctx.listTracker;
-      listTracker.pushList(odtElement['@xml:id'], odtElement['@style-name']);
+      listTracker.pushList(
+        odtElement['@xml:id'],
+        odtElement['@text:style-name'],
+      );
 
       const { nodeTypeName, attrs } = processListStyle(
         ctx,
@@ -91,17 +92,17 @@ export function getListNodesHandlers(): Record<string, NodeHandler> {
       if (odtElement['@xml:id']) {
         attrs['id'] = odtElement['@xml:id'];
       }
-      if (odtElement['@continue-list']) {
-        attrs['continue'] = odtElement['@continue-list'];
+      if (odtElement['@text:continue-list']) {
+        attrs['continue'] = odtElement['@text:continue-list'];
       }
-      if (odtElement['@continue-numbering']) {
+      if (odtElement['@text:continue-numbering']) {
         attrs['continue'] = '_last';
       }
 
       ctx.openNode();
 
-      const children = odtElement['list-item'].map((item: any) => ({
-        'list-item': item,
+      const children = odtElement['text:list-item'].map((item: any) => ({
+        'text:list-item': item,
       }));
 
       iterateChildren(children, (child) => {
@@ -116,7 +117,7 @@ export function getListNodesHandlers(): Record<string, NodeHandler> {
 
       listTracker.listStack.pop();
     },
-    'list-item': (ctx: OdtStashContext, odtElement: any) => {
+    'text:list-item': (ctx: OdtStashContext, odtElement: any) => {
       ctx.openNode();
       iterateChildren(
         odtElement.$value,
@@ -2,27 +2,31 @@ import { iterateChildren, NodeHandler, OdtStashContext } from '../OdtParser.ts';
 
 export function getTableNodesHandlers(): Record<string, NodeHandler> {
   return {
-    'table': (ctx: OdtStashContext, value: any) => {
+    // Repeated rows and cells are expanded by `table_grid`, which also
+    // drops the padding spreadsheets repeat up to their maximum size.
+    'table:table': (ctx: OdtStashContext, value: any) => {
+      const grid = ctx.tableGrid(value);
       ctx.openNode();
-      for (const item of value['table-row']) {
-        ctx.handle('table-row', item);
+      for (const row of grid.rows) {
+        ctx.openNode();
+        for (const slot of row) {
+          // Cells hidden by a span have no node of their own.
+          if (slot === 'Empty') {
+            ctx.openNode();
+            ctx.closeNode('table_cell');
+          } else if ('Anchor' in slot) {
+            const { cell, colspan, rowspan } = slot.Anchor;
+            ctx.openNode();
+            iterateChildren(
+              cell.$value,
+              (child) => ctx.handle(child.tag, child.value),
+            );
+            ctx.closeNode('table_cell', { colspan, rowspan });
+          }
+        }
+        ctx.closeNode('table_row');
       }
       ctx.closeNode('table');
     },
-    'table-row': (ctx: OdtStashContext, value: any) => {
-      ctx.openNode();
-      for (const item of value['table-cell']) {
-        ctx.handle('table-cell', item);
-      }
-      ctx.closeNode('table_row');
-    },
-    'table-cell': (ctx: OdtStashContext, value: any) => {
-      ctx.openNode();
-      iterateChildren(
-        value.$value,
-        (child) => ctx.handle(child.tag, child.value),
-      );
-      ctx.closeNode('table_cell');
-    },
   };
 }
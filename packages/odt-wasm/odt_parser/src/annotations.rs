@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::OfficeAnnotation;
+use crate::walk::markers;
+use crate::walk::Marker;
+use crate::walk::TextPosition;
+
+/// A comment with the text it is anchored to. `start == end` for comments
+/// on a point.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct AnnotationRange<'a> {
+  pub annotation: &'a OfficeAnnotation,
+  pub start: TextPosition,
+  pub end: TextPosition,
+}
+
+impl DocumentContent {
+  /// Every comment of the body in document order, with its range resolved
+  /// from the `office:annotation-end` of the same name.
+  pub fn annotations(&self) -> Vec<AnnotationRange<'_>> {
+    let mut ranges: Vec<AnnotationRange> = Vec::new();
+    // Ranges waiting for their end, by name.
+    let mut open: HashMap<&str, usize> = HashMap::new();
+
+    for (position, marker) in markers(&self.body.text) {
+      match marker {
+        Marker::AnnotationStart(annotation) => {
+          if let Some(name) = &annotation.name {
+            open.insert(name, ranges.len());
+          }
+          ranges.push(AnnotationRange {
+            annotation,
+            start: position,
+            end: position,
+          });
+        }
+        Marker::AnnotationEnd(end) => {
+          if let Some(index) = open.remove(end.name.as_str()) {
+            ranges[index].end = position;
+          }
+        }
+        _ => {}
+      }
+    }
+    ranges
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::ods_structs::OfficeAnnotationItem;
+  use crate::writer::write_content;
+
+  const BODY: &str = r#"
+    <text:p>Hello <office:annotation office:name="__Annotation__1">
+        <dc:creator>Ann Smith</dc:creator>
+        <dc:date>2024-03-01T10:20:30</dc:date>
+        <text:p>Reword <text:span>this</text:span></text:p>
+        <text:list><text:list-item><text:p>a point</text:p></text:list-item></text:list>
+      </office:annotation>big<text:s text:c="2"/><text:span>wide</text:span></text:p>
+    <text:p>wor<office:annotation-end office:name="__Annotation__1"/>ld<office:annotation>
+        <dc:creator>Bob</dc:creator><text:p>Nice</text:p>
+      </office:annotation></text:p>"#;
+
+  #[test]
+  fn resolves_ranges() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let annotations = content.annotations();
+    assert_eq!(annotations.len(), 2);
+
+    let first = &annotations[0];
+    assert_eq!(first.annotation.creator.as_deref(), Some("Ann Smith"));
+    assert_eq!(
+      first.annotation.date.as_deref(),
+      Some("2024-03-01T10:20:30")
+    );
+    assert!(matches!(
+      first.annotation.list[..],
+      [
+        OfficeAnnotationItem::TextParagraph(_),
+        OfficeAnnotationItem::TextList(_)
+      ]
+    ));
+    assert_eq!(
+      (first.start, first.end),
+      (
+        TextPosition {
+          block: 0,
+          offset: 6
+        },
+        TextPosition {
+          block: 1,
+          offset: 3
+        }
+      )
+    );
+
+    let second = &annotations[1];
+    assert_eq!(second.annotation.name, None);
+    assert_eq!(second.start, second.end);
+    assert_eq!(
+      second.start,
+      TextPosition {
+        block: 1,
+        offset: 5
+      }
+    );
+  }
+
+  #[test]
+  fn resolves_ranges_in_links() {
+    let content = parse_content(content_xml(
+      r#"
+    <text:p>See <text:a xlink:href="https://example.com"><office:annotation office:name="c1">
+        <dc:creator>Ann</dc:creator><text:p>Dead link?</text:p>
+      </office:annotation>this<office:annotation-end office:name="c1"/> page</text:a></text:p>"#,
+    ))
+    .unwrap();
+    let annotations = content.annotations();
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].annotation.creator.as_deref(), Some("Ann"));
+    assert_eq!(
+      (annotations[0].start.offset, annotations[0].end.offset),
+      (4, 8)
+    );
+
+    let xml = write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn writes_annotations_back() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let xml = String::from_utf8(write_content(&content).unwrap()).unwrap();
+    assert!(xml.contains(
+      r#"<office:annotation office:name="__Annotation__1"><dc:creator>Ann Smith</dc:creator><dc:date>2024-03-01T10:20:30</dc:date><text:p>Reword <text:span>this</text:span></text:p>"#
+    ));
+    assert!(xml.contains(
+      r#"wor<office:annotation-end office:name="__Annotation__1"/>ld"#
+    ));
+    assert_eq!(parse_content(xml.into_bytes()).unwrap(), content);
+  }
+}
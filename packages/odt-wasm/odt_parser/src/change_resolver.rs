@@ -0,0 +1,891 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::mem;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ods_structs::ChangedRegion;
+use crate::ods_structs::DeletionItem;
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::NoteBodyItem;
+use crate::ods_structs::OfficeTextItem;
+use crate::ods_structs::TableCellItem;
+use crate::ods_structs::TableRowItem;
+use crate::ods_structs::TableTable;
+use crate::ods_structs::TextLink;
+use crate::ods_structs::TextLinkItem;
+use crate::ods_structs::TextList;
+use crate::ods_structs::TextListItemEnum;
+use crate::ods_structs::TextParagraphItem;
+use crate::ods_structs::TextSpan;
+use crate::ods_structs::TextSpanItem;
+use crate::tracked_changes::ChangeKind;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Decision {
+  Accept,
+  Reject,
+}
+
+/// Selects changed regions by their `office:change-info`. An empty filter
+/// selects every region.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ChangeFilter {
+  /// Only changes by this `dc:creator`.
+  pub author: Option<String>,
+  /// Only changes dated strictly before this ISO 8601 date.
+  pub before: Option<String>,
+}
+
+impl ChangeFilter {
+  pub fn matches(&self, region: &ChangedRegion) -> bool {
+    let info = region.change_info();
+    let creator = info.and_then(|info| info.creator.as_deref());
+    let date = info.and_then(|info| info.date.as_deref());
+    self
+      .author
+      .as_deref()
+      .is_none_or(|author| creator == Some(author))
+      && self
+        .before
+        .as_deref()
+        // ISO 8601 dates of the same form sort as strings.
+        .is_none_or(|before| date.is_some_and(|date| date < before))
+  }
+}
+
+impl DocumentContent {
+  /// A copy of the content with the changes selected by `filter` accepted
+  /// or rejected, their markers and regions removed. Other changes stay
+  /// tracked.
+  ///
+  /// Rejecting an insertion removes its text, joining the paragraphs it
+  /// started and ended in. Rejecting a deletion puts the deleted content
+  /// back at its `text:change`, splitting the paragraph when it holds
+  /// several blocks. Format changes only lose their markers either way, as
+  /// ODF does not keep the previous formatting.
+  pub fn resolve_changes(
+    &self,
+    decision: Decision,
+    filter: &ChangeFilter,
+  ) -> DocumentContent {
+    let mut content = self.clone();
+    let Some(tracked) = &mut content.body.text.tracked_changes else {
+      return content;
+    };
+
+    let mut resolver = Resolver::default();
+    tracked.regions.retain(|region| {
+      let (Some(id), Some(kind)) = (region.id(), region.kind()) else {
+        return true;
+      };
+      if !filter.matches(region) {
+        return true;
+      }
+      let action = match (kind, decision) {
+        (ChangeKind::Insertion, Decision::Reject) => Action::Remove,
+        (ChangeKind::Deletion, Decision::Reject) => Action::Restore(
+          region
+            .deletion
+            .as_ref()
+            .map(|deletion| deletion.list.clone())
+            .unwrap_or_default(),
+        ),
+        _ => Action::Drop,
+      };
+      resolver.actions.insert(id.to_string(), action);
+      false
+    });
+
+    content.body.text.list =
+      resolver.blocks(mem::take(&mut content.body.text.list));
+    content
+  }
+
+  /// See [`DocumentContent::resolve_changes`].
+  pub fn accept_all_changes(&self) -> DocumentContent {
+    self.resolve_changes(Decision::Accept, &ChangeFilter::default())
+  }
+
+  /// See [`DocumentContent::resolve_changes`].
+  pub fn reject_all_changes(&self) -> DocumentContent {
+    self.resolve_changes(Decision::Reject, &ChangeFilter::default())
+  }
+}
+
+enum Action {
+  /// Remove the markers only.
+  Drop,
+  /// Remove the markers and the content between them.
+  Remove,
+  /// Put the deleted blocks back in place of the marker.
+  Restore(Vec<DeletionItem>),
+}
+
+#[derive(Default)]
+struct Resolver {
+  actions: HashMap<String, Action>,
+  /// Insertions being removed, between their start and end.
+  removing: HashSet<String>,
+}
+
+/// What a paragraph becomes. The first piece keeps the paragraph, blocks
+/// restored from a deletion follow it.
+enum Piece {
+  /// Items of the paragraph, or of a copy of it after restored blocks.
+  Inline(Vec<TextParagraphItem>),
+  Block(DeletionItem),
+}
+
+enum SpanPiece {
+  Items(Vec<TextSpanItem>),
+  Restore(Vec<DeletionItem>),
+}
+
+/// A block container item: the body, table cells, list items and notes.
+trait Block: Clone + Sized {
+  /// Items of a paragraph or heading.
+  fn inline(&mut self) -> Option<&mut Vec<TextParagraphItem>>;
+  /// Resolves changes in the blocks nested in a table or list.
+  fn nested(&mut self, resolver: &mut Resolver);
+  fn from_deleted(block: DeletionItem) -> Option<Self>;
+}
+
+impl Resolver {
+  fn removing(&self) -> bool {
+    !self.removing.is_empty()
+  }
+
+  fn blocks<B: Block>(&mut self, items: Vec<B>) -> Vec<B> {
+    let mut out: Vec<B> = Vec::new();
+    // The paragraph an insertion being removed started in, which takes the
+    // text after its end.
+    let mut join: Option<usize> = None;
+
+    for mut block in items {
+      let removing = self.removing();
+      let Some(list) = block.inline() else {
+        block.nested(self);
+        // Whole blocks inside a removed insertion go with it.
+        if !(removing && self.removing()) {
+          out.push(block);
+        }
+        if !self.removing() {
+          join = None;
+        }
+        continue;
+      };
+
+      let list = mem::take(list);
+      let shell = block.clone();
+      let mut pieces = self.paragraph(list).into_iter();
+      let Some(Piece::Inline(first)) = pieces.next() else {
+        unreachable!("a paragraph starts with its own items");
+      };
+      match join.and_then(|index| out[index].inline()) {
+        Some(target) if removing => target.extend(first),
+        _ if removing && self.removing() && first.is_empty() => {}
+        _ => {
+          *block.inline().unwrap() = first;
+          out.push(block);
+        }
+      }
+      for piece in pieces {
+        let block = match piece {
+          Piece::Inline(items) => {
+            let mut copy = shell.clone();
+            *copy.inline().unwrap() = items;
+            Some(copy)
+          }
+          Piece::Block(block) => B::from_deleted(block),
+        };
+        out.extend(block);
+      }
+
+      join = match join {
+        Some(index) if removing && self.removing() => Some(index),
+        _ if self.removing() => {
+          out.iter_mut().rposition(|block| block.inline().is_some())
+        }
+        _ => None,
+      };
+    }
+    out
+  }
+
+  fn paragraph(&mut self, items: Vec<TextParagraphItem>) -> Vec<Piece> {
+    let mut pieces = vec![Piece::Inline(Vec::new())];
+    for item in items {
+      match item {
+        TextParagraphItem::TextChangeStart(marker) => {
+          if !self.start(&marker.change_id) {
+            tail(&mut pieces).push(TextParagraphItem::TextChangeStart(marker));
+          }
+        }
+        TextParagraphItem::TextChangeEnd(marker) => {
+          if !self.end(&marker.change_id) {
+            tail(&mut pieces).push(TextParagraphItem::TextChangeEnd(marker));
+          }
+        }
+        TextParagraphItem::TextChange(marker) => {
+          match self.point(&marker.change_id) {
+            Some(blocks) => restore(&mut pieces, blocks),
+            None if self.has_action(&marker.change_id) => {}
+            None => {
+              tail(&mut pieces).push(TextParagraphItem::TextChange(marker))
+            }
+          }
+        }
+        TextParagraphItem::TextSpan(span) => {
+          let removing = self.removing();
+          let empty = span.list.is_empty();
+          let TextSpan {
+            list,
+            style_name,
+            extra,
+          } = span;
+          for piece in self.span(list) {
+            match piece {
+              SpanPiece::Items(list) => {
+                if !list.is_empty() || (empty && !removing) {
+                  tail(&mut pieces).push(TextParagraphItem::TextSpan(
+                    TextSpan {
+                      list,
+                      style_name: style_name.clone(),
+                      extra: extra.clone(),
+                    },
+                  ));
+                }
+              }
+              SpanPiece::Restore(blocks) => restore(&mut pieces, blocks),
+            }
+          }
+        }
+        TextParagraphItem::TextLink(link) => {
+          for piece in self.link(link) {
+            match piece {
+              Piece::Inline(items) => tail(&mut pieces).extend(items),
+              piece => pieces.push(piece),
+            }
+          }
+        }
+        _ if self.removing() => {}
+        TextParagraphItem::TextNote(mut note) => {
+          if let Some(body) = &mut note.note_body {
+            body.list = self.blocks(mem::take(&mut body.list));
+          }
+          tail(&mut pieces).push(TextParagraphItem::TextNote(note));
+        }
+        item => tail(&mut pieces).push(item),
+      }
+    }
+    pieces
+  }
+
+  // Spans cannot hold blocks: restored deletions split them, see
+  // `paragraph`.
+  fn span(&mut self, items: Vec<TextSpanItem>) -> Vec<SpanPiece> {
+    let mut pieces = Vec::new();
+    let mut current = Vec::new();
+    for item in items {
+      match item {
+        TextSpanItem::TextChangeStart(marker) => {
+          if !self.start(&marker.change_id) {
+            current.push(TextSpanItem::TextChangeStart(marker));
+          }
+        }
+        TextSpanItem::TextChangeEnd(marker) => {
+          if !self.end(&marker.change_id) {
+            current.push(TextSpanItem::TextChangeEnd(marker));
+          }
+        }
+        TextSpanItem::TextChange(marker) => {
+          match self.point(&marker.change_id) {
+            Some(blocks) => {
+              pieces.push(SpanPiece::Items(mem::take(&mut current)));
+              pieces.push(SpanPiece::Restore(blocks));
+            }
+            None if self.has_action(&marker.change_id) => {}
+            None => current.push(TextSpanItem::TextChange(marker)),
+          }
+        }
+        _ if self.removing() => {}
+        item => current.push(item),
+      }
+    }
+    pieces.push(SpanPiece::Items(current));
+    pieces
+  }
+
+  // Like `paragraph`: the first deleted paragraph goes back into the link,
+  // other restored blocks split it.
+  fn link(&mut self, link: TextLink) -> Vec<Piece> {
+    let removing = self.removing();
+    let empty = link.list.is_empty();
+    let TextLink { list, .. } = link;
+    let shell = TextLink {
+      list: Vec::new(),
+      ..link
+    };
+    let mut links = LinkPieces {
+      shell,
+      pieces: vec![Piece::Inline(Vec::new())],
+      current: Vec::new(),
+      written: false,
+    };
+    for item in list {
+      match item {
+        TextLinkItem::TextSpan(mut span) => {
+          let removing = self.removing();
+          let empty = span.list.is_empty();
+          for piece in self.span(mem::take(&mut span.list)) {
+            match piece {
+              SpanPiece::Items(list) => {
+                if !list.is_empty() || (empty && !removing) {
+                  links.current.push(TextLinkItem::TextSpan(TextSpan {
+                    list,
+                    ..span.clone()
+                  }));
+                }
+              }
+              SpanPiece::Restore(blocks) => links.restore(blocks),
+            }
+          }
+        }
+        TextLinkItem::TextChangeStart(marker) => {
+          if !self.start(&marker.change_id) {
+            links.current.push(TextLinkItem::TextChangeStart(marker));
+          }
+        }
+        TextLinkItem::TextChangeEnd(marker) => {
+          if !self.end(&marker.change_id) {
+            links.current.push(TextLinkItem::TextChangeEnd(marker));
+          }
+        }
+        TextLinkItem::TextChange(marker) => {
+          match self.point(&marker.change_id) {
+            Some(blocks) => links.restore(blocks),
+            None if self.has_action(&marker.change_id) => {}
+            None => links.current.push(TextLinkItem::TextChange(marker)),
+          }
+        }
+        _ if self.removing() => {}
+        item => links.current.push(item),
+      }
+    }
+    links.flush();
+    if !links.written && empty && !removing {
+      tail(&mut links.pieces).push(TextParagraphItem::TextLink(links.shell));
+    }
+    links.pieces
+  }
+
+  fn has_action(&self, id: &str) -> bool {
+    self.actions.contains_key(id)
+  }
+
+  /// Whether the `text:change-start` goes.
+  fn start(&mut self, id: &str) -> bool {
+    match self.actions.get(id) {
+      Some(Action::Remove) => {
+        self.removing.insert(id.to_string());
+        true
+      }
+      action => action.is_some(),
+    }
+  }
+
+  /// Whether the `text:change-end` goes.
+  fn end(&mut self, id: &str) -> bool {
+    self.removing.remove(id);
+    self.has_action(id)
+  }
+
+  /// The blocks to put back for a `text:change`. Not inside a removed
+  /// insertion, where they would go again.
+  fn point(&self, id: &str) -> Option<Vec<DeletionItem>> {
+    match self.actions.get(id) {
+      Some(Action::Restore(blocks)) if !self.removing() => Some(blocks.clone()),
+      _ => None,
+    }
+  }
+
+  fn table(&mut self, table: &mut TableTable) {
+    let rows = table
+      .header_rows
+      .iter_mut()
+      .flat_map(|header| header.rows.iter_mut())
+      .chain(table.rows.iter_mut());
+    for row in rows {
+      for item in &mut row.list {
+        if let TableRowItem::TableCell(cell)
+        | TableRowItem::CoveredTableCell(cell) = item
+        {
+          cell.list = self.blocks(mem::take(&mut cell.list));
+        }
+      }
+    }
+  }
+
+  fn list(&mut self, list: &mut TextList) {
+    for item in &mut list.list {
+      item.list = self.blocks(mem::take(&mut item.list));
+    }
+  }
+}
+
+/// Items the text continues with.
+fn tail(pieces: &mut Vec<Piece>) -> &mut Vec<TextParagraphItem> {
+  if !matches!(
+    pieces.last(),
+    Some(
+      Piece::Inline(_)
+        | Piece::Block(
+          DeletionItem::TextParagraph(_) | DeletionItem::TextHeading(_)
+        )
+    )
+  ) {
+    pieces.push(Piece::Inline(Vec::new()));
+  }
+  match pieces.last_mut() {
+    Some(Piece::Inline(items)) => items,
+    Some(Piece::Block(DeletionItem::TextParagraph(paragraph))) => {
+      &mut paragraph.list
+    }
+    Some(Piece::Block(DeletionItem::TextHeading(heading))) => &mut heading.list,
+    _ => unreachable!(),
+  }
+}
+
+/// The first deleted paragraph joins the text before the marker, the text
+/// after it continues the last one.
+fn restore(pieces: &mut Vec<Piece>, blocks: Vec<DeletionItem>) {
+  for (index, block) in blocks.into_iter().enumerate() {
+    match block {
+      DeletionItem::TextParagraph(paragraph) if index == 0 => {
+        tail(pieces).extend(paragraph.list)
+      }
+      DeletionItem::TextHeading(heading) if index == 0 => {
+        tail(pieces).extend(heading.list)
+      }
+      block => pieces.push(Piece::Block(block)),
+    }
+  }
+}
+
+/// A link being split by the blocks restored inside it.
+struct LinkPieces {
+  /// The link without its items.
+  shell: TextLink,
+  pieces: Vec<Piece>,
+  /// Items of the link since the last split.
+  current: Vec<TextLinkItem>,
+  /// Whether a part of the link was kept.
+  written: bool,
+}
+
+impl LinkPieces {
+  fn flush(&mut self) {
+    if self.current.is_empty() {
+      return;
+    }
+    let link = TextLink {
+      list: mem::take(&mut self.current),
+      ..self.shell.clone()
+    };
+    tail(&mut self.pieces).push(TextParagraphItem::TextLink(link));
+    self.written = true;
+  }
+
+  /// Like `restore`, the items links cannot hold going after the link.
+  fn restore(&mut self, blocks: Vec<DeletionItem>) {
+    for (index, block) in blocks.into_iter().enumerate() {
+      let items = match block {
+        DeletionItem::TextParagraph(paragraph) if index == 0 => paragraph.list,
+        DeletionItem::TextHeading(heading) if index == 0 => heading.list,
+        block => {
+          self.flush();
+          self.pieces.push(Piece::Block(block));
+          continue;
+        }
+      };
+      for item in items {
+        self.push(item);
+      }
+    }
+  }
+
+  /// Adds a restored item to the link, or after it if links cannot hold
+  /// it.
+  fn push(&mut self, item: TextParagraphItem) {
+    let item = match item {
+      TextParagraphItem::TextSpace(space) => TextLinkItem::TextSpace(space),
+      TextParagraphItem::TextTab => TextLinkItem::TextTab,
+      TextParagraphItem::TextSpan(span) => TextLinkItem::TextSpan(span),
+      TextParagraphItem::OfficeAnnotation(annotation) => {
+        TextLinkItem::OfficeAnnotation(annotation)
+      }
+      TextParagraphItem::OfficeAnnotationEnd(end) => {
+        TextLinkItem::OfficeAnnotationEnd(end)
+      }
+      TextParagraphItem::TextChangeStart(marker) => {
+        TextLinkItem::TextChangeStart(marker)
+      }
+      TextParagraphItem::TextChangeEnd(marker) => {
+        TextLinkItem::TextChangeEnd(marker)
+      }
+      TextParagraphItem::TextChange(marker) => TextLinkItem::TextChange(marker),
+      TextParagraphItem::TextPageNumber(field) => {
+        TextLinkItem::TextPageNumber(field)
+      }
+      TextParagraphItem::TextDate(field) => TextLinkItem::TextDate(field),
+      TextParagraphItem::TextTime(field) => TextLinkItem::TextTime(field),
+      TextParagraphItem::TextAuthorName(field) => {
+        TextLinkItem::TextAuthorName(field)
+      }
+      TextParagraphItem::TextTitle(field) => TextLinkItem::TextTitle(field),
+      TextParagraphItem::TextChapter(field) => TextLinkItem::TextChapter(field),
+      TextParagraphItem::TextBookmarkRef(field) => {
+        TextLinkItem::TextBookmarkRef(field)
+      }
+      TextParagraphItem::TextSequence(field) => {
+        TextLinkItem::TextSequence(field)
+      }
+      TextParagraphItem::TextSequenceRef(field) => {
+        TextLinkItem::TextSequenceRef(field)
+      }
+      TextParagraphItem::TextVariableSet(field) => {
+        TextLinkItem::TextVariableSet(field)
+      }
+      TextParagraphItem::TextVariableGet(field) => {
+        TextLinkItem::TextVariableGet(field)
+      }
+      TextParagraphItem::TextUserFieldGet(field) => {
+        TextLinkItem::TextUserFieldGet(field)
+      }
+      TextParagraphItem::Text(text) => TextLinkItem::Text(text),
+      TextParagraphItem::DrawFrame(frame) => TextLinkItem::DrawFrame(frame),
+      TextParagraphItem::Raw(raw) => TextLinkItem::Raw(raw),
+      item => {
+        self.flush();
+        tail(&mut self.pieces).push(item);
+        return;
+      }
+    };
+    self.current.push(item);
+  }
+}
+
+impl Block for OfficeTextItem {
+  fn inline(&mut self) -> Option<&mut Vec<TextParagraphItem>> {
+    match self {
+      OfficeTextItem::TextParagraph(paragraph) => Some(&mut paragraph.list),
+      OfficeTextItem::TextHeading(heading) => Some(&mut heading.list),
+      _ => None,
+    }
+  }
+
+  fn nested(&mut self, resolver: &mut Resolver) {
+    match self {
+      OfficeTextItem::TableTable(table) => resolver.table(table),
+      OfficeTextItem::TextList(list) => resolver.list(list),
+      OfficeTextItem::TextSection(section) => {
+        section.list = resolver.blocks(mem::take(&mut section.list));
+      }
+      _ => {}
+    }
+  }
+
+  fn from_deleted(block: DeletionItem) -> Option<Self> {
+    match block {
+      DeletionItem::TextParagraph(paragraph) => {
+        Some(OfficeTextItem::TextParagraph(paragraph))
+      }
+      DeletionItem::TextHeading(heading) => {
+        Some(OfficeTextItem::TextHeading(heading))
+      }
+      DeletionItem::TextList(list) => Some(OfficeTextItem::TextList(list)),
+      DeletionItem::TableTable(table) => {
+        Some(OfficeTextItem::TableTable(table))
+      }
+      DeletionItem::Raw(xml) => Some(OfficeTextItem::Raw(xml)),
+      DeletionItem::Unknown => None,
+    }
+  }
+}
+
+impl Block for TableCellItem {
+  fn inline(&mut self) -> Option<&mut Vec<TextParagraphItem>> {
+    match self {
+      TableCellItem::TextParagraph(paragraph) => Some(&mut paragraph.list),
+      TableCellItem::TextHeading(heading) => Some(&mut heading.list),
+      _ => None,
+    }
+  }
+
+  fn nested(&mut self, resolver: &mut Resolver) {
+    match self {
+      TableCellItem::TableTable(table) => resolver.table(table),
+      TableCellItem::TextList(list) => resolver.list(list),
+      _ => {}
+    }
+  }
+
+  fn from_deleted(block: DeletionItem) -> Option<Self> {
+    match block {
+      DeletionItem::TextParagraph(paragraph) => {
+        Some(TableCellItem::TextParagraph(paragraph))
+      }
+      DeletionItem::TextHeading(heading) => {
+        Some(TableCellItem::TextHeading(heading))
+      }
+      DeletionItem::TextList(list) => Some(TableCellItem::TextList(list)),
+      DeletionItem::TableTable(table) => Some(TableCellItem::TableTable(table)),
+      DeletionItem::Raw(xml) => Some(TableCellItem::Raw(xml)),
+      DeletionItem::Unknown => None,
+    }
+  }
+}
+
+impl Block for TextListItemEnum {
+  fn inline(&mut self) -> Option<&mut Vec<TextParagraphItem>> {
+    match self {
+      TextListItemEnum::TextParagraph(paragraph) => Some(&mut paragraph.list),
+      TextListItemEnum::TextHeading(heading) => Some(&mut heading.list),
+      _ => None,
+    }
+  }
+
+  fn nested(&mut self, resolver: &mut Resolver) {
+    if let TextListItemEnum::TextList(list) = self {
+      resolver.list(list);
+    }
+  }
+
+  // List items cannot hold tables.
+  fn from_deleted(block: DeletionItem) -> Option<Self> {
+    match block {
+      DeletionItem::TextParagraph(paragraph) => {
+        Some(TextListItemEnum::TextParagraph(paragraph))
+      }
+      DeletionItem::TextHeading(heading) => {
+        Some(TextListItemEnum::TextHeading(heading))
+      }
+      DeletionItem::TextList(list) => Some(TextListItemEnum::TextList(list)),
+      DeletionItem::Raw(xml) => Some(TextListItemEnum::Raw(xml)),
+      DeletionItem::TableTable(_) | DeletionItem::Unknown => None,
+    }
+  }
+}
+
+impl Block for NoteBodyItem {
+  fn inline(&mut self) -> Option<&mut Vec<TextParagraphItem>> {
+    match self {
+      NoteBodyItem::TextParagraph(paragraph) => Some(&mut paragraph.list),
+      NoteBodyItem::TextHeading(heading) => Some(&mut heading.list),
+      _ => None,
+    }
+  }
+
+  fn nested(&mut self, resolver: &mut Resolver) {
+    match self {
+      NoteBodyItem::TableTable(table) => resolver.table(table),
+      NoteBodyItem::TextList(list) => resolver.list(list),
+      _ => {}
+    }
+  }
+
+  fn from_deleted(block: DeletionItem) -> Option<Self> {
+    match block {
+      DeletionItem::TextParagraph(paragraph) => {
+        Some(NoteBodyItem::TextParagraph(paragraph))
+      }
+      DeletionItem::TextHeading(heading) => {
+        Some(NoteBodyItem::TextHeading(heading))
+      }
+      DeletionItem::TextList(list) => Some(NoteBodyItem::TextList(list)),
+      DeletionItem::TableTable(table) => Some(NoteBodyItem::TableTable(table)),
+      DeletionItem::Raw(xml) => Some(NoteBodyItem::Raw(xml)),
+      DeletionItem::Unknown => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::open;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::writer::write_content;
+
+  fn content(regions: &str, body: &str) -> DocumentContent {
+    let body = format!(
+      "<text:tracked-changes>{}</text:tracked-changes>{}",
+      regions, body
+    );
+    parse_content(content_xml(&body)).unwrap()
+  }
+
+  // The body as written, without the tracked changes.
+  fn body(content: &DocumentContent) -> String {
+    let xml = String::from_utf8(write_content(content).unwrap()).unwrap();
+    let start = match xml.find("</text:tracked-changes>") {
+      Some(index) => index + "</text:tracked-changes>".len(),
+      None => {
+        let index = xml.find("<text:tracked-changes").unwrap();
+        index + xml[index..].find("/>").unwrap() + 2
+      }
+    };
+    let end = xml.find("</office:text>").unwrap();
+    xml[start..end].to_string()
+  }
+
+  const REGIONS: &str = r#"
+<text:changed-region text:id="ct1"><text:insertion><office:change-info>
+  <dc:creator>Ann</dc:creator><dc:date>2024-01-01T00:00:00</dc:date>
+</office:change-info></text:insertion></text:changed-region>
+<text:changed-region text:id="ct2"><text:deletion><office:change-info>
+  <dc:creator>Bob</dc:creator><dc:date>2024-02-01T00:00:00</dc:date>
+</office:change-info><text:p>X</text:p></text:deletion></text:changed-region>
+<text:changed-region text:id="ct3"><text:format-change><office:change-info/></text:format-change></text:changed-region>"#;
+
+  const BODY: &str = r#"<text:p>Hi <text:change-start text:change-id="ct1"/>new<text:change-end text:change-id="ct1"/> text</text:p><text:p>a<text:span>b<text:change text:change-id="ct2"/>c</text:span><text:change-start text:change-id="ct3"/>d<text:change-end text:change-id="ct3"/></text:p>"#;
+
+  #[test]
+  fn accepts_and_rejects_all() {
+    let original = content(REGIONS, BODY);
+
+    let accepted = original.accept_all_changes();
+    assert_eq!(
+      body(&accepted),
+      "<text:p>Hi new text</text:p><text:p>a<text:span>bc</text:span>d</text:p>"
+    );
+    let tracked = accepted.body.text.tracked_changes.as_ref().unwrap();
+    assert!(tracked.regions.is_empty());
+
+    let rejected = original.reject_all_changes();
+    assert_eq!(
+      body(&rejected),
+      "<text:p>Hi  text</text:p><text:p>a<text:span>b</text:span>X<text:span>c</text:span>d</text:p>"
+    );
+
+    // The input is untouched.
+    assert_eq!(original, content(REGIONS, BODY));
+  }
+
+  #[test]
+  fn filters_by_author_and_date() {
+    let original = content(REGIONS, BODY);
+    let by_ann = ChangeFilter {
+      author: Some("Ann".to_string()),
+      before: None,
+    };
+    let resolved = original.resolve_changes(Decision::Reject, &by_ann);
+    let regions = &resolved.body.text.tracked_changes.as_ref().unwrap().regions;
+    let ids: Vec<_> = regions.iter().map(|region| region.id()).collect();
+    assert_eq!(ids, vec![Some("ct2"), Some("ct3")]);
+    assert!(body(&resolved).starts_with("<text:p>Hi  text</text:p>"));
+    assert!(body(&resolved).contains(r#"<text:change text:change-id="ct2"/>"#));
+
+    // Undated changes are never before a date.
+    let before = ChangeFilter {
+      author: None,
+      before: Some("2024-03-01".to_string()),
+    };
+    let resolved = original.resolve_changes(Decision::Accept, &before);
+    let regions = &resolved.body.text.tracked_changes.as_ref().unwrap().regions;
+    let ids: Vec<_> = regions.iter().map(|region| region.id()).collect();
+    assert_eq!(ids, vec![Some("ct3")]);
+  }
+
+  #[test]
+  fn joins_and_splits_paragraphs() {
+    let regions = r#"
+<text:changed-region text:id="i"><text:insertion><office:change-info/></text:insertion></text:changed-region>
+<text:changed-region text:id="d"><text:deletion><office:change-info/>
+  <text:p>X</text:p><text:list><text:list-item><text:p>Y</text:p></text:list-item></text:list><text:h>Z</text:h>
+</text:deletion></text:changed-region>"#;
+    let original = content(
+      regions,
+      r#"<text:p>A<text:change-start text:change-id="i"/>B</text:p><text:p>C</text:p><text:p>D<text:change-end text:change-id="i"/>E</text:p><text:h>a<text:change text:change-id="d"/>b</text:h>"#,
+    );
+
+    assert_eq!(
+      body(&original.accept_all_changes()),
+      "<text:p>AB</text:p><text:p>C</text:p><text:p>DE</text:p><text:h>ab</text:h>"
+    );
+    assert_eq!(
+      body(&original.reject_all_changes()),
+      "<text:p>AE</text:p><text:h>aX</text:h><text:list><text:list-item><text:p>Y</text:p></text:list-item></text:list><text:h>Zb</text:h>"
+    );
+  }
+
+  #[test]
+  fn resolves_changes_in_links() {
+    let regions = r#"
+<text:changed-region text:id="i"><text:insertion><office:change-info/></text:insertion></text:changed-region>"#;
+    let original = content(
+      regions,
+      r#"<text:p>Go <text:a xlink:href="https://example.com">to <text:change-start text:change-id="i"/>the site</text:a> now<text:change-end text:change-id="i"/>!</text:p>"#,
+    );
+    let changes = original.tracked_changes();
+    assert_eq!(changes[0].start.map(|start| start.offset), Some(6));
+    assert_eq!(changes[0].end.map(|end| end.offset), Some(18));
+
+    assert_eq!(
+      body(&original.accept_all_changes()),
+      r#"<text:p>Go <text:a xlink:href="https://example.com">to the site</text:a> now!</text:p>"#
+    );
+    assert_eq!(
+      body(&original.reject_all_changes()),
+      r#"<text:p>Go <text:a xlink:href="https://example.com">to </text:a>!</text:p>"#
+    );
+  }
+
+  #[test]
+  fn restores_deletions_in_links() {
+    let regions = r#"
+<text:changed-region text:id="d1"><text:deletion><office:change-info/>
+  <text:p>my <text:span text:style-name="T1">own</text:span>-</text:p>
+</text:deletion></text:changed-region>
+<text:changed-region text:id="d2"><text:deletion><office:change-info/>
+  <text:p>X<text:line-break/>Y</text:p><text:p>Z</text:p>
+</text:deletion></text:changed-region>"#;
+    let original = content(
+      regions,
+      r##"<text:p>Go <text:a xlink:href="#a">to <text:change text:change-id="d1"/>site</text:a>!</text:p><text:p><text:a xlink:href="#b">1<text:span>2<text:change text:change-id="d2"/>3</text:span></text:a>4</text:p>"##,
+    );
+
+    assert_eq!(
+      body(&original.accept_all_changes()),
+      r##"<text:p>Go <text:a xlink:href="#a">to site</text:a>!</text:p><text:p><text:a xlink:href="#b">1<text:span>23</text:span></text:a>4</text:p>"##
+    );
+    assert_eq!(
+      body(&original.reject_all_changes()),
+      r##"<text:p>Go <text:a xlink:href="#a">to my <text:span text:style-name="T1">own</text:span>-site</text:a>!</text:p><text:p><text:a xlink:href="#b">1<text:span>2</text:span>X</text:a><text:line-break/><text:a xlink:href="#b">Y</text:a></text:p><text:p>Z<text:a xlink:href="#b"><text:span>3</text:span></text:a>4</text:p>"##
+    );
+  }
+
+  #[test]
+  fn suggest_fixture() {
+    let document =
+      open(std::fs::read("../../extension-odt/test/wgd/suggest.odt").unwrap())
+        .unwrap();
+    for resolved in [
+      document.content.accept_all_changes(),
+      document.content.reject_all_changes(),
+    ] {
+      assert!(resolved.tracked_changes().is_empty());
+      assert!(crate::walk::markers(&resolved.body.text).iter().all(
+        |(_, marker)| !matches!(
+          marker,
+          crate::walk::Marker::ChangeStart(_)
+            | crate::walk::Marker::ChangeEnd(_)
+            | crate::walk::Marker::Change(_)
+        )
+      ));
+    }
+  }
+}
@@ -1,62 +1,329 @@
 use std::collections::HashMap;
-use std::io::Cursor;
-use std::io::Read;
 
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::document::OdtDocument;
+use crate::error::OdtError;
+use crate::flat;
+use crate::lossless::attach_unknown;
+use crate::manifest::MANIFEST_PATH;
+use crate::namespaces::normalize;
+use crate::namespaces::NormalizedXml;
 use crate::ods_structs::DocumentContent;
+use crate::ods_structs::DocumentMeta;
 use crate::ods_structs::DocumentStyles;
+use crate::package::Package;
 
 // use serde_xml_rs::from_reader;
-use quick_xml::de::from_reader;
+use quick_xml::de::Deserializer;
 // use serde_roxmltree::from_str;
 
-pub fn unzip(zip_data: Vec<u8>) -> HashMap<String, Vec<u8>> {
-  let reader = Cursor::new(zip_data.clone());
-  let mut archive = zip::ZipArchive::new(reader).unwrap();
+/// Extracts every part listed in the manifest (see [`Package::entries`]) by
+/// its full path.
+pub fn unzip(zip_data: Vec<u8>) -> Result<HashMap<String, Vec<u8>>, OdtError> {
+  let mut package = Package::new(zip_data)?;
+  let paths = package.entries().into_iter().map(|entry| entry.full_path);
+  package.read_all(paths)
+}
 
-  let mut result: HashMap<String, Vec<u8>> = HashMap::new();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentFormat {
+  /// Zipped package (.odt)
+  Package,
+  /// Flat XML (.fodt)
+  Flat,
+}
 
-  for i in 0..archive.len() {
-    let mut file = archive.by_index(i).unwrap();
-    if !file.is_file() {
-      continue;
+pub fn detect_format(data: &[u8]) -> Option<DocumentFormat> {
+  if data.starts_with(b"PK\x03\x04") {
+    return Some(DocumentFormat::Package);
+  }
+  let text = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+  let start = text.iter().position(|b| !b.is_ascii_whitespace())?;
+  (text[start] == b'<').then_some(DocumentFormat::Flat)
+}
+
+/// Reads the whole package: content, styles, meta, settings, manifest and
+/// every other part, which is kept in [`OdtDocument::media`].
+///
+/// Flat XML documents are accepted too, see [`crate::flat::split`].
+pub fn open(zip_data: Vec<u8>) -> Result<OdtDocument, OdtError> {
+  open_package(zip_data, false)
+}
+
+/// Like [`open`], but keeps the elements and attributes the model does not
+/// know, so that writing the document back does not lose them.
+pub fn open_lossless(zip_data: Vec<u8>) -> Result<OdtDocument, OdtError> {
+  open_package(zip_data, true)
+}
+
+fn open_package(
+  zip_data: Vec<u8>,
+  lossless: bool,
+) -> Result<OdtDocument, OdtError> {
+  let (mut files, manifest) = match detect_format(&zip_data) {
+    Some(DocumentFormat::Flat) => (flat::split(&zip_data)?, None),
+    _ => {
+      let mut package = Package::new(zip_data)?;
+      let files = package.read_all(package.file_names())?;
+      (files, package.into_manifest())
     }
+  };
 
-    let filename = file.name().to_string();
+  let mut take = |name: &str| files.remove(name);
+  let mimetype = take("mimetype")
+    .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string());
+  let content = take("content.xml").ok_or(OdtError::MissingPart {
+    path: "content.xml".to_string(),
+  })?;
+  let styles = take("styles.xml").ok_or(OdtError::MissingPart {
+    path: "styles.xml".to_string(),
+  })?;
+  let meta = take("meta.xml");
+  let settings = take("settings.xml").map(into_string);
+  take(MANIFEST_PATH);
+
+  let (content, styles, meta) = if lossless {
+    (
+      parse_content_lossless(content)?,
+      parse_styles_lossless(styles)?,
+      meta.map(|meta| parse_xml_lossless(&meta)).transpose()?,
+    )
+  } else {
+    (
+      parse_content(content)?,
+      parse_styles(styles)?,
+      meta.map(parse_meta).transpose()?,
+    )
+  };
+
+  Ok(OdtDocument {
+    mimetype,
+    content,
+    styles,
+    meta,
+    settings,
+    manifest,
+    media: files,
+  })
+}
 
-    match filename.as_str() {
-      "content.xml" | "styles.xml" => {
-        let mut content = Vec::new();
-        file.read_to_end(&mut content).unwrap();
-        result.insert(filename, content);
+fn into_string(bytes: Vec<u8>) -> String {
+  String::from_utf8(bytes)
+    .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).to_string())
+}
+
+pub fn parse_content(xml_bytes: Vec<u8>) -> Result<DocumentContent, OdtError> {
+  parse_xml(&xml_bytes)
+}
+
+pub fn parse_styles(xml_bytes: Vec<u8>) -> Result<DocumentStyles, OdtError> {
+  parse_xml(&xml_bytes)
+}
+
+pub fn parse_meta(xml_bytes: Vec<u8>) -> Result<DocumentMeta, OdtError> {
+  parse_xml(&xml_bytes)
+}
+
+pub fn parse_content_lossless(
+  xml_bytes: Vec<u8>,
+) -> Result<DocumentContent, OdtError> {
+  parse_xml_lossless(&xml_bytes)
+}
+
+pub fn parse_styles_lossless(
+  xml_bytes: Vec<u8>,
+) -> Result<DocumentStyles, OdtError> {
+  parse_xml_lossless(&xml_bytes)
+}
+
+pub(crate) fn parse_xml<T: DeserializeOwned>(
+  xml_bytes: &[u8],
+) -> Result<T, OdtError> {
+  deserialize(&normalize(xml_bytes)?)
+}
+
+/// Parses like [`parse_xml`], then keeps what the model does not know in
+/// `Extra` fields and `Raw` variants (see lossless.rs).
+pub(crate) fn parse_xml_lossless<T: Serialize + DeserializeOwned>(
+  xml_bytes: &[u8],
+) -> Result<T, OdtError> {
+  let normalized = normalize(xml_bytes)?;
+  let model: T = deserialize(&normalized)?;
+
+  let mut value =
+    serde_json::to_value(&model).map_err(|err| OdtError::Serialize {
+      message: err.to_string(),
+    })?;
+  attach_unknown(&normalized.xml, &mut value)?;
+  serde_json::from_value(value).map_err(|err| OdtError::Serialize {
+    message: err.to_string(),
+  })
+}
+
+fn deserialize<T: DeserializeOwned>(
+  normalized: &NormalizedXml,
+) -> Result<T, OdtError> {
+  let mut de = Deserializer::from_reader(&normalized.xml[..]);
+
+  serde_path_to_error::deserialize(&mut de).map_err(|err| {
+    let (path, offset) = locate(normalized, err.path());
+    match err.into_inner() {
+      quick_xml::DeError::InvalidXml(err) => OdtError::XmlSyntax {
+        message: err.to_string(),
+        offset,
+      },
+      err => OdtError::SchemaMismatch {
+        message: err.to_string(),
+        path,
+        offset,
+      },
+    }
+  })
+}
+
+// A step in the element path of a deserialization error.
+struct PathStep {
+  name: String,
+  // Index among all children (`$value` lists) or among siblings of the same
+  // name (repeated fields).
+  index: Option<usize>,
+  of_value: bool,
+}
+
+fn path_steps(path: &serde_path_to_error::Path) -> Vec<PathStep> {
+  use serde_path_to_error::Segment;
+
+  let mut steps = Vec::new();
+  let mut index = None;
+  let mut of_value = false;
+
+  for segment in path.iter() {
+    match segment {
+      Segment::Map { key } if key == "$value" => of_value = true,
+      Segment::Map { key } if key.starts_with('@') || key == "$text" => break,
+      Segment::Map { key } | Segment::Enum { variant: key } => {
+        steps.push(PathStep {
+          name: key.to_string(),
+          index: index.take(),
+          of_value,
+        });
+        of_value = false;
       }
-      other
-        if other.ends_with("/content.xml")
-          || other.ends_with(".png")
-          || other.ends_with(".jpg") =>
-      {
-        let mut content = Vec::new();
-        file.read_to_end(&mut content).unwrap();
-        result.insert(other.to_string(), content);
+      Segment::Seq { index: i } => {
+        // A repeated field reports its index after the name.
+        match steps.last_mut() {
+          Some(step) if !of_value && step.index.is_none() => {
+            step.index = Some(*i)
+          }
+          _ => index = Some(*i),
+        }
       }
-      _ => {}
+      Segment::Unknown => break,
     }
   }
 
-  result
+  steps
 }
 
-pub fn parse_content(xml_bytes: Vec<u8>) -> DocumentContent {
-  from_reader(&xml_bytes[..]).unwrap()
-}
+/// Finds the element a deserialization error points to. Returns its `/`
+/// separated path and its offset in the source document.
+fn locate(
+  normalized: &NormalizedXml,
+  path: &serde_path_to_error::Path,
+) -> (String, u64) {
+  let steps = path_steps(path);
+  let mut reader = Reader::from_reader(&normalized.xml[..]);
+
+  let mut names: Vec<String> = Vec::new();
+  let mut offset = 0;
+  // (children, children by name) of the open elements on the matched chain
+  let mut counters: Vec<(usize, HashMap<String, usize>)> = Vec::new();
+  let mut depth = 0;
+
+  loop {
+    let pos = reader.buffer_position() as usize;
+    let (start, is_empty) = match reader.read_event() {
+      Ok(Event::Start(e)) => (e, false),
+      Ok(Event::Empty(e)) => (e, true),
+      Ok(Event::Text(e)) if depth == counters.len() && depth > 0 => {
+        if !e.iter().all(|b| b.is_ascii_whitespace()) {
+          counters[depth - 1].0 += 1;
+        }
+        continue;
+      }
+      Ok(Event::End(_)) => {
+        if depth == counters.len() {
+          // left the matched element without finding the next step
+          break;
+        }
+        depth -= 1;
+        continue;
+      }
+      Ok(Event::Eof) | Err(_) => break,
+      _ => continue,
+    };
+
+    let name = String::from_utf8_lossy(start.name().as_ref())
+      .trim_start_matches(':')
+      .to_string();
+
+    if depth > counters.len() {
+      depth += !is_empty as usize;
+      continue;
+    }
+
+    if names.is_empty() {
+      // root element
+      names.push(name);
+      offset = normalized.source_offset(pos);
+      counters.push((0, HashMap::new()));
+      depth = 1;
+      if steps.is_empty() {
+        break;
+      }
+      continue;
+    }
+
+    let (children, by_name) = counters.last_mut().unwrap();
+    let child_idx = *children;
+    let name_idx = *by_name.get(&name).unwrap_or(&0);
+    *children += 1;
+    *by_name.entry(name.clone()).or_default() += 1;
 
-pub fn parse_styles(xml_bytes: Vec<u8>) -> DocumentStyles {
-  from_reader(&xml_bytes[..]).unwrap()
+    let step = &steps[names.len() - 1];
+    let matches = step.name == name
+      && match (step.index, step.of_value) {
+        (None, _) => true,
+        (Some(idx), true) => idx == child_idx,
+        (Some(idx), false) => idx == name_idx,
+      };
+
+    if matches {
+      names.push(name);
+      offset = normalized.source_offset(pos);
+      if names.len() > steps.len() || is_empty {
+        break;
+      }
+      counters.push((0, HashMap::new()));
+      depth += 1;
+    } else {
+      depth += !is_empty as usize;
+    }
+  }
+
+  (names.join("/"), offset as u64)
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
   use super::*;
+  use crate::ods_structs::*;
   use std::fs::File;
+  use std::io::Read;
   use std::io::{self};
 
   fn read_file_to_vec(file_path: &str) -> io::Result<Vec<u8>> {
@@ -66,12 +333,306 @@ mod tests {
     Ok(buffer)
   }
 
+  const NAMESPACES: &str = r#"
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0"
+  xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+  xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+  xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+  xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
+  xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0"
+  xmlns:xlink="http://www.w3.org/1999/xlink"
+  xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0"
+  xmlns:dc="http://purl.org/dc/elements/1.1/""#;
+
+  /// A `content.xml` with `body` inside `office:text`.
+  pub(crate) fn content_xml(body: &str) -> Vec<u8> {
+    styled_content_xml("", body)
+  }
+
+  /// A `content.xml` with `automatic_styles` inside
+  /// `office:automatic-styles` and `body` inside `office:text`.
+  pub(crate) fn styled_content_xml(
+    automatic_styles: &str,
+    body: &str,
+  ) -> Vec<u8> {
+    format!(
+      r#"<office:document-content{}>
+  <office:font-face-decls/>
+  <office:automatic-styles>{}</office:automatic-styles>
+  <office:body><office:text>{}</office:text></office:body>
+</office:document-content>"#,
+      NAMESPACES, automatic_styles, body
+    )
+    .into_bytes()
+  }
+
+  /// A `styles.xml` with the children of `office:styles`,
+  /// `office:automatic-styles` and `office:master-styles`.
+  pub(crate) fn styles_xml(
+    styles: &str,
+    automatic_styles: &str,
+    master_styles: &str,
+  ) -> Vec<u8> {
+    format!(
+      r#"<office:document-styles{}>
+  <office:styles>{}</office:styles>
+  <office:automatic-styles>{}</office:automatic-styles>
+  <office:master-styles>{}</office:master-styles>
+</office:document-styles>"#,
+      NAMESPACES, styles, automatic_styles, master_styles
+    )
+    .into_bytes()
+  }
+
   #[test]
   fn parse() {
     let content =
       read_file_to_vec("src/example.odt").expect("Failed to read file");
-    let zip = unzip(content);
+    let zip = unzip(content).expect("Failed to unzip");
     let content: Vec<u8> = zip.get("content.xml").unwrap().clone();
-    parse_content(content);
+    parse_content(content).expect("Failed to parse content");
+  }
+
+  #[test]
+  fn unzip_follows_manifest() {
+    let files = unzip(read_file_to_vec("src/example.odt").unwrap()).unwrap();
+    assert!(files.contains_key("meta.xml"));
+    assert!(files.contains_key("Object 2/content.xml"));
+    assert!(files.contains_key("ObjectReplacements/Object 1"));
+    // Neither is listed in the manifest.
+    assert!(!files.contains_key("mimetype"));
+    assert!(!files.contains_key(MANIFEST_PATH));
+  }
+
+  #[test]
+  fn open_document() {
+    let doc = open(read_file_to_vec("src/example.odt").unwrap()).unwrap();
+    assert_eq!(
+      doc.mimetype.as_deref(),
+      Some("application/vnd.oasis.opendocument.text")
+    );
+    assert!(!doc.content.body.text.list.is_empty());
+    let statistic = doc.meta.unwrap().meta.document_statistic.unwrap();
+    assert_eq!(statistic.word_count, Some(380));
+    assert_eq!(statistic.table_count, Some(2));
+    assert!(doc.settings.unwrap().contains("office:document-settings"));
+    let manifest = doc.manifest.unwrap();
+    assert_eq!(
+      manifest.entry("/").unwrap().media_type,
+      "application/vnd.oasis.opendocument.text"
+    );
+    assert!(!doc.media.contains_key(MANIFEST_PATH));
+    assert!(doc.media.contains_key("Object 2/content.xml"));
+    assert!(doc.media.contains_key("ObjectReplacements/Object 1"));
+    assert!(!doc.media.contains_key("content.xml"));
+  }
+
+  const META: &[u8] = br#"<office:document-meta
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:dc="http://purl.org/dc/elements/1.1/"
+  xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0"
+  office:version="1.3"><office:meta>
+  <meta:initial-creator>Ann</meta:initial-creator>
+  <meta:creation-date>2024-01-02T03:04:05</meta:creation-date>
+  <dc:title>Report</dc:title>
+  <meta:keyword>one</meta:keyword>
+  <dc:subject>Budget</dc:subject>
+  <meta:keyword>two</meta:keyword>
+  <dc:date>2024-02-03T04:05:06.7</dc:date>
+  <meta:editing-cycles>12</meta:editing-cycles>
+  <meta:editing-duration>PT1H2M3S</meta:editing-duration>
+  <meta:document-statistic meta:page-count="3" meta:word-count="1234"/>
+  <meta:user-defined meta:name="Reviewed" meta:value-type="boolean">true</meta:user-defined>
+  <meta:user-defined meta:name="Empty"/>
+</office:meta></office:document-meta>"#;
+
+  #[test]
+  fn meta() {
+    let meta = parse_meta(META.to_vec()).unwrap().meta;
+    assert_eq!(meta.title.as_deref(), Some("Report"));
+    assert_eq!(meta.subject.as_deref(), Some("Budget"));
+    assert_eq!(meta.keywords, vec!["one", "two"]);
+    assert_eq!(meta.initial_creator.as_deref(), Some("Ann"));
+    assert_eq!(meta.creation_date.as_deref(), Some("2024-01-02T03:04:05"));
+    assert_eq!(meta.date.as_deref(), Some("2024-02-03T04:05:06.7"));
+    assert_eq!(meta.editing_cycles, Some(12));
+    assert_eq!(meta.editing_duration.as_deref(), Some("PT1H2M3S"));
+    let statistic = meta.document_statistic.unwrap();
+    assert_eq!(statistic.page_count, Some(3));
+    assert_eq!(statistic.word_count, Some(1234));
+    assert_eq!(meta.user_defined.len(), 2);
+    assert_eq!(meta.user_defined[0].name, "Reviewed");
+    assert_eq!(meta.user_defined[0].value_type.as_deref(), Some("boolean"));
+    assert_eq!(meta.user_defined[0].value, "true");
+    assert_eq!(meta.user_defined[1].value, "");
+  }
+
+  #[test]
+  fn headings() {
+    let content = parse_content(content_xml(
+      r#"<text:h text:style-name="Heading_20_2" text:outline-level="2"
+  text:restart-numbering="true">Intro <text:span>text</text:span></text:h>
+<text:list><text:list-item>
+  <text:h text:outline-level="3" text:is-list-header="false">Item</text:h>
+</text:list-item></text:list>"#,
+    ))
+    .unwrap();
+
+    let list = &content.body.text.list;
+    let OfficeTextItem::TextHeading(heading) = &list[0] else {
+      panic!("expected heading, got {:?}", list[0]);
+    };
+    assert_eq!(heading.outline_level, Some(2));
+    assert_eq!(heading.restart_numbering, Some(true));
+    assert_eq!(heading.style_name.as_deref(), Some("Heading_20_2"));
+    assert_eq!(heading.list.len(), 2);
+
+    let OfficeTextItem::TextList(text_list) = &list[1] else {
+      panic!("expected list, got {:?}", list[1]);
+    };
+    let TextListItemEnum::TextHeading(heading) = &text_list.list[0].list[0]
+    else {
+      panic!("expected heading in list item");
+    };
+    assert_eq!(heading.outline_level, Some(3));
+    assert_eq!(heading.is_list_header, Some(false));
+  }
+
+  #[test]
+  fn sections() {
+    let content = parse_content(content_xml(
+      r#"<text:section text:style-name="Sect1" text:name="Outer"
+  text:protected="true" text:protection-key="abc=">
+  <text:p>one</text:p>
+  <text:section text:name="Linked">
+    <text:section-source xlink:href="../policy.odt" text:section-name="Scope"
+      text:filter-name="writer8"/>
+    <text:h text:outline-level="1">two</text:h>
+    <text:list><text:list-item><text:p>three</text:p></text:list-item></text:list>
+  </text:section>
+</text:section>"#,
+    ))
+    .unwrap();
+
+    let OfficeTextItem::TextSection(outer) = &content.body.text.list[0] else {
+      panic!("expected section, got {:?}", content.body.text.list[0]);
+    };
+    assert_eq!(outer.name.as_deref(), Some("Outer"));
+    assert_eq!(outer.style_name.as_deref(), Some("Sect1"));
+    assert_eq!(outer.protected, Some(true));
+    assert_eq!(outer.protection_key.as_deref(), Some("abc="));
+    assert_eq!(outer.section_source, None);
+    assert!(matches!(outer.list[0], OfficeTextItem::TextParagraph(_)));
+
+    let OfficeTextItem::TextSection(inner) = &outer.list[1] else {
+      panic!("expected nested section, got {:?}", outer.list[1]);
+    };
+    let source = inner.section_source.as_ref().unwrap();
+    assert_eq!(source.href.as_deref(), Some("../policy.odt"));
+    assert_eq!(source.section_name.as_deref(), Some("Scope"));
+    assert_eq!(source.filter_name.as_deref(), Some("writer8"));
+    assert!(matches!(
+      inner.list[..],
+      [OfficeTextItem::TextHeading(_), OfficeTextItem::TextList(_)]
+    ));
+    assert_eq!(crate::walk::text_blocks(&content.body.text).len(), 3);
+
+    let xml = crate::writer::write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn custom_shape() {
+    let content = parse_content(content_xml(
+      r#"<text:p text:style-name="P1"><draw:custom-shape
+  xmlns:drawooo="http://openoffice.org/2010/draw"
+  draw:style-name="gr1" svg:width="1in" svg:height="1in">
+  <text:p/>
+  <draw:enhanced-geometry svg:viewBox="0 0 21600 21600" draw:type="ellipse"
+    draw:enhanced-path="U 10800 10800 10800 10800 0 360 Z N"
+    drawooo:enhanced-path="U 0 0 Z N">
+    <draw:equation draw:name="f0" draw:formula="$0"/>
+  </draw:enhanced-geometry>
+</draw:custom-shape></text:p>"#,
+    ))
+    .unwrap();
+
+    let OfficeTextItem::TextParagraph(para) = &content.body.text.list[0] else {
+      panic!("expected paragraph");
+    };
+    let TextParagraphItem::DrawCustomShape(shape) = &para.list[0] else {
+      panic!("expected custom shape, got {:?}", para.list[0]);
+    };
+    assert_eq!(shape.style_name.as_deref(), Some("gr1"));
+    assert_eq!(shape.width, Some("1in".into()));
+    assert_eq!(shape.x, None);
+    assert!(matches!(
+      shape.list[0],
+      DrawCustomShapeItem::TextParagraph(_)
+    ));
+    let DrawCustomShapeItem::DrawEnhancedGeometry(geometry) = &shape.list[1]
+    else {
+      panic!("expected geometry, got {:?}", shape.list[1]);
+    };
+    assert_eq!(geometry.shape_type.as_deref(), Some("ellipse"));
+    assert_eq!(
+      geometry.path.as_deref(),
+      Some("U 10800 10800 10800 10800 0 360 Z N")
+    );
+    assert_eq!(geometry.path2.as_deref(), Some("U 0 0 Z N"));
+    assert_eq!(geometry.equations[0].formula, "$0");
+  }
+
+  #[test]
+  fn parse_fixtures() {
+    for entry in std::fs::read_dir("../../extension-odt/test/wgd").unwrap() {
+      let path = entry.unwrap().path();
+      if path.extension().is_none_or(|ext| ext != "odt") {
+        continue;
+      }
+      let zip = unzip(read_file_to_vec(path.to_str().unwrap()).unwrap())
+        .expect("Failed to unzip");
+      parse_content(zip["content.xml"].clone())
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+      parse_styles(zip["styles.xml"].clone())
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+    }
+  }
+
+  #[test]
+  fn invalid_zip() {
+    let err = unzip(b"not a zip".to_vec()).unwrap_err();
+    assert_eq!(err.kind(), "invalid-zip");
+  }
+
+  #[test]
+  fn schema_mismatch() {
+    let xml = content_xml(
+      r#"<text:list><text:list-item>
+  <text:p><text:s text:c="many"/></text:p>
+</text:list-item></text:list>"#,
+    );
+    let err = parse_content(xml.clone()).unwrap_err();
+    match err {
+      OdtError::SchemaMismatch { path, offset, .. } => {
+        assert_eq!(
+          path,
+          "office:document-content/office:body/office:text/text:list/\
+text:list-item/text:p/text:s"
+        );
+        assert_eq!(
+          offset,
+          xml.windows(7).position(|w| w == b"<text:s").unwrap() as u64
+        );
+      }
+      err => panic!("unexpected error: {:?}", err),
+    }
+  }
+
+  #[test]
+  fn xml_syntax() {
+    let err = parse_content(b"<a><b></a>".to_vec()).unwrap_err();
+    assert_eq!(err.kind(), "xml-syntax");
   }
 }
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::manifest::Manifest;
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::DocumentMeta;
+use crate::ods_structs::DocumentStyles;
+
+/// An opened .odt package, see [`crate::core::open`].
+#[derive(Debug, Serialize, PartialEq)]
+pub struct OdtDocument {
+  pub mimetype: Option<String>,
+  pub content: DocumentContent,
+  pub styles: DocumentStyles,
+  pub meta: Option<DocumentMeta>,
+  /// Raw `settings.xml`
+  pub settings: Option<String>,
+  /// `META-INF/manifest.xml`, `None` for flat XML documents.
+  pub manifest: Option<Manifest>,
+  /// Every other part of the package (pictures, embedded objects, ...) by
+  /// its full path.
+  #[serde(skip)]
+  pub media: HashMap<String, Vec<u8>>,
+}
+
+impl OdtDocument {
+  pub fn part_names(&self) -> Vec<String> {
+    let mut names: Vec<String> = self.media.keys().cloned().collect();
+    names.sort();
+    names
+  }
+
+  pub fn part(&self, name: &str) -> Option<&[u8]> {
+    self.media.get(name).map(|bytes| bytes.as_slice())
+  }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use wasm_bindgen::JsValue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OdtError {
+  /// The input is not a readable zip archive.
+  InvalidZip { message: String },
+
+  /// A part required by the caller is not present in the package.
+  MissingPart { path: String },
+
+  /// The XML of a part is not well-formed.
+  XmlSyntax { message: String, offset: u64 },
+
+  /// The XML is well-formed, but does not match the expected ODF structure.
+  /// `path` is the element path (eg. `office:document-content/office:body`)
+  /// open at `offset`.
+  SchemaMismatch {
+    message: String,
+    path: String,
+    offset: u64,
+  },
+
+  /// The package (or one of its parts) is encrypted.
+  UnsupportedEncryption { path: String },
+
+  /// A style is its own ancestor. `path` lists the chain, eg. `A -> B -> A`.
+  StyleCycle { path: String },
+
+  /// A table repeats its rows or cells into more cells than can be
+  /// expanded.
+  TableTooLarge { rows: usize, columns: usize },
+
+  /// The model could not be written back to XML.
+  Serialize { message: String },
+}
+
+impl OdtError {
+  pub fn kind(&self) -> &'static str {
+    match self {
+      OdtError::InvalidZip { .. } => "invalid-zip",
+      OdtError::MissingPart { .. } => "missing-part",
+      OdtError::XmlSyntax { .. } => "xml-syntax",
+      OdtError::SchemaMismatch { .. } => "schema-mismatch",
+      OdtError::UnsupportedEncryption { .. } => "unsupported-encryption",
+      OdtError::StyleCycle { .. } => "style-cycle",
+      OdtError::TableTooLarge { .. } => "table-too-large",
+      OdtError::Serialize { .. } => "serialize",
+    }
+  }
+}
+
+impl fmt::Display for OdtError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      OdtError::InvalidZip { message } => {
+        write!(f, "invalid zip archive: {}", message)
+      }
+      OdtError::MissingPart { path } => write!(f, "missing part: {}", path),
+      OdtError::XmlSyntax { message, offset } => {
+        write!(f, "xml syntax error at byte {}: {}", offset, message)
+      }
+      OdtError::SchemaMismatch {
+        message,
+        path,
+        offset,
+      } => write!(
+        f,
+        "unexpected content in {} at byte {}: {}",
+        path, offset, message
+      ),
+      OdtError::UnsupportedEncryption { path } => {
+        write!(f, "encrypted part is not supported: {}", path)
+      }
+      OdtError::StyleCycle { path } => {
+        write!(f, "style inherits from itself: {}", path)
+      }
+      OdtError::TableTooLarge { rows, columns } => {
+        write!(f, "table too large: {} rows of {} cells", rows, columns)
+      }
+      OdtError::Serialize { message } => {
+        write!(f, "failed to write xml: {}", message)
+      }
+    }
+  }
+}
+
+impl std::error::Error for OdtError {}
+
+impl From<zip::result::ZipError> for OdtError {
+  fn from(err: zip::result::ZipError) -> Self {
+    match err {
+      zip::result::ZipError::UnsupportedArchive(
+        zip::result::ZipError::PASSWORD_REQUIRED,
+      )
+      | zip::result::ZipError::InvalidPassword => {
+        OdtError::UnsupportedEncryption {
+          path: String::new(),
+        }
+      }
+      err => OdtError::InvalidZip {
+        message: err.to_string(),
+      },
+    }
+  }
+}
+
+/// Converts the error into a JS `Error` carrying `kind`, `path` and `offset`
+/// fields, so callers can tell the failure modes apart.
+impl From<OdtError> for JsValue {
+  fn from(err: OdtError) -> Self {
+    let js_err = js_sys::Error::new(&err.to_string());
+    js_err.set_name("OdtError");
+
+    let set = |key: &str, value: JsValue| {
+      let _ = js_sys::Reflect::set(&js_err, &JsValue::from_str(key), &value);
+    };
+
+    set("kind", JsValue::from_str(err.kind()));
+    match &err {
+      OdtError::InvalidZip { .. }
+      | OdtError::TableTooLarge { .. }
+      | OdtError::Serialize { .. } => {}
+      OdtError::MissingPart { path }
+      | OdtError::UnsupportedEncryption { path }
+      | OdtError::StyleCycle { path } => {
+        set("path", JsValue::from_str(path));
+      }
+      OdtError::XmlSyntax { offset, .. } => {
+        set("offset", JsValue::from_f64(*offset as f64));
+      }
+      OdtError::SchemaMismatch { path, offset, .. } => {
+        set("path", JsValue::from_str(path));
+        set("offset", JsValue::from_f64(*offset as f64));
+      }
+    }
+
+    js_err.into()
+  }
+}
@@ -0,0 +1,239 @@
+use serde::Serialize;
+
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::TextChapter;
+use crate::ods_structs::TextDate;
+use crate::ods_structs::TextLinkItem;
+use crate::ods_structs::TextMetaField;
+use crate::ods_structs::TextPageNumber;
+use crate::ods_structs::TextParagraphItem;
+use crate::ods_structs::TextReference;
+use crate::ods_structs::TextSequence;
+use crate::ods_structs::TextSpanItem;
+use crate::ods_structs::TextTime;
+use crate::ods_structs::TextVariableGet;
+use crate::ods_structs::TextVariableSet;
+use crate::walk::text_blocks;
+
+/// A field of the text, whatever item it is in.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum Field<'a> {
+  PageNumber(&'a TextPageNumber),
+  Date(&'a TextDate),
+  Time(&'a TextTime),
+  AuthorName(&'a TextMetaField),
+  Title(&'a TextMetaField),
+  Chapter(&'a TextChapter),
+  BookmarkRef(&'a TextReference),
+  Sequence(&'a TextSequence),
+  SequenceRef(&'a TextReference),
+  VariableSet(&'a TextVariableSet),
+  VariableGet(&'a TextVariableGet),
+  UserFieldGet(&'a TextVariableGet),
+}
+
+impl<'a> Field<'a> {
+  /// The value displayed when the document was saved.
+  pub fn value(&self) -> &'a str {
+    match self {
+      Field::PageNumber(field) => &field.value,
+      Field::Date(field) => &field.value,
+      Field::Time(field) => &field.value,
+      Field::AuthorName(field) | Field::Title(field) => &field.value,
+      Field::Chapter(field) => &field.value,
+      Field::BookmarkRef(field) | Field::SequenceRef(field) => &field.value,
+      Field::Sequence(field) => &field.value,
+      Field::VariableSet(field) => &field.value,
+      Field::VariableGet(field) | Field::UserFieldGet(field) => &field.value,
+    }
+  }
+
+  /// Name of the bookmark or sequence a cross-reference points to.
+  pub fn reference(&self) -> Option<&'a str> {
+    match self {
+      Field::BookmarkRef(field) | Field::SequenceRef(field) => {
+        field.ref_name.as_deref()
+      }
+      _ => None,
+    }
+  }
+}
+
+impl TextParagraphItem {
+  pub fn field(&self) -> Option<Field<'_>> {
+    Some(match self {
+      TextParagraphItem::TextPageNumber(field) => Field::PageNumber(field),
+      TextParagraphItem::TextDate(field) => Field::Date(field),
+      TextParagraphItem::TextTime(field) => Field::Time(field),
+      TextParagraphItem::TextAuthorName(field) => Field::AuthorName(field),
+      TextParagraphItem::TextTitle(field) => Field::Title(field),
+      TextParagraphItem::TextChapter(field) => Field::Chapter(field),
+      TextParagraphItem::TextBookmarkRef(field) => Field::BookmarkRef(field),
+      TextParagraphItem::TextSequence(field) => Field::Sequence(field),
+      TextParagraphItem::TextSequenceRef(field) => Field::SequenceRef(field),
+      TextParagraphItem::TextVariableSet(field) => Field::VariableSet(field),
+      TextParagraphItem::TextVariableGet(field) => Field::VariableGet(field),
+      TextParagraphItem::TextUserFieldGet(field) => Field::UserFieldGet(field),
+      _ => return None,
+    })
+  }
+}
+
+impl TextSpanItem {
+  pub fn field(&self) -> Option<Field<'_>> {
+    Some(match self {
+      TextSpanItem::TextPageNumber(field) => Field::PageNumber(field),
+      TextSpanItem::TextDate(field) => Field::Date(field),
+      TextSpanItem::TextTime(field) => Field::Time(field),
+      TextSpanItem::TextAuthorName(field) => Field::AuthorName(field),
+      TextSpanItem::TextTitle(field) => Field::Title(field),
+      TextSpanItem::TextChapter(field) => Field::Chapter(field),
+      TextSpanItem::TextBookmarkRef(field) => Field::BookmarkRef(field),
+      TextSpanItem::TextSequence(field) => Field::Sequence(field),
+      TextSpanItem::TextSequenceRef(field) => Field::SequenceRef(field),
+      TextSpanItem::TextVariableSet(field) => Field::VariableSet(field),
+      TextSpanItem::TextVariableGet(field) => Field::VariableGet(field),
+      TextSpanItem::TextUserFieldGet(field) => Field::UserFieldGet(field),
+      _ => return None,
+    })
+  }
+}
+
+impl TextLinkItem {
+  pub fn field(&self) -> Option<Field<'_>> {
+    Some(match self {
+      TextLinkItem::TextPageNumber(field) => Field::PageNumber(field),
+      TextLinkItem::TextDate(field) => Field::Date(field),
+      TextLinkItem::TextTime(field) => Field::Time(field),
+      TextLinkItem::TextAuthorName(field) => Field::AuthorName(field),
+      TextLinkItem::TextTitle(field) => Field::Title(field),
+      TextLinkItem::TextChapter(field) => Field::Chapter(field),
+      TextLinkItem::TextBookmarkRef(field) => Field::BookmarkRef(field),
+      TextLinkItem::TextSequence(field) => Field::Sequence(field),
+      TextLinkItem::TextSequenceRef(field) => Field::SequenceRef(field),
+      TextLinkItem::TextVariableSet(field) => Field::VariableSet(field),
+      TextLinkItem::TextVariableGet(field) => Field::VariableGet(field),
+      TextLinkItem::TextUserFieldGet(field) => Field::UserFieldGet(field),
+      _ => return None,
+    })
+  }
+}
+
+impl DocumentContent {
+  /// Every field of the body in document order, including those in spans
+  /// and links.
+  pub fn fields(&self) -> Vec<Field<'_>> {
+    let mut fields = Vec::new();
+    for block in text_blocks(&self.body.text) {
+      for item in block.items() {
+        match item {
+          TextParagraphItem::TextSpan(span) => {
+            fields.extend(span.list.iter().filter_map(TextSpanItem::field))
+          }
+          TextParagraphItem::TextLink(link) => {
+            for item in &link.list {
+              match item {
+                TextLinkItem::TextSpan(span) => fields
+                  .extend(span.list.iter().filter_map(TextSpanItem::field)),
+                item => fields.extend(item.field()),
+              }
+            }
+          }
+          item => fields.extend(item.field()),
+        }
+      }
+    }
+    fields
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::walk::markers;
+  use crate::walk::Marker;
+  use crate::writer::write_content;
+
+  const BODY: &str = r##"
+    <text:p>Page <text:page-number text:select-page="current" style:num-format="i">iv</text:page-number> of <text:title>Policy</text:title>, <text:author-name text:fixed="true">Ann</text:author-name></text:p>
+    <text:p><text:date style:data-style-name="N37" text:date-value="2024-05-06">06/05/2024</text:date> <text:time text:fixed="false">10:00</text:time> <text:chapter text:display="number-and-name" text:outline-level="1">2 Scope</text:chapter></text:p>
+    <text:p>Figure <text:sequence text:ref-name="refIllustration0" text:name="Illustration" text:formula="ooow:Illustration+1" style:num-format="1">1</text:sequence>: plan</text:p>
+    <text:p>See <text:span><text:sequence-ref text:reference-format="category-and-value" text:ref-name="refIllustration0">Figure 1</text:sequence-ref></text:span> and <text:bookmark-ref text:reference-format="page" text:ref-name="intro">3</text:bookmark-ref>.</text:p>
+    <text:p><text:variable-set text:name="total" office:value-type="float" office:value="5" text:display="none"/><text:variable-get text:name="total">5</text:variable-get>, <text:user-field-get text:name="Owner">Bob</text:user-field-get><text:change text:change-id="x"/></text:p>
+    <text:p><text:a xlink:href="#intro"><text:bookmark-ref text:reference-format="text" text:ref-name="intro">Introduction</text:bookmark-ref></text:a><text:bookmark text:name="after"/></text:p>"##;
+
+  #[test]
+  fn parses_fields() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let fields = content.fields();
+    let values: Vec<&str> = fields.iter().map(Field::value).collect();
+    assert_eq!(
+      values,
+      vec![
+        "iv",
+        "Policy",
+        "Ann",
+        "06/05/2024",
+        "10:00",
+        "2 Scope",
+        "1",
+        "Figure 1",
+        "3",
+        "",
+        "5",
+        "Bob",
+        "Introduction"
+      ]
+    );
+
+    let Field::PageNumber(page) = fields[0] else {
+      panic!("expected page number, got {:?}", fields[0]);
+    };
+    assert_eq!(page.select_page.as_deref(), Some("current"));
+    assert_eq!(page.num_format.as_deref(), Some("i"));
+    assert!(
+      matches!(fields[2], Field::AuthorName(author) if author.fixed == Some(true))
+    );
+    let Field::Date(date) = fields[3] else {
+      panic!("expected date, got {:?}", fields[3]);
+    };
+    assert_eq!(date.date_value.as_deref(), Some("2024-05-06"));
+    assert_eq!(date.data_style_name.as_deref(), Some("N37"));
+    let Field::Chapter(chapter) = fields[5] else {
+      panic!("expected chapter, got {:?}", fields[5]);
+    };
+    assert_eq!(chapter.display.as_deref(), Some("number-and-name"));
+    assert_eq!(chapter.outline_level, Some(1));
+    let Field::Sequence(sequence) = fields[6] else {
+      panic!("expected sequence, got {:?}", fields[6]);
+    };
+    assert_eq!(sequence.name, "Illustration");
+    assert_eq!(sequence.ref_name.as_deref(), Some("refIllustration0"));
+    assert_eq!(fields[7].reference(), Some("refIllustration0"));
+    assert_eq!(fields[8].reference(), Some("intro"));
+    let Field::VariableSet(set) = fields[9] else {
+      panic!("expected variable-set, got {:?}", fields[9]);
+    };
+    assert_eq!(set.office_value.as_deref(), Some("5"));
+    assert_eq!(set.display.as_deref(), Some("none"));
+    assert!(
+      matches!(fields[11], Field::UserFieldGet(get) if get.name == "Owner")
+    );
+
+    assert_eq!(fields[12].reference(), Some("intro"));
+
+    // Field values count as text, also inside links.
+    let markers = markers(&content.body.text);
+    assert_eq!(markers[0].0.offset, 6);
+    let (position, marker) = markers.last().unwrap();
+    assert_eq!(*marker, Marker::Bookmark("after"));
+    assert_eq!(position.offset, "Introduction".len());
+    let blocks = text_blocks(&content.body.text);
+    assert_eq!(blocks[5].text(), "Introduction");
+
+    let xml = write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+}
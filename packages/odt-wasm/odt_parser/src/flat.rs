@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+
+use quick_xml::events::BytesDecl;
+use quick_xml::events::BytesEnd;
+use quick_xml::events::BytesStart;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use quick_xml::Writer;
+
+use crate::error::OdtError;
+use crate::namespaces::declare_namespaces;
+use crate::namespaces::normalize;
+use crate::namespaces::qualified_name;
+use crate::namespaces::qualified_start;
+use crate::namespaces::NormalizedXml;
+
+// Flat XML ODF (.fodt) keeps the whole package in one `office:document`
+// element. Its children are split into the parts of a zipped package, so
+// the rest of the crate does not need to know which format it reads.
+
+/// Which part(s) of a package each child of `office:document` belongs to.
+const PARTS: &[(&str, &[&str])] = &[
+  ("office:meta", &["meta.xml"]),
+  ("office:settings", &["settings.xml"]),
+  ("office:scripts", &["content.xml"]),
+  ("office:font-face-decls", &["content.xml", "styles.xml"]),
+  ("office:styles", &["styles.xml"]),
+  // Automatic styles of styles.xml hold the page layouts.
+  ("office:automatic-styles", &["content.xml", "styles.xml"]),
+  ("office:master-styles", &["styles.xml"]),
+  ("office:body", &["content.xml"]),
+];
+
+/// Root element of each part.
+const ROOTS: &[(&str, &str)] = &[
+  ("content.xml", "office:document-content"),
+  ("styles.xml", "office:document-styles"),
+  ("meta.xml", "office:document-meta"),
+  ("settings.xml", "office:document-settings"),
+];
+
+/// Splits a flat ODF document into the files of the equivalent package:
+/// `mimetype`, `content.xml`, `styles.xml`, `meta.xml`, `settings.xml`, and
+/// a `Pictures/` entry for each `office:binary-data`, which is replaced by an
+/// `xlink:href` to it.
+pub fn split(xml: &[u8]) -> Result<HashMap<String, Vec<u8>>, OdtError> {
+  let normalized = normalize(xml)?;
+  let mut reader = Reader::from_reader(&normalized.xml[..]);
+
+  let root = loop {
+    match read_event(&mut reader, &normalized)? {
+      Event::Start(e) => break e,
+      Event::Empty(_) | Event::Eof => {
+        return Err(OdtError::SchemaMismatch {
+          message: "empty document".to_string(),
+          path: String::new(),
+          offset: 0,
+        });
+      }
+      _ => {}
+    }
+  };
+  if qualified_name(root.name().as_ref()) != "office:document" {
+    return Err(OdtError::SchemaMismatch {
+      message: "expected office:document".to_string(),
+      path: qualified_name(root.name().as_ref()),
+      offset: normalized.source_offset(0) as u64,
+    });
+  }
+
+  let mut files = HashMap::new();
+  let mut root_attributes = Vec::new();
+  for attr in qualified_start(&root).attributes().flatten() {
+    if attr.key.as_ref() == b"office:mimetype" {
+      files.insert("mimetype".to_string(), attr.value.to_vec());
+    } else {
+      root_attributes.push((attr.key.as_ref().to_vec(), attr.value.to_vec()));
+    }
+  }
+
+  let mut parts: HashMap<&str, Vec<u8>> = HashMap::new();
+  let mut media = HashMap::new();
+  loop {
+    let (start, is_empty) = match read_event(&mut reader, &normalized)? {
+      Event::Start(e) => (e, false),
+      Event::Empty(e) => (e, true),
+      Event::End(_) | Event::Eof => break,
+      _ => continue,
+    };
+
+    let name = qualified_name(start.name().as_ref());
+    let xml =
+      copy_element(&mut reader, &normalized, &start, is_empty, &mut media)?;
+    let targets = PARTS
+      .iter()
+      .find(|(part, _)| *part == name)
+      .map(|(_, targets)| *targets)
+      .unwrap_or_default();
+    for target in targets {
+      parts.entry(target).or_default().extend_from_slice(&xml);
+    }
+  }
+
+  for (part, root) in ROOTS {
+    let children = parts.remove(part);
+    if children.is_none() && !matches!(*part, "content.xml" | "styles.xml") {
+      continue;
+    }
+
+    let mut start = BytesStart::new(*root);
+    declare_namespaces(&mut start);
+    for (key, value) in &root_attributes {
+      start.push_attribute((&key[..], &value[..]));
+    }
+    files.insert(
+      part.to_string(),
+      wrap(start, &children.unwrap_or_default())?,
+    );
+  }
+
+  files.extend(media);
+  Ok(files)
+}
+
+fn read_event<'a>(
+  reader: &mut Reader<&'a [u8]>,
+  normalized: &NormalizedXml,
+) -> Result<Event<'a>, OdtError> {
+  reader.read_event().map_err(|err| OdtError::XmlSyntax {
+    message: err.to_string(),
+    offset: normalized.source_offset(reader.error_position() as usize) as u64,
+  })
+}
+
+fn write_event(
+  writer: &mut Writer<Vec<u8>>,
+  event: Event,
+) -> Result<(), OdtError> {
+  writer
+    .write_event(event)
+    .map_err(|err| OdtError::Serialize {
+      message: err.to_string(),
+    })
+}
+
+fn wrap(start: BytesStart, children: &[u8]) -> Result<Vec<u8>, OdtError> {
+  let mut writer = Writer::new(Vec::new());
+  let end = start.to_end().into_owned();
+  write_event(
+    &mut writer,
+    Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)),
+  )?;
+  write_event(&mut writer, Event::Start(start))?;
+  let mut xml = writer.into_inner();
+  xml.extend_from_slice(children);
+
+  let mut writer = Writer::new(xml);
+  write_event(&mut writer, Event::End(end))?;
+  Ok(writer.into_inner())
+}
+
+/// Copies the element at `start` in qualified form, moving its
+/// `office:binary-data` into `media`.
+fn copy_element(
+  reader: &mut Reader<&[u8]>,
+  normalized: &NormalizedXml,
+  start: &BytesStart,
+  is_empty: bool,
+  media: &mut HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, OdtError> {
+  let mut writer = Writer::new(Vec::new());
+  if is_empty {
+    write_event(&mut writer, Event::Empty(qualified_start(start)))?;
+    return Ok(writer.into_inner());
+  }
+
+  // The last start tag is held back until its first child, which may be an
+  // `office:binary-data` that turns into an `xlink:href` on it.
+  let mut pending = Some(qualified_start(start));
+  let mut pending_space = Vec::new();
+  let mut depth = 0;
+
+  loop {
+    let pos = reader.buffer_position() as usize;
+    let event = read_event(reader, normalized)?;
+    match &event {
+      Event::Text(text)
+        if pending.is_some()
+          && text.iter().all(|b| b.is_ascii_whitespace()) =>
+      {
+        pending_space.push(event.into_owned());
+        continue;
+      }
+      Event::Start(e)
+        if qualified_name(e.name().as_ref()) == "office:binary-data" =>
+      {
+        let data =
+          reader
+            .read_text(e.name())
+            .map_err(|err| OdtError::XmlSyntax {
+              message: err.to_string(),
+              offset: normalized.source_offset(pos) as u64,
+            })?;
+        let bytes = decode_base64(&data).ok_or(OdtError::SchemaMismatch {
+          message: "invalid base64".to_string(),
+          path: "office:binary-data".to_string(),
+          offset: normalized.source_offset(pos) as u64,
+        })?;
+
+        let path = format!(
+          "Pictures/image{}.{}",
+          media.len() + 1,
+          picture_extension(&bytes)
+        );
+        if let Some(parent) = pending.as_mut() {
+          parent.push_attribute(("xlink:href", path.as_str()));
+          parent.push_attribute(("xlink:type", "simple"));
+          parent.push_attribute(("xlink:show", "embed"));
+          parent.push_attribute(("xlink:actuate", "onLoad"));
+        }
+        media.insert(path, bytes);
+        pending_space.clear();
+        continue;
+      }
+      _ => {}
+    }
+
+    if let Some(start) = pending.take() {
+      write_event(&mut writer, Event::Start(start))?;
+    }
+    for space in pending_space.drain(..) {
+      write_event(&mut writer, space)?;
+    }
+
+    match event {
+      Event::Start(e) => {
+        depth += 1;
+        pending = Some(qualified_start(&e));
+      }
+      Event::Empty(e) => {
+        write_event(&mut writer, Event::Empty(qualified_start(&e)))?
+      }
+      Event::End(e) => {
+        let name = qualified_name(e.name().as_ref());
+        write_event(&mut writer, Event::End(BytesEnd::new(name)))?;
+        if depth == 0 {
+          break;
+        }
+        depth -= 1;
+      }
+      Event::Eof => break,
+      event => write_event(&mut writer, event)?,
+    }
+  }
+
+  Ok(writer.into_inner())
+}
+
+fn picture_extension(bytes: &[u8]) -> &'static str {
+  if bytes.starts_with(b"\x89PNG") {
+    "png"
+  } else if bytes.starts_with(b"\xFF\xD8") {
+    "jpg"
+  } else if bytes.starts_with(b"GIF8") {
+    "gif"
+  } else if bytes.starts_with(b"<svg") || bytes.starts_with(b"<?xml") {
+    "svg"
+  } else if bytes.starts_with(b"BM") {
+    "bmp"
+  } else {
+    "bin"
+  }
+}
+
+/// Standard base64, ignoring whitespace.
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+  let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+  let mut buffer = 0u32;
+  let mut bits = 0;
+
+  for c in data.bytes() {
+    let value = match c {
+      b'A'..=b'Z' => c - b'A',
+      b'a'..=b'z' => c - b'a' + 26,
+      b'0'..=b'9' => c - b'0' + 52,
+      b'+' => 62,
+      b'/' => 63,
+      b'=' => break,
+      c if c.is_ascii_whitespace() => continue,
+      _ => return None,
+    };
+    buffer = (buffer << 6) | value as u32;
+    bits += 6;
+    if bits >= 8 {
+      bits -= 8;
+      bytes.push((buffer >> bits) as u8);
+    }
+  }
+
+  Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::detect_format;
+  use crate::core::open;
+  use crate::core::DocumentFormat;
+  use crate::ods_structs::OfficeTextItem;
+  use crate::writer::write;
+  use crate::writer::write_content;
+
+  const FLAT: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+  xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+  xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
+  xmlns:dc="http://purl.org/dc/elements/1.1/"
+  office:version="1.3"
+  office:mimetype="application/vnd.oasis.opendocument.text">
+  <office:meta><dc:title>Manual</dc:title></office:meta>
+  <office:font-face-decls/>
+  <office:styles>
+    <style:style style:name="Standard" style:family="paragraph"/>
+  </office:styles>
+  <office:automatic-styles>
+    <style:style style:name="P1" style:family="paragraph"
+      style:parent-style-name="Standard"/>
+  </office:automatic-styles>
+  <office:body><office:text>
+    <text:p text:style-name="P1">Logo: <draw:frame><draw:image>
+      <office:binary-data>iVBORw0KGgoAAAAN
+        SUhEUg==</office:binary-data>
+    </draw:image></draw:frame></text:p>
+    <text:h text:outline-level="1">Intro</text:h>
+  </office:text></office:body>
+</office:document>"#;
+
+  #[test]
+  fn detects_format() {
+    let odt = std::fs::read("src/example.odt").unwrap();
+    assert_eq!(detect_format(&odt), Some(DocumentFormat::Package));
+    assert_eq!(detect_format(FLAT), Some(DocumentFormat::Flat));
+    assert_eq!(
+      detect_format(b"\xEF\xBB\xBF  <x/>"),
+      Some(DocumentFormat::Flat)
+    );
+    assert_eq!(detect_format(b"hello"), None);
+  }
+
+  #[test]
+  fn splits_parts() {
+    let files = split(FLAT).unwrap();
+    let mut names: Vec<&str> = files.keys().map(|name| name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+      names,
+      vec![
+        "Pictures/image1.png",
+        "content.xml",
+        "meta.xml",
+        "mimetype",
+        "styles.xml"
+      ]
+    );
+    assert_eq!(
+      files["Pictures/image1.png"],
+      b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec()
+    );
+
+    let meta = String::from_utf8(files["meta.xml"].clone()).unwrap();
+    assert!(meta.contains(
+      r#"office:version="1.3"><office:meta><dc:title>Manual</dc:title></office:meta></office:document-meta>"#
+    ));
+  }
+
+  #[test]
+  fn opens_flat_document() {
+    let document = open(FLAT.to_vec()).unwrap();
+    assert_eq!(
+      document.mimetype.as_deref(),
+      Some("application/vnd.oasis.opendocument.text")
+    );
+    assert_eq!(
+      document.meta.as_ref().unwrap().meta.title.as_deref(),
+      Some("Manual")
+    );
+    assert_eq!(document.settings, None);
+    assert_eq!(document.part_names(), vec!["Pictures/image1.png"]);
+    assert_eq!(document.styles.styles.styles.len(), 1);
+    assert_eq!(document.content.automatic_styles.styles.len(), 1);
+
+    let list = &document.content.body.text.list;
+    assert!(matches!(list[1], OfficeTextItem::TextHeading(_)));
+    let content =
+      String::from_utf8(write_content(&document.content).unwrap()).unwrap();
+    assert!(content.contains(r#"<draw:image xlink:href="Pictures/image1.png""#));
+
+    // Written back as a zipped package.
+    let reopened = open(write(&document).unwrap()).unwrap();
+    assert_eq!(reopened.content, document.content);
+    assert_eq!(
+      reopened.part("Pictures/image1.png"),
+      document.part("Pictures/image1.png")
+    );
+  }
+
+  #[test]
+  fn rejects_other_roots() {
+    let err = split(b"<office:document-content/>").unwrap_err();
+    assert_eq!(err.kind(), "schema-mismatch");
+  }
+
+  #[test]
+  fn decodes_base64() {
+    assert_eq!(decode_base64("TWFu").unwrap(), b"Man");
+    assert_eq!(decode_base64("TWE=").unwrap(), b"Ma");
+    assert_eq!(decode_base64("T W\nE=").unwrap(), b"Ma");
+    assert_eq!(decode_base64("TQ==").unwrap(), b"M");
+    assert_eq!(decode_base64("T*=="), None);
+  }
+}
@@ -0,0 +1,411 @@
+use std::mem;
+
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::Extra;
+use crate::ods_structs::IndexEntryTemplate;
+use crate::ods_structs::IndexEntryTemplateItem;
+use crate::ods_structs::OfficeText;
+use crate::ods_structs::OfficeTextItem;
+use crate::ods_structs::TableOfContent;
+use crate::ods_structs::TextHeading;
+use crate::ods_structs::TextIndexBody;
+use crate::ods_structs::TextLink;
+use crate::ods_structs::TextLinkItem;
+use crate::ods_structs::TextParagraph;
+use crate::ods_structs::TextParagraphItem;
+use crate::walk::text_blocks;
+use crate::walk::TextBlock;
+
+impl OfficeTextItem {
+  /// The generated content of a table of contents or other index.
+  pub fn index_body(&self) -> Option<&TextIndexBody> {
+    match self {
+      OfficeTextItem::TableOfContent(index) => Some(&index.index_body),
+      OfficeTextItem::AlphabeticalIndex(index) => Some(&index.index_body),
+      OfficeTextItem::IllustrationIndex(index) => Some(&index.index_body),
+      OfficeTextItem::TableIndex(index) => Some(&index.index_body),
+      OfficeTextItem::Bibliography(index) => Some(&index.index_body),
+      _ => None,
+    }
+  }
+}
+
+impl TextIndexBody {
+  /// The paragraphs of the title, then the entries.
+  pub fn paragraphs(&self) -> impl Iterator<Item = &TextParagraph> {
+    self
+      .index_title
+      .iter()
+      .flat_map(|title| &title.list)
+      .chain(&self.list)
+  }
+}
+
+impl IndexEntryTemplate {
+  pub fn level(&self) -> Option<u32> {
+    self.outline_level.as_deref()?.parse().ok()
+  }
+}
+
+impl TableOfContent {
+  /// Builds the entries again from the `text:h` of `text` up to the outline
+  /// level of the source, following its entry templates. The title is kept.
+  ///
+  /// Entries link to the first bookmark of their heading. Page and chapter
+  /// numbers depend on the layout and are left for the office application
+  /// to fill in when it updates the index.
+  pub fn regenerate(&self, text: &OfficeText) -> TextIndexBody {
+    let source = self.source.as_ref();
+    let max_level = source.and_then(|source| source.outline_level);
+    let use_headings = source
+      .and_then(|source| source.use_outline_level)
+      .unwrap_or(true);
+    let templates = source
+      .map(|source| source.entry_templates.as_slice())
+      .unwrap_or_default();
+
+    let mut list = Vec::new();
+    if use_headings {
+      for block in text_blocks(text) {
+        let TextBlock::Heading(heading) = block else {
+          continue;
+        };
+        let level = heading.outline_level.unwrap_or(1);
+        if max_level.is_some_and(|max_level| level > max_level) {
+          continue;
+        }
+        let template = templates
+          .iter()
+          .find(|template| template.level() == Some(level));
+        list.push(entry(heading, level, template, &block.text()));
+      }
+    }
+
+    TextIndexBody {
+      index_title: self.index_body.index_title.clone(),
+      list,
+      extra: self.index_body.extra.clone(),
+    }
+  }
+}
+
+impl DocumentContent {
+  /// A copy of the content with every table of contents regenerated, see
+  /// [`TableOfContent::regenerate`].
+  pub fn regenerate_tables_of_content(&self) -> DocumentContent {
+    let mut content = self.clone();
+    let mut list = mem::take(&mut content.body.text.list);
+    regenerate(&mut list, &self.body.text);
+    content.body.text.list = list;
+    content
+  }
+}
+
+fn regenerate(list: &mut [OfficeTextItem], text: &OfficeText) {
+  for item in list {
+    match item {
+      OfficeTextItem::TableOfContent(toc) => {
+        toc.index_body = toc.regenerate(text)
+      }
+      OfficeTextItem::TextSection(section) => {
+        regenerate(&mut section.list, text)
+      }
+      _ => {}
+    }
+  }
+}
+
+fn entry(
+  heading: &TextHeading,
+  level: u32,
+  template: Option<&IndexEntryTemplate>,
+  text: &str,
+) -> TextParagraph {
+  let mut entry = Entry {
+    anchor: heading.list.iter().find_map(|item| match item {
+      TextParagraphItem::TextBookmark(bookmark) => Some(bookmark.name.as_str()),
+      TextParagraphItem::TextBookmarkStart(bookmark) => {
+        Some(bookmark.name.as_str())
+      }
+      _ => None,
+    }),
+    items: Vec::new(),
+    link: None,
+  };
+
+  match template {
+    Some(template) => {
+      for part in &template.list {
+        match part {
+          IndexEntryTemplateItem::IndexEntryText(_) => entry.text(text),
+          IndexEntryTemplateItem::IndexEntrySpan(span) => {
+            entry.text(&span.value)
+          }
+          IndexEntryTemplateItem::IndexEntryTabStop(_) => entry.tab(),
+          IndexEntryTemplateItem::IndexEntryLinkStart(start) => {
+            entry.start_link(start.style_name.clone())
+          }
+          IndexEntryTemplateItem::IndexEntryLinkEnd(_) => entry.end_link(),
+          _ => {}
+        }
+      }
+    }
+    None => {
+      entry.start_link(None);
+      entry.text(text);
+    }
+  }
+  entry.end_link();
+
+  TextParagraph {
+    list: entry.items,
+    style_name: Some(
+      template
+        .and_then(|template| template.style_name.clone())
+        .unwrap_or_else(|| format!("Contents_20_{}", level)),
+    ),
+    extra: Extra::default(),
+  }
+}
+
+struct Entry<'a> {
+  /// Bookmark of the heading.
+  anchor: Option<&'a str>,
+  items: Vec<TextParagraphItem>,
+  /// The link being built and its style, between link start and end.
+  link: Option<TextLink>,
+}
+
+impl Entry<'_> {
+  fn text(&mut self, text: &str) {
+    match &mut self.link {
+      Some(link) => link.list.push(TextLinkItem::Text(text.to_string())),
+      None => self.items.push(TextParagraphItem::Text(text.to_string())),
+    }
+  }
+
+  fn tab(&mut self) {
+    match &mut self.link {
+      Some(link) => link.list.push(TextLinkItem::TextTab),
+      None => self.items.push(TextParagraphItem::TextTab),
+    }
+  }
+
+  // Templates may repeat the link start, the last style wins.
+  fn start_link(&mut self, style_name: Option<String>) {
+    let Some(anchor) = self.anchor else {
+      return;
+    };
+    let link = self.link.get_or_insert_with(|| TextLink {
+      link_type: Some("simple".to_string()),
+      href: format!("#{}", anchor),
+      target_frame_name: None,
+      show: None,
+      name: None,
+      title: None,
+      style_name: None,
+      visited_style_name: None,
+      list: Vec::new(),
+      extra: Extra::default(),
+    });
+    if style_name.is_some() {
+      link.visited_style_name.clone_from(&style_name);
+      link.style_name = style_name;
+    }
+  }
+
+  fn end_link(&mut self) {
+    if let Some(link) = self.link.take() {
+      self.items.push(TextParagraphItem::TextLink(link));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::open;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::writer::write_content;
+
+  fn content(body: &str) -> DocumentContent {
+    parse_content(content_xml(body)).unwrap()
+  }
+
+  #[test]
+  fn parses_example_source() {
+    let document = open(std::fs::read("src/example.odt").unwrap()).unwrap();
+    let toc = document
+      .content
+      .body
+      .text
+      .list
+      .iter()
+      .find_map(|item| match item {
+        OfficeTextItem::TableOfContent(toc) => Some(toc),
+        _ => None,
+      })
+      .unwrap();
+    assert_eq!(toc.name.as_deref(), Some("Table of Contents1"));
+    assert_eq!(toc.protected, Some(true));
+    let source = toc.source.as_ref().unwrap();
+    assert_eq!(source.outline_level, Some(10));
+    assert_eq!(source.use_index_marks, Some(false));
+    assert_eq!(
+      source
+        .index_title_template
+        .as_ref()
+        .unwrap()
+        .style_name
+        .as_deref(),
+      Some("Contents_20_Heading")
+    );
+    assert_eq!(source.entry_templates.len(), 10);
+    let template = &source.entry_templates[0];
+    assert_eq!(template.level(), Some(1));
+    assert_eq!(template.style_name.as_deref(), Some("Contents_20_1"));
+    let IndexEntryTemplateItem::IndexEntryTabStop(tab) = &template.list[4]
+    else {
+      panic!("expected tab stop, got {:?}", template.list[4]);
+    };
+    assert_eq!(tab.tab_type.as_deref(), Some("right"));
+    assert_eq!(tab.leader_char.as_deref(), Some("."));
+    assert!(matches!(
+      template.list[5],
+      IndexEntryTemplateItem::IndexEntryPageNumber(_)
+    ));
+    assert!(!toc.index_body.list.is_empty());
+  }
+
+  #[test]
+  fn parses_other_indexes() {
+    let content = content(
+      r#"<text:alphabetical-index text:name="Index1">
+  <text:alphabetical-index-source text:alphabetical-separators="true" text:ignore-case="true">
+    <text:index-title-template>Index</text:index-title-template>
+    <text:alphabetical-index-entry-template text:outline-level="separator" text:style-name="Index_20_Separator"><text:index-entry-text/></text:alphabetical-index-entry-template>
+    <text:alphabetical-index-entry-template text:outline-level="1" text:style-name="Index_20_1"><text:index-entry-text/><text:index-entry-tab-stop style:type="left" style:position="5cm"/><text:index-entry-page-number/></text:alphabetical-index-entry-template>
+  </text:alphabetical-index-source>
+  <text:index-body>
+    <text:index-title text:name="Index1_Head"><text:p>Index</text:p></text:index-title>
+    <text:p>apple, 2</text:p>
+  </text:index-body>
+</text:alphabetical-index>
+<text:illustration-index>
+  <text:illustration-index-source text:caption-sequence-name="Figure" text:caption-sequence-format="caption">
+    <text:illustration-index-entry-template text:style-name="Figure_20_Index_20_1"><text:index-entry-text/></text:illustration-index-entry-template>
+  </text:illustration-index-source>
+  <text:index-body><text:p>Figure 1: plan</text:p></text:index-body>
+</text:illustration-index>
+<text:table-index>
+  <text:table-index-source text:use-caption="false"/>
+  <text:index-body/>
+</text:table-index>
+<text:bibliography>
+  <text:bibliography-source>
+    <text:bibliography-entry-template text:bibliography-type="book" text:style-name="Bibliography_20_1">
+      <text:index-entry-bibliography text:bibliography-data-field="author"/>
+      <text:index-entry-span>: </text:index-entry-span>
+      <text:index-entry-bibliography text:bibliography-data-field="title"/>
+    </text:bibliography-entry-template>
+  </text:bibliography-source>
+  <text:index-body><text:p>Knuth: TAOCP</text:p></text:index-body>
+</text:bibliography>"#,
+    );
+    let list = &content.body.text.list;
+
+    let OfficeTextItem::AlphabeticalIndex(alphabetical) = &list[0] else {
+      panic!("expected alphabetical index, got {:?}", list[0]);
+    };
+    let source = alphabetical.source.as_ref().unwrap();
+    assert_eq!(source.alphabetical_separators, Some(true));
+    assert_eq!(source.index_title_template.as_ref().unwrap().value, "Index");
+    let levels: Vec<_> = source
+      .entry_templates
+      .iter()
+      .map(|template| (template.outline_level.as_deref(), template.level()))
+      .collect();
+    assert_eq!(
+      levels,
+      vec![(Some("separator"), None), (Some("1"), Some(1))]
+    );
+    let IndexEntryTemplateItem::IndexEntryTabStop(tab) =
+      &source.entry_templates[1].list[1]
+    else {
+      panic!(
+        "expected tab stop, got {:?}",
+        source.entry_templates[1].list
+      );
+    };
+    assert_eq!(tab.position, Some("5cm".into()));
+    let body = list[0].index_body().unwrap();
+    assert_eq!(
+      body.index_title.as_ref().unwrap().name.as_deref(),
+      Some("Index1_Head")
+    );
+    assert_eq!(body.paragraphs().count(), 2);
+
+    let OfficeTextItem::IllustrationIndex(illustrations) = &list[1] else {
+      panic!("expected illustration index, got {:?}", list[1]);
+    };
+    let source = illustrations.source.as_ref().unwrap();
+    assert_eq!(source.caption_sequence_name.as_deref(), Some("Figure"));
+    assert!(source.entry_template.is_some());
+
+    let OfficeTextItem::TableIndex(tables) = &list[2] else {
+      panic!("expected table index, got {:?}", list[2]);
+    };
+    assert_eq!(tables.source.as_ref().unwrap().use_caption, Some(false));
+
+    let OfficeTextItem::Bibliography(bibliography) = &list[3] else {
+      panic!("expected bibliography, got {:?}", list[3]);
+    };
+    let template = &bibliography.source.as_ref().unwrap().entry_templates[0];
+    assert_eq!(template.bibliography_type.as_deref(), Some("book"));
+    let IndexEntryTemplateItem::IndexEntryBibliography(field) =
+      &template.list[2]
+    else {
+      panic!("expected bibliography field, got {:?}", template.list[2]);
+    };
+    assert_eq!(field.data_field, "title");
+
+    let xml = write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn regenerates_table_of_contents() {
+    let content = content(
+      r#"<text:table-of-content text:name="TOC">
+  <text:table-of-content-source text:outline-level="2">
+    <text:table-of-content-entry-template text:outline-level="1" text:style-name="Contents_20_1">
+      <text:index-entry-link-start/><text:index-entry-link-start text:style-name="Index_20_Link"/>
+      <text:index-entry-chapter/><text:index-entry-text/><text:index-entry-tab-stop style:type="right"/><text:index-entry-page-number/>
+      <text:index-entry-link-end/><text:index-entry-link-end/>
+    </text:table-of-content-entry-template>
+  </text:table-of-content-source>
+  <text:index-body>
+    <text:index-title><text:p>Contents</text:p></text:index-title>
+    <text:p>Stale</text:p>
+  </text:index-body>
+</text:table-of-content>
+<text:h text:outline-level="1"><text:bookmark-start text:name="__RefHeading___Toc1"/>Intro<text:bookmark-end text:name="__RefHeading___Toc1"/></text:h>
+<text:section><text:h text:outline-level="2">Scope <text:span>and</text:span> aims</text:h></text:section>
+<text:h text:outline-level="3">Too deep</text:h>"#,
+    );
+    let regenerated = content.regenerate_tables_of_content();
+    let OfficeTextItem::TableOfContent(toc) = &regenerated.body.text.list[0]
+    else {
+      panic!("expected table of contents");
+    };
+    let xml = String::from_utf8(write_content(&regenerated).unwrap()).unwrap();
+    assert!(xml.contains(
+      r##"<text:index-body><text:index-title><text:p>Contents</text:p></text:index-title><text:p text:style-name="Contents_20_1"><text:a xlink:type="simple" xlink:href="#__RefHeading___Toc1" text:style-name="Index_20_Link" text:visited-style-name="Index_20_Link">Intro<text:tab/></text:a></text:p><text:p text:style-name="Contents_20_2">Scope and aims</text:p></text:index-body>"##
+    ));
+    assert_eq!(toc.index_body.list.len(), 2);
+    // The copy only differs in the index body.
+    assert_ne!(regenerated, content);
+    assert_eq!(regenerated.body.text.list[1..], content.body.text.list[1..]);
+  }
+}
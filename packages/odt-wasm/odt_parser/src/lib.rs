@@ -1,11 +1,44 @@
+pub mod annotations;
+pub mod change_resolver;
 pub mod core;
+pub mod document;
+pub mod error;
+pub mod fields;
+pub mod flat;
+pub mod indexes;
+pub mod links;
+pub mod lossless;
+pub mod manifest;
+pub mod master_pages;
+pub mod namespaces;
 pub mod ods_structs;
+pub mod package;
+pub mod paragraph_properties;
+pub mod stream;
+pub mod style_resolver;
+pub mod table_grid;
+pub mod text_properties;
+pub mod tracked_changes;
+pub mod units;
+pub mod walk;
+pub mod writer;
 
+pub use change_resolver::ChangeFilter;
+pub use change_resolver::Decision;
+pub use core::open;
+pub use core::open_lossless;
+pub use document::OdtDocument;
+pub use error::OdtError;
+pub use manifest::Manifest;
+pub use package::Package;
+pub use stream::ContentStream;
+pub use style_resolver::StyleResolver;
+pub use writer::write;
+
+use serde_wasm_bindgen::from_value;
 use serde_wasm_bindgen::to_value;
 use wasm_bindgen::prelude::*;
 
-use console_error_panic_hook;
-
 #[wasm_bindgen]
 pub fn init_debug() {
   console_error_panic_hook::set_once();
@@ -17,18 +50,352 @@ pub fn echo(zip_data: Vec<u8>) -> Vec<u8> {
 }
 
 #[wasm_bindgen]
-pub fn unzip(zip_data: Vec<u8>) -> JsValue {
-  to_value(&core::unzip(zip_data)).unwrap()
+pub fn unzip(zip_data: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&core::unzip(zip_data)?)?)
+}
+
+/// "package" for a zipped .odt, "flat" for flat XML (.fodt).
+#[wasm_bindgen]
+pub fn detect_format(data: &[u8]) -> Option<String> {
+  core::detect_format(data).map(|format| {
+    match format {
+      core::DocumentFormat::Package => "package",
+      core::DocumentFormat::Flat => "flat",
+    }
+    .to_string()
+  })
+}
+
+/// Splits a flat XML document into the same files `unzip` returns.
+#[wasm_bindgen]
+pub fn split_flat(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&flat::split(&xml_bytes)?)?)
+}
+
+#[wasm_bindgen]
+pub fn parse_content(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&core::parse_content(xml_bytes)?)?)
+}
+
+#[wasm_bindgen]
+pub fn parse_styles(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&core::parse_styles(xml_bytes)?)?)
 }
 
+/// Expands a `table:table` object of `parse_content` into rows of equal
+/// length, see `TableTable::to_grid`.
 #[wasm_bindgen]
-pub fn parse_content(xml_bytes: Vec<u8>) -> JsValue {
-  to_value(&core::parse_content(xml_bytes)).unwrap()
+pub fn table_grid(table: JsValue) -> Result<JsValue, JsValue> {
+  let table: ods_structs::TableTable = from_value(table)?;
+  Ok(to_value(&table.to_grid()?)?)
 }
 
+/// A `parse_content` object with its tables of contents rebuilt from the
+/// headings, see `TableOfContent::regenerate`.
 #[wasm_bindgen]
-pub fn parse_styles(xml_bytes: Vec<u8>) -> JsValue {
-  to_value(&core::parse_styles(xml_bytes)).unwrap()
+pub fn regenerate_tables_of_content(
+  content: JsValue,
+) -> Result<JsValue, JsValue> {
+  let content: ods_structs::DocumentContent = from_value(content)?;
+  Ok(to_value(&content.regenerate_tables_of_content())?)
+}
+
+#[wasm_bindgen]
+pub fn parse_meta(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&core::parse_meta(xml_bytes)?)?)
+}
+
+/// meta.xml for an object returned by `parse_meta`.
+#[wasm_bindgen]
+pub fn write_meta(meta: JsValue) -> Result<Vec<u8>, JsValue> {
+  let meta: ods_structs::DocumentMeta = from_value(meta)?;
+  Ok(writer::write_meta(&meta)?)
+}
+
+/// Like `parse_content`, but unknown elements and attributes are kept (as
+/// `odt:raw` items and `$extra` fields) and `write_odt` writes them back.
+#[wasm_bindgen]
+pub fn parse_content_lossless(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&core::parse_content_lossless(xml_bytes)?)?)
+}
+
+#[wasm_bindgen]
+pub fn parse_styles_lossless(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  Ok(to_value(&core::parse_styles_lossless(xml_bytes)?)?)
+}
+
+/// Streams the block-level items of content.xml. The returned object is an
+/// async iterator: `for await (const block of stream_content(bytes))`.
+#[wasm_bindgen]
+pub fn stream_content(xml_bytes: Vec<u8>) -> Result<JsValue, JsValue> {
+  let stream = JsValue::from(JsContentStream {
+    inner: ContentStream::new(xml_bytes)?,
+  });
+  // `Object.prototype.valueOf` returns `this`, which is all
+  // `[Symbol.asyncIterator]()` has to do.
+  let value_of =
+    js_sys::Reflect::get(&js_sys::Object::new(), &JsValue::from("valueOf"))?;
+  js_sys::Reflect::set(&stream, &js_sys::Symbol::async_iterator(), &value_of)?;
+  Ok(stream)
+}
+
+#[wasm_bindgen(js_name = ContentStream)]
+pub struct JsContentStream {
+  inner: ContentStream,
+}
+
+#[wasm_bindgen(js_class = ContentStream)]
+impl JsContentStream {
+  pub fn automatic_styles(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(self.inner.automatic_styles())?)
+  }
+
+  /// A promise of the next `{ done, value }` iterator result.
+  #[allow(clippy::should_implement_trait)]
+  pub fn next(&mut self) -> js_sys::Promise {
+    let result = js_sys::Object::new();
+    let (done, value) = match self.inner.next() {
+      Some(Ok(item)) => match to_value(&item) {
+        Ok(value) => (false, value),
+        Err(err) => return js_sys::Promise::reject(&err.into()),
+      },
+      Some(Err(err)) => return js_sys::Promise::reject(&err.into()),
+      None => (true, JsValue::UNDEFINED),
+    };
+    let _ = js_sys::Reflect::set(&result, &"done".into(), &done.into());
+    let _ = js_sys::Reflect::set(&result, &"value".into(), &value);
+    js_sys::Promise::resolve(&result)
+  }
+}
+
+/// Builds an .odt package from the objects returned by `parse_content`,
+/// `parse_styles` and `parse_meta`. `styles` and `meta` may be omitted.
+#[wasm_bindgen]
+pub fn write_odt(
+  content: JsValue,
+  styles: JsValue,
+  meta: JsValue,
+) -> Result<Vec<u8>, JsValue> {
+  let styles = if styles.is_undefined() || styles.is_null() {
+    Default::default()
+  } else {
+    from_value(styles)?
+  };
+  let meta = if meta.is_undefined() || meta.is_null() {
+    None
+  } else {
+    Some(from_value(meta)?)
+  };
+  let document = OdtDocument {
+    mimetype: None,
+    content: from_value(content)?,
+    styles,
+    meta,
+    settings: None,
+    manifest: None,
+    media: Default::default(),
+  };
+  Ok(writer::write(&document)?)
+}
+
+/// JS handle to a zipped package whose parts are read on demand.
+#[wasm_bindgen(js_name = OdtPackage)]
+pub struct JsPackage {
+  inner: Package,
+}
+
+#[wasm_bindgen(js_class = OdtPackage)]
+impl JsPackage {
+  #[wasm_bindgen(constructor)]
+  pub fn new(zip_data: Vec<u8>) -> Result<JsPackage, JsValue> {
+    Ok(Self {
+      inner: Package::new(zip_data)?,
+    })
+  }
+
+  pub fn manifest(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.manifest())?)
+  }
+
+  /// Manifest entries present in the zip, only those whose media type
+  /// starts with `media_type` if given (eg. "image/").
+  pub fn entries(
+    &self,
+    media_type: Option<String>,
+  ) -> Result<JsValue, JsValue> {
+    let entries = self
+      .inner
+      .entries_of_type(media_type.as_deref().unwrap_or_default());
+    Ok(to_value(&entries)?)
+  }
+
+  pub fn read(&mut self, path: &str) -> Result<Vec<u8>, JsValue> {
+    Ok(self.inner.read(path)?)
+  }
+}
+
+/// JS handle to an opened .odt package.
+#[wasm_bindgen(js_name = OdtDocument)]
+pub struct JsOdtDocument {
+  inner: OdtDocument,
+}
+
+#[wasm_bindgen(js_class = OdtDocument)]
+impl JsOdtDocument {
+  #[wasm_bindgen(constructor)]
+  pub fn new(zip_data: Vec<u8>) -> Result<JsOdtDocument, JsValue> {
+    Ok(Self {
+      inner: core::open(zip_data)?,
+    })
+  }
+
+  /// Opens the package keeping unknown elements and attributes, see
+  /// `parse_content_lossless`.
+  pub fn open_lossless(zip_data: Vec<u8>) -> Result<JsOdtDocument, JsValue> {
+    Ok(Self {
+      inner: core::open_lossless(zip_data)?,
+    })
+  }
+
+  #[wasm_bindgen(getter)]
+  pub fn mimetype(&self) -> Option<String> {
+    self.inner.mimetype.clone()
+  }
+
+  pub fn content(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content)?)
+  }
+
+  pub fn styles(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.styles)?)
+  }
+
+  /// Effective text and paragraph properties of a style, eg.
+  /// `resolve_style("P1", "paragraph")`.
+  pub fn resolve_style(
+    &self,
+    name: &str,
+    family: &str,
+  ) -> Result<JsValue, JsValue> {
+    let resolver =
+      StyleResolver::new(&self.inner.styles, Some(&self.inner.content));
+    Ok(to_value(&resolver.resolve(name, family)?)?)
+  }
+
+  /// Like `resolve_style`, for the style names used in headers and footers,
+  /// which refer to the automatic styles of styles.xml.
+  pub fn resolve_master_page_style(
+    &self,
+    name: &str,
+    family: &str,
+  ) -> Result<JsValue, JsValue> {
+    let resolver = StyleResolver::for_master_pages(&self.inner.styles);
+    Ok(to_value(&resolver.resolve(name, family)?)?)
+  }
+
+  /// Master pages of styles.xml, with their header and footer content.
+  pub fn master_pages(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(self.inner.styles.master_pages())?)
+  }
+
+  /// Page size, margins, orientation and columns of a master page, eg.
+  /// `page_layout("Standard")`. `undefined` when the master page is missing.
+  pub fn page_layout(&self, master_page: &str) -> Result<JsValue, JsValue> {
+    let styles = &self.inner.styles;
+    let page_layout = styles
+      .master_page(master_page)
+      .and_then(|master_page| styles.master_page_layout(master_page));
+    Ok(to_value(&page_layout)?)
+  }
+
+  /// The body blocks starting a master page, with the master page whose
+  /// headers and footers apply from there.
+  pub fn master_page_starts(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.master_page_starts()?)?)
+  }
+
+  /// Footnotes and endnotes in citation order, with their number.
+  pub fn notes(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content.notes())?)
+  }
+
+  /// Comments with their text range, see `DocumentContent::annotations`.
+  pub fn annotations(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content.annotations())?)
+  }
+
+  /// Page numbers, dates, cross-references and other fields in document
+  /// order, see `DocumentContent::fields`.
+  pub fn fields(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content.fields())?)
+  }
+
+  /// Hyperlinks with the bookmark or heading of internal ones, see
+  /// `DocumentContent::links`.
+  pub fn links(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content.links())?)
+  }
+
+  /// What an `xlink:href` points to in the body.
+  pub fn resolve_anchor(&self, href: &str) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content.resolve_anchor(href))?)
+  }
+
+  /// Changed regions joined to their markers, see
+  /// `DocumentContent::tracked_changes`.
+  pub fn tracked_changes(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.content.tracked_changes())?)
+  }
+
+  /// The content with the changes matching `filter` (`{ author, before }`,
+  /// every change when omitted) accepted, see
+  /// `DocumentContent::resolve_changes`.
+  pub fn accept_changes(&self, filter: JsValue) -> Result<JsValue, JsValue> {
+    self.resolve_changes(Decision::Accept, filter)
+  }
+
+  /// Like `accept_changes`, rejecting the changes.
+  pub fn reject_changes(&self, filter: JsValue) -> Result<JsValue, JsValue> {
+    self.resolve_changes(Decision::Reject, filter)
+  }
+
+  fn resolve_changes(
+    &self,
+    decision: Decision,
+    filter: JsValue,
+  ) -> Result<JsValue, JsValue> {
+    let filter: Option<ChangeFilter> = from_value(filter)?;
+    let content = self
+      .inner
+      .content
+      .resolve_changes(decision, &filter.unwrap_or_default());
+    Ok(to_value(&content)?)
+  }
+
+  pub fn meta(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.meta)?)
+  }
+
+  pub fn settings(&self) -> Option<String> {
+    self.inner.settings.clone()
+  }
+
+  pub fn manifest(&self) -> Result<JsValue, JsValue> {
+    Ok(to_value(&self.inner.manifest)?)
+  }
+
+  pub fn part_names(&self) -> Vec<String> {
+    self.inner.part_names()
+  }
+
+  pub fn part(&self, name: &str) -> Option<Vec<u8>> {
+    self.inner.part(name).map(|bytes| bytes.to_vec())
+  }
+
+  /// The document zipped back into an .odt package.
+  pub fn write(&self) -> Result<Vec<u8>, JsValue> {
+    Ok(writer::write(&self.inner)?)
+  }
 }
 
 #[wasm_bindgen]
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::OfficeText;
+use crate::ods_structs::TextHeading;
+use crate::ods_structs::TextLink;
+use crate::walk::markers;
+use crate::walk::text_blocks;
+use crate::walk::Marker;
+use crate::walk::TextBlock;
+use crate::walk::TextPosition;
+
+/// What the `xlink:href` of a link points to.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum LinkTarget<'a> {
+  /// Not a `#` anchor: a URL or a path.
+  External,
+  /// A `text:bookmark` or `text:bookmark-start`, such as the
+  /// `__RefHeading__` bookmarks of headings. `heading` is set when the
+  /// bookmark is in one.
+  Bookmark {
+    name: &'a str,
+    position: TextPosition,
+    heading: Option<&'a TextHeading>,
+  },
+  /// A heading, from the `#1.2.Text|outline` anchors of LibreOffice.
+  Heading {
+    position: TextPosition,
+    heading: &'a TextHeading,
+  },
+  /// An anchor nothing in the body matches.
+  Missing,
+}
+
+/// A hyperlink of the body with what it points to.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ResolvedLink<'a> {
+  pub link: &'a TextLink,
+  pub position: TextPosition,
+  pub target: LinkTarget<'a>,
+}
+
+impl TextLink {
+  /// The anchor name of a link into the document, without `#`.
+  pub fn anchor(&self) -> Option<&str> {
+    self.href.strip_prefix('#')
+  }
+}
+
+impl DocumentContent {
+  /// Resolves an `xlink:href` against the bookmarks and headings of the
+  /// body.
+  pub fn resolve_anchor(&self, href: &str) -> LinkTarget<'_> {
+    Anchors::new(&self.body.text).resolve(href)
+  }
+
+  /// Every hyperlink of the body in document order, with its target.
+  pub fn links(&self) -> Vec<ResolvedLink<'_>> {
+    let anchors = Anchors::new(&self.body.text);
+    anchors
+      .markers
+      .iter()
+      .filter_map(|(position, marker)| match marker {
+        Marker::Link(link) => Some(ResolvedLink {
+          link,
+          position: *position,
+          target: anchors.resolve(&link.href),
+        }),
+        _ => None,
+      })
+      .collect()
+  }
+}
+
+struct Anchors<'a> {
+  blocks: Vec<TextBlock<'a>>,
+  markers: Vec<(TextPosition, Marker<'a>)>,
+  /// First bookmark of each name.
+  bookmarks: HashMap<&'a str, TextPosition>,
+}
+
+impl<'a> Anchors<'a> {
+  fn new(text: &'a OfficeText) -> Self {
+    let markers = markers(text);
+    let mut bookmarks = HashMap::new();
+    for (position, marker) in &markers {
+      if let Marker::Bookmark(name) = marker {
+        bookmarks.entry(*name).or_insert(*position);
+      }
+    }
+    Self {
+      blocks: text_blocks(text),
+      markers,
+      bookmarks,
+    }
+  }
+
+  fn resolve(&self, href: &str) -> LinkTarget<'a> {
+    let Some(anchor) = href.strip_prefix('#') else {
+      return LinkTarget::External;
+    };
+    let decoded = percent_decode(anchor);
+    for name in [anchor, decoded.as_str()] {
+      if let Some((&name, &position)) = self.bookmarks.get_key_value(name) {
+        let heading = match self.blocks[position.block] {
+          TextBlock::Heading(heading) => Some(heading),
+          TextBlock::Paragraph(_) => None,
+        };
+        return LinkTarget::Bookmark {
+          name,
+          position,
+          heading,
+        };
+      }
+      if let Some(title) = name.strip_suffix("|outline") {
+        if let Some(target) = self.heading(title) {
+          return target;
+        }
+      }
+    }
+    LinkTarget::Missing
+  }
+
+  // The title is the heading text, after its number when it has one.
+  fn heading(&self, title: &str) -> Option<LinkTarget<'a>> {
+    self.blocks.iter().enumerate().find_map(|(index, block)| {
+      let TextBlock::Heading(heading) = block else {
+        return None;
+      };
+      let text = block.text();
+      let numbered = title.strip_suffix(text.as_str()).is_some_and(|number| {
+        number
+          .chars()
+          .all(|char| char.is_ascii_digit() || char == '.')
+      });
+      numbered.then_some(LinkTarget::Heading {
+        position: TextPosition {
+          block: index,
+          offset: 0,
+        },
+        heading,
+      })
+    })
+  }
+}
+
+/// Decodes `%XX` escapes, leaving invalid ones as they are.
+fn percent_decode(value: &str) -> String {
+  let mut bytes = Vec::with_capacity(value.len());
+  let mut rest = value.as_bytes();
+  while let Some((&byte, tail)) = rest.split_first() {
+    let escaped = (byte == b'%')
+      .then(|| tail.get(..2))
+      .flatten()
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match escaped {
+      Some(decoded) => {
+        bytes.push(decoded);
+        rest = &tail[2..];
+      }
+      None => {
+        bytes.push(byte);
+        rest = tail;
+      }
+    }
+  }
+  String::from_utf8(bytes)
+    .unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::open;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::writer::write_content;
+
+  const BODY: &str = r##"
+    <text:h text:outline-level="1"><text:bookmark-start text:name="__RefHeading___Toc12_3"/>Setup<text:bookmark-end text:name="__RefHeading___Toc12_3"/></text:h>
+    <text:p>See <text:a xlink:type="simple" xlink:href="#__RefHeading___Toc12_3" office:target-frame-name="_self" xlink:show="replace" office:title="Go to setup" text:style-name="Internet_20_link" text:visited-style-name="Visited_20_Internet_20_Link">setup</text:a>, <text:a xlink:href="#1.Setup|outline">this</text:a>, <text:a xlink:href="#my%20mark">mark</text:a><text:bookmark text:name="my mark"/>.</text:p>
+    <text:p><text:a xlink:href="https://example.com/" office:target-frame-name="_blank" xlink:show="new">web</text:a> <text:a xlink:href="#Table1|table">table</text:a></text:p>"##;
+
+  #[test]
+  fn resolves_links() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let links = content.links();
+    assert_eq!(links.len(), 5);
+
+    let first = links[0].link;
+    assert_eq!(first.link_type.as_deref(), Some("simple"));
+    assert_eq!(first.target_frame_name.as_deref(), Some("_self"));
+    assert_eq!(first.show.as_deref(), Some("replace"));
+    assert_eq!(first.title.as_deref(), Some("Go to setup"));
+    assert_eq!(
+      first.visited_style_name.as_deref(),
+      Some("Visited_20_Internet_20_Link")
+    );
+    assert_eq!(first.anchor(), Some("__RefHeading___Toc12_3"));
+    assert_eq!(
+      links[0].position,
+      TextPosition {
+        block: 1,
+        offset: 4
+      }
+    );
+    let LinkTarget::Bookmark {
+      name,
+      position,
+      heading: Some(heading),
+    } = links[0].target
+    else {
+      panic!("expected bookmark in heading, got {:?}", links[0].target);
+    };
+    assert_eq!(name, "__RefHeading___Toc12_3");
+    assert_eq!(position.block, 0);
+    assert_eq!(heading.outline_level, Some(1));
+
+    assert!(matches!(
+      links[1].target,
+      LinkTarget::Heading { position, .. } if position.block == 0
+    ));
+    assert!(matches!(
+      links[2].target,
+      LinkTarget::Bookmark {
+        name: "my mark",
+        heading: None,
+        ..
+      }
+    ));
+    assert_eq!(links[3].target, LinkTarget::External);
+    assert_eq!(links[3].link.target_frame_name.as_deref(), Some("_blank"));
+    assert_eq!(links[4].target, LinkTarget::Missing);
+    assert_eq!(content.resolve_anchor("#Setup|outline"), links[1].target);
+
+    let xml = write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn header_link_fixture() {
+    let document = open(
+      std::fs::read("../../extension-odt/test/wgd/header-link.odt").unwrap(),
+    )
+    .unwrap();
+    let links = document.content.links();
+    let targets: Vec<_> = links
+      .iter()
+      .map(|link| (link.link.href.as_str(), link.target))
+      .collect();
+    assert!(matches!(
+      targets[..],
+      [
+        ("#n2kl3gvfts47", LinkTarget::Bookmark { heading: None, .. }),
+        (_, LinkTarget::External)
+      ]
+    ));
+  }
+}
@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+
+use quick_xml::events::BytesEnd;
+use quick_xml::events::BytesStart;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use quick_xml::Writer;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::error::OdtError;
+use crate::namespaces::qualified_name;
+use crate::namespaces::qualified_start;
+use crate::ods_structs::Extra;
+use crate::ods_structs::RawElement;
+
+// The lossless parser first deserializes the model as usual, then walks the
+// normalized XML next to the model serialized as JSON. Attributes without a
+// `@` field and elements without a field or `$value` variant end up in the
+// `$extra` of their parent, together with the known element they precede.
+// `Unknown` items of `$value` lists are replaced by `odt:raw` items. The JSON
+// is then deserialized back into the model.
+
+/// Attaches the unknown attributes and elements of `xml` (normalized) to
+/// `value`, the serialized model of the same document.
+pub(crate) fn attach_unknown(
+  xml: &[u8],
+  value: &mut Value,
+) -> Result<(), OdtError> {
+  let mut reader = Reader::from_reader(xml);
+  loop {
+    match read_event(&mut reader)? {
+      Event::Start(e) => return walk(&mut reader, &e, false, value),
+      Event::Empty(e) => return walk(&mut reader, &e, true, value),
+      Event::Eof => return Ok(()),
+      _ => {}
+    }
+  }
+}
+
+fn read_event<'a>(
+  reader: &mut Reader<&'a [u8]>,
+) -> Result<Event<'a>, OdtError> {
+  reader.read_event().map_err(|err| OdtError::XmlSyntax {
+    message: err.to_string(),
+    offset: reader.error_position(),
+  })
+}
+
+fn walk(
+  reader: &mut Reader<&[u8]>,
+  start: &BytesStart,
+  is_empty: bool,
+  value: &mut Value,
+) -> Result<(), OdtError> {
+  // Unit variants and text have nowhere to keep unknown content.
+  let Value::Object(map) = value else {
+    return skip(reader, is_empty);
+  };
+
+  let mut extra = Extra::default();
+  for attr in start.attributes() {
+    let attr = attr.map_err(|err| OdtError::XmlSyntax {
+      message: err.to_string(),
+      offset: reader.buffer_position(),
+    })?;
+    let name = qualified_name(attr.key.as_ref());
+    if !map.contains_key(&format!("@{}", name)) {
+      let value = attr.unescape_value().map_err(|err| OdtError::XmlSyntax {
+        message: err.to_string(),
+        offset: reader.buffer_position(),
+      })?;
+      extra.attributes.push((name, value.to_string()));
+    }
+  }
+
+  let mut children = Children {
+    seen: HashMap::new(),
+    cursor: 0,
+  };
+  if !is_empty {
+    loop {
+      match read_event(reader)? {
+        Event::Start(e) => children.walk(reader, &e, false, map, &mut extra)?,
+        Event::Empty(e) => children.walk(reader, &e, true, map, &mut extra)?,
+        Event::End(_) | Event::Eof => break,
+        _ => {}
+      }
+    }
+  }
+
+  // In the order the writer puts them back, which follows the fields of the
+  // model rather than the source.
+  extra.elements.sort_by_key(|raw| match &raw.before {
+    Some((name, index)) => match map.keys().position(|key| key == name) {
+      Some(field) => (field, *index),
+      None => (usize::MAX, 0),
+    },
+    None => (usize::MAX, 0),
+  });
+
+  if !extra.is_empty() {
+    let extra =
+      serde_json::to_value(extra).map_err(|err| OdtError::Serialize {
+        message: err.to_string(),
+      })?;
+    map.insert("$extra".to_string(), extra);
+  }
+  Ok(())
+}
+
+// Matches the child elements of a node to the fields of its model.
+struct Children {
+  // Occurrences of each named field so far.
+  seen: HashMap<String, usize>,
+  // Next item of the `$value` list.
+  cursor: usize,
+}
+
+impl Children {
+  fn walk(
+    &mut self,
+    reader: &mut Reader<&[u8]>,
+    start: &BytesStart,
+    is_empty: bool,
+    map: &mut Map<String, Value>,
+    extra: &mut Extra,
+  ) -> Result<(), OdtError> {
+    let name = qualified_name(start.name().as_ref());
+
+    if let Some(field) = map.get_mut(&name) {
+      let index = self.seen.entry(name.clone()).or_default();
+      // Unknown elements so far are written back before this one.
+      for raw in &mut extra.elements {
+        if raw.before.is_none() {
+          raw.before = Some((name.clone(), *index));
+        }
+      }
+      let target = match field {
+        Value::Array(items) => items.get_mut(*index),
+        field if *index == 0 => Some(field),
+        _ => None,
+      };
+      *index += 1;
+      return match target {
+        Some(target) => walk(reader, start, is_empty, target),
+        None => skip(reader, is_empty),
+      };
+    }
+
+    let Some(Value::Array(items)) = map.get_mut("$value") else {
+      extra.elements.push(RawElement {
+        xml: capture(reader, start, is_empty)?,
+        before: None,
+      });
+      return Ok(());
+    };
+
+    // Text items have no element of their own.
+    while items.get(self.cursor).is_some_and(is_text) {
+      self.cursor += 1;
+    }
+    let Some(item) = items.get_mut(self.cursor) else {
+      return skip(reader, is_empty);
+    };
+    self.cursor += 1;
+
+    match item {
+      Value::String(variant) if variant == "Unknown" => {
+        let raw = capture(reader, start, is_empty)?;
+        *item = Value::Object(Map::from_iter([(
+          "odt:raw".to_string(),
+          Value::String(raw),
+        )]));
+        Ok(())
+      }
+      Value::Object(variant) => match variant.get_mut(&name) {
+        Some(inner) => walk(reader, start, is_empty, inner),
+        None => skip(reader, is_empty),
+      },
+      _ => skip(reader, is_empty),
+    }
+  }
+}
+
+fn is_text(item: &Value) -> bool {
+  matches!(item, Value::Object(map) if map.contains_key("$text"))
+}
+
+fn skip(reader: &mut Reader<&[u8]>, is_empty: bool) -> Result<(), OdtError> {
+  if is_empty {
+    return Ok(());
+  }
+  let mut depth = 0;
+  loop {
+    match read_event(reader)? {
+      Event::Start(_) => depth += 1,
+      Event::End(_) if depth == 0 => return Ok(()),
+      Event::End(_) => depth -= 1,
+      Event::Eof => return Ok(()),
+      _ => {}
+    }
+  }
+}
+
+/// Raw XML of the element at `start`, with names in their qualified form.
+fn capture(
+  reader: &mut Reader<&[u8]>,
+  start: &BytesStart,
+  is_empty: bool,
+) -> Result<String, OdtError> {
+  let mut writer = Writer::new(Vec::new());
+  let mut write = |event: Event| {
+    writer
+      .write_event(event)
+      .map_err(|err| OdtError::Serialize {
+        message: err.to_string(),
+      })
+  };
+
+  if is_empty {
+    write(Event::Empty(qualified_start(start)))?;
+  } else {
+    write(Event::Start(qualified_start(start)))?;
+    let mut depth = 0;
+    loop {
+      match read_event(reader)? {
+        Event::Start(e) => {
+          depth += 1;
+          write(Event::Start(qualified_start(&e)))?;
+        }
+        Event::Empty(e) => write(Event::Empty(qualified_start(&e)))?,
+        Event::End(e) => {
+          let name = qualified_name(e.name().as_ref());
+          write(Event::End(BytesEnd::new(name)))?;
+          if depth == 0 {
+            break;
+          }
+          depth -= 1;
+        }
+        Event::Eof => break,
+        event => write(event)?,
+      }
+    }
+  }
+
+  let raw = writer.into_inner();
+  Ok(String::from_utf8_lossy(&raw).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use quick_xml::events::Event;
+  use quick_xml::Reader;
+
+  use crate::core::open;
+  use crate::core::open_lossless;
+  use crate::core::parse_content;
+  use crate::core::parse_content_lossless;
+  use crate::core::parse_styles_lossless;
+  use crate::core::unzip;
+  use crate::namespaces::normalize;
+  use crate::namespaces::qualified_name;
+  use crate::ods_structs::*;
+  use crate::writer::write_content;
+  use crate::writer::write_styles;
+
+  const CONTENT: &[u8] = br#"<office:document-content
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+  xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+  xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0"
+  xmlns:ext="http://example.com/ext"
+  office:version="1.3">
+  <office:font-face-decls>
+    <style:font-face style:name="Arial" svg:font-family="Arial"/>
+  </office:font-face-decls>
+  <office:automatic-styles/>
+  <office:body><office:text>
+    <text:sequence-decls><text:sequence-decl text:name="Table"/></text:sequence-decls>
+    <text:p text:style-name="P1" text:cond-style-name="C1">Hello <text:file-name
+      text:display="full">1 &amp; 2</text:file-name><ext:mark ext:id="7"/></text:p>
+  </office:text></office:body>
+</office:document-content>"#;
+
+  #[test]
+  fn keeps_unknown_content() {
+    let content = parse_content_lossless(CONTENT.to_vec()).unwrap();
+
+    assert_eq!(
+      content.font_face_decls[0].extra.elements,
+      vec![RawElement {
+        xml: r#"<style:font-face style:name="Arial" svg:font-family="Arial"/>"#
+          .to_string(),
+        before: None,
+      }]
+    );
+    assert!(content.extra.attributes.contains(&(
+      "xmlns:ext".to_string(),
+      "http://example.com/ext".to_string()
+    )));
+
+    let list = &content.body.text.list;
+    assert_eq!(
+      list[0],
+      OfficeTextItem::Raw(
+        r#"<text:sequence-decls><text:sequence-decl text:name="Table"/></text:sequence-decls>"#
+          .to_string()
+      )
+    );
+    let OfficeTextItem::TextParagraph(para) = &list[1] else {
+      panic!("expected paragraph, got {:?}", list[1]);
+    };
+    assert_eq!(
+      para.extra.attributes,
+      vec![("text:cond-style-name".to_string(), "C1".to_string())]
+    );
+    assert_eq!(
+      para.list[1],
+      TextParagraphItem::Raw(
+        r#"<text:file-name text:display="full">1 &amp; 2</text:file-name>"#
+          .to_string()
+      )
+    );
+    assert_eq!(
+      para.list[2],
+      TextParagraphItem::Raw(r#"<ext:mark ext:id="7"/>"#.to_string())
+    );
+
+    // The default parser drops all of it.
+    let content = parse_content(CONTENT.to_vec()).unwrap();
+    assert!(content.extra.is_empty());
+    assert_eq!(content.body.text.list[0], OfficeTextItem::Unknown);
+  }
+
+  #[test]
+  fn writes_unknown_content_back() {
+    let mut content = parse_content_lossless(CONTENT.to_vec()).unwrap();
+    let OfficeTextItem::TextParagraph(para) = &mut content.body.text.list[1]
+    else {
+      panic!("expected paragraph");
+    };
+    para.list[0] = TextParagraphItem::Text("Goodbye ".to_string());
+
+    let xml = String::from_utf8(write_content(&content).unwrap()).unwrap();
+    assert_eq!(xml.matches(r#"office:version="1.3""#).count(), 1);
+    assert!(xml.contains(r#"xmlns:ext="http://example.com/ext""#));
+    assert!(xml.contains(
+      r#"<office:font-face-decls><style:font-face style:name="Arial" svg:font-family="Arial"/></office:font-face-decls>"#
+    ));
+    assert!(xml.contains(
+      r#"<office:text><text:sequence-decls><text:sequence-decl text:name="Table"/></text:sequence-decls>"#
+    ));
+    assert!(xml.contains(
+      r#"<text:p text:style-name="P1" text:cond-style-name="C1">Goodbye <text:file-name text:display="full">1 &amp; 2</text:file-name><ext:mark ext:id="7"/></text:p>"#
+    ));
+
+    let reparsed = parse_content_lossless(xml.into_bytes()).unwrap();
+    assert_eq!(reparsed, content);
+  }
+
+  // Elements with their attributes, and text, in document order.
+  fn outline(xml: &[u8]) -> Vec<String> {
+    let xml = normalize(xml).unwrap().xml;
+    let mut reader = Reader::from_reader(xml.as_slice());
+    let mut outline = vec![];
+    loop {
+      match reader.read_event().unwrap() {
+        Event::Start(e) | Event::Empty(e) => {
+          let mut attributes: Vec<String> = e
+            .attributes()
+            .map(|attr| {
+              let attr = attr.unwrap();
+              let name = qualified_name(attr.key.as_ref());
+              format!("{}={}", name, attr.unescape_value().unwrap())
+            })
+            .filter(|attr| !attr.starts_with("xmlns"))
+            .collect();
+          attributes.sort();
+          let name = qualified_name(e.name().as_ref());
+          outline.push(format!("<{} {}>", name, attributes.join(" ")));
+        }
+        Event::Text(e) => {
+          let text = e.xml_content().unwrap();
+          if !text.trim().is_empty() {
+            outline.push(text.to_string());
+          }
+        }
+        Event::Eof => return outline,
+        _ => {}
+      }
+    }
+  }
+
+  #[test]
+  fn writes_unknown_elements_in_place() {
+    let mut parts = unzip(std::fs::read("src/example.odt").unwrap()).unwrap();
+    let original = parts.remove("content.xml").unwrap();
+    let content = parse_content_lossless(original.clone()).unwrap();
+    assert_eq!(
+      content.extra.elements,
+      vec![RawElement {
+        xml: "<office:scripts/>".to_string(),
+        before: Some(("office:font-face-decls".to_string(), 0)),
+      }]
+    );
+
+    let written = write_content(&content).unwrap();
+    assert_eq!(outline(&written), outline(&original));
+  }
+
+  #[test]
+  fn round_trips_fixtures() {
+    let mut paths = vec![std::path::PathBuf::from("src/example.odt")];
+    for entry in std::fs::read_dir("../../extension-odt/test/wgd").unwrap() {
+      let path = entry.unwrap().path();
+      if path.extension().is_some_and(|ext| ext == "odt") {
+        paths.push(path);
+      }
+    }
+
+    for path in paths {
+      let document = open_lossless(std::fs::read(&path).unwrap())
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+      let content = write_content(&document.content).unwrap();
+      let styles = write_styles(&document.styles).unwrap();
+      assert_eq!(
+        parse_content_lossless(content).unwrap(),
+        document.content,
+        "{:?}",
+        path
+      );
+      assert_eq!(
+        parse_styles_lossless(styles).unwrap(),
+        document.styles,
+        "{:?}",
+        path
+      );
+    }
+
+    // Without lossless mode nothing is kept.
+    let document = open(std::fs::read("src/example.odt").unwrap()).unwrap();
+    assert!(document.content.font_face_decls[0].extra.is_empty());
+  }
+}
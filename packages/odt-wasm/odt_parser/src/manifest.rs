@@ -0,0 +1,246 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::parse_xml;
+use crate::error::OdtError;
+
+pub const MANIFEST_PATH: &str = "META-INF/manifest.xml";
+
+/// `META-INF/manifest.xml`, the list of every part of a package.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Manifest {
+  #[serde(rename = "@manifest:version")]
+  pub version: Option<String>,
+
+  #[serde(default)]
+  #[serde(rename = "manifest:file-entry")]
+  pub entries: Vec<FileEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FileEntry {
+  /// Path in the package, `/` for the document itself. Directories (eg. an
+  /// embedded object) end with `/`.
+  #[serde(rename = "@manifest:full-path")]
+  pub full_path: String,
+
+  #[serde(default)]
+  #[serde(rename = "@manifest:media-type")]
+  pub media_type: String,
+
+  #[serde(rename = "@manifest:version")]
+  pub version: Option<String>,
+
+  /// Size of the part before encryption.
+  #[serde(rename = "@manifest:size")]
+  pub size: Option<u64>,
+
+  #[serde(rename = "manifest:encryption-data")]
+  pub encryption_data: Option<EncryptionData>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EncryptionData {
+  #[serde(rename = "@manifest:checksum-type")]
+  pub checksum_type: Option<String>,
+
+  #[serde(rename = "@manifest:checksum")]
+  pub checksum: Option<String>,
+
+  #[serde(rename = "manifest:algorithm")]
+  pub algorithm: Option<Algorithm>,
+
+  #[serde(rename = "manifest:start-key-generation")]
+  pub start_key_generation: Option<StartKeyGeneration>,
+
+  #[serde(rename = "manifest:key-derivation")]
+  pub key_derivation: Option<KeyDerivation>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Algorithm {
+  #[serde(rename = "@manifest:algorithm-name")]
+  pub name: String,
+
+  #[serde(rename = "@manifest:initialisation-vector")]
+  pub initialisation_vector: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StartKeyGeneration {
+  #[serde(rename = "@manifest:start-key-generation-name")]
+  pub name: String,
+
+  #[serde(rename = "@manifest:key-size")]
+  pub key_size: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct KeyDerivation {
+  #[serde(rename = "@manifest:key-derivation-name")]
+  pub name: String,
+
+  #[serde(rename = "@manifest:salt")]
+  pub salt: Option<String>,
+
+  #[serde(rename = "@manifest:iteration-count")]
+  pub iteration_count: Option<u32>,
+
+  #[serde(rename = "@manifest:key-size")]
+  pub key_size: Option<u32>,
+}
+
+impl Manifest {
+  pub fn parse(xml_bytes: &[u8]) -> Result<Self, OdtError> {
+    parse_xml(xml_bytes)
+  }
+
+  pub fn entry(&self, full_path: &str) -> Option<&FileEntry> {
+    self
+      .entries
+      .iter()
+      .find(|entry| entry.full_path == full_path)
+  }
+
+  /// Entries of actual files, without the document (`/`) and directories.
+  pub fn files(&self) -> impl Iterator<Item = &FileEntry> {
+    self.entries.iter().filter(|entry| entry.is_file())
+  }
+
+  /// Files whose media type starts with `prefix`, eg. `image/`.
+  pub fn files_of_type<'a>(
+    &'a self,
+    prefix: &'a str,
+  ) -> impl Iterator<Item = &'a FileEntry> {
+    self
+      .files()
+      .filter(move |entry| entry.media_type.starts_with(prefix))
+  }
+
+  /// First entry with encryption data. ODF encrypts parts itself (the zip
+  /// entries are not password protected), so this is the only hint.
+  pub fn encrypted_entry(&self) -> Option<&FileEntry> {
+    self
+      .entries
+      .iter()
+      .find(|entry| entry.encryption_data.is_some())
+  }
+}
+
+impl FileEntry {
+  pub fn new(full_path: &str, media_type: &str) -> Self {
+    Self {
+      full_path: full_path.to_string(),
+      media_type: media_type.to_string(),
+      ..Default::default()
+    }
+  }
+
+  pub fn is_file(&self) -> bool {
+    self.full_path != "/" && !self.full_path.ends_with('/')
+  }
+}
+
+/// Media type of a part by its extension, for parts missing from the
+/// manifest.
+pub fn guess_media_type(path: &str) -> &'static str {
+  let extension = path.rsplit_once('.').map(|(_, ext)| ext.to_lowercase());
+  match extension.as_deref() {
+    Some("xml") => "text/xml",
+    Some("rdf") => "application/rdf+xml",
+    Some("png") => "image/png",
+    Some("jpg" | "jpeg") => "image/jpeg",
+    Some("gif") => "image/gif",
+    Some("svg") => "image/svg+xml",
+    Some("bmp") => "image/bmp",
+    Some("tif" | "tiff") => "image/tiff",
+    Some("emf") => "image/x-emf",
+    Some("wmf") => "image/x-wmf",
+    Some("pdf") => "application/pdf",
+    _ => "",
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::package::Package;
+
+  #[test]
+  fn parses_example() {
+    let mut package =
+      Package::new(std::fs::read("src/example.odt").unwrap()).unwrap();
+    let manifest =
+      Manifest::parse(&package.read(MANIFEST_PATH).unwrap()).unwrap();
+
+    assert_eq!(manifest.version.as_deref(), Some("1.2"));
+    let root = manifest.entry("/").unwrap();
+    assert_eq!(root.media_type, "application/vnd.oasis.opendocument.text");
+    assert!(!root.is_file());
+    assert_eq!(
+      manifest.entry("Object 4/").unwrap().media_type,
+      "application/vnd.oasis.opendocument.formula"
+    );
+    assert_eq!(
+      manifest.entry("content.xml").unwrap().media_type,
+      "text/xml"
+    );
+    assert!(manifest
+      .files_of_type("image/")
+      .all(|entry| entry.full_path.starts_with("Pictures/")
+        || entry.full_path.starts_with("Thumbnails/")));
+    assert!(manifest.files_of_type("image/").count() > 0);
+    assert_eq!(manifest.encrypted_entry(), None);
+  }
+
+  #[test]
+  fn parses_encryption_data() {
+    let xml = br#"<manifest:manifest
+  xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0"
+  manifest:version="1.2">
+  <manifest:file-entry manifest:full-path="/"
+    manifest:media-type="application/vnd.oasis.opendocument.text"/>
+  <manifest:file-entry manifest:full-path="content.xml"
+    manifest:media-type="text/xml" manifest:size="1234">
+    <manifest:encryption-data
+      manifest:checksum-type="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0#sha256-1k"
+      manifest:checksum="Y2hlY2s=">
+      <manifest:algorithm
+        manifest:algorithm-name="http://www.w3.org/2001/04/xmlenc#aes256-cbc"
+        manifest:initialisation-vector="aXY="/>
+      <manifest:start-key-generation
+        manifest:start-key-generation-name="http://www.w3.org/2000/09/xmldsig#sha256"
+        manifest:key-size="32"/>
+      <manifest:key-derivation manifest:key-derivation-name="PBKDF2"
+        manifest:key-size="32" manifest:iteration-count="100000"
+        manifest:salt="c2FsdA=="/>
+    </manifest:encryption-data>
+  </manifest:file-entry>
+</manifest:manifest>"#;
+    let manifest = Manifest::parse(xml).unwrap();
+
+    let entry = manifest.encrypted_entry().unwrap();
+    assert_eq!(entry.full_path, "content.xml");
+    assert_eq!(entry.size, Some(1234));
+    let data = entry.encryption_data.as_ref().unwrap();
+    assert_eq!(
+      data.algorithm.as_ref().unwrap().name,
+      "http://www.w3.org/2001/04/xmlenc#aes256-cbc"
+    );
+    let derivation = data.key_derivation.as_ref().unwrap();
+    assert_eq!(derivation.name, "PBKDF2");
+    assert_eq!(derivation.iteration_count, Some(100000));
+    assert_eq!(derivation.salt.as_deref(), Some("c2FsdA=="));
+    assert_eq!(
+      data.start_key_generation.as_ref().unwrap().key_size,
+      Some(32)
+    );
+  }
+
+  #[test]
+  fn guesses_media_types() {
+    assert_eq!(guess_media_type("Pictures/a.JPEG"), "image/jpeg");
+    assert_eq!(guess_media_type("Pictures/a.svg"), "image/svg+xml");
+    assert_eq!(guess_media_type("Object 1"), "");
+  }
+}
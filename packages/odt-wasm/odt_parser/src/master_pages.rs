@@ -0,0 +1,506 @@
+use serde::Serialize;
+
+use crate::document::OdtDocument;
+use crate::error::OdtError;
+use crate::ods_structs::DocumentStyles;
+use crate::ods_structs::HeaderFooter;
+use crate::ods_structs::MasterPage;
+use crate::ods_structs::OfficeTextItem;
+use crate::ods_structs::PageLayout;
+use crate::style_resolver::StyleResolver;
+use crate::walk::blocks;
+use crate::walk::TextBlock;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum HeaderFooterKind {
+  Header,
+  HeaderLeft,
+  HeaderFirst,
+  Footer,
+  FooterLeft,
+  FooterFirst,
+}
+
+/// A body block that starts a master page, and so its headers and footers.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct MasterPageStart<'a> {
+  /// Index in [`text_blocks`](crate::walk::text_blocks) of the body.
+  pub block: usize,
+  /// Indices of the body item that holds the block, through `office:text`
+  /// and the sections it is nested in.
+  pub path: Vec<usize>,
+  pub master_page: &'a MasterPage,
+}
+
+impl MasterPage {
+  /// The headers and footers of the master page, without hidden ones.
+  pub fn headers_footers(&self) -> Vec<(HeaderFooterKind, &HeaderFooter)> {
+    [
+      (HeaderFooterKind::Header, &self.header),
+      (HeaderFooterKind::HeaderLeft, &self.header_left),
+      (HeaderFooterKind::HeaderFirst, &self.header_first),
+      (HeaderFooterKind::Footer, &self.footer),
+      (HeaderFooterKind::FooterLeft, &self.footer_left),
+      (HeaderFooterKind::FooterFirst, &self.footer_first),
+    ]
+    .into_iter()
+    .filter_map(|(kind, content)| Some((kind, content.as_ref()?)))
+    .filter(|(_, content)| content.display != Some(false))
+    .collect()
+  }
+}
+
+impl HeaderFooter {
+  /// Paragraphs and headings of the content, as
+  /// [`text_blocks`](crate::walk::text_blocks) gives them for the body.
+  /// Their styles resolve with [`StyleResolver::for_master_pages`].
+  pub fn text_blocks(&self) -> Vec<TextBlock<'_>> {
+    blocks(&self.list)
+  }
+}
+
+impl DocumentStyles {
+  pub fn master_pages(&self) -> &[MasterPage] {
+    self
+      .master_styles
+      .as_ref()
+      .map(|master_styles| &master_styles.master_pages[..])
+      .unwrap_or_default()
+  }
+
+  pub fn master_page(&self, name: &str) -> Option<&MasterPage> {
+    self
+      .master_pages()
+      .iter()
+      .find(|master_page| master_page.name.as_deref() == Some(name))
+  }
+
+  pub fn page_layout(&self, name: &str) -> Option<&PageLayout> {
+    self
+      .automatic_styles
+      .page_layouts
+      .iter()
+      .find(|page_layout| page_layout.name.as_deref() == Some(name))
+  }
+
+  /// Page layout of a master page, or the default page layout when it names
+  /// none or a missing one.
+  pub fn master_page_layout(
+    &self,
+    master_page: &MasterPage,
+  ) -> Option<&PageLayout> {
+    master_page
+      .page_layout_name
+      .as_deref()
+      .and_then(|name| self.page_layout(name))
+      .or(self.styles.default_page_layout.as_ref())
+  }
+}
+
+impl OdtDocument {
+  /// Where each master page starts in the body, from the
+  /// `style:master-page-name` of paragraph and table styles. A body item
+  /// starts at most one master page, the first one its blocks name. The
+  /// first page uses "Standard" unless the first block names another master
+  /// page.
+  pub fn master_page_starts(
+    &self,
+  ) -> Result<Vec<MasterPageStart<'_>>, OdtError> {
+    let resolver = StyleResolver::new(&self.styles, Some(&self.content));
+    let mut starts = Vec::new();
+    self.find_master_page_starts(
+      &resolver,
+      &self.content.body.text.list,
+      &mut Vec::new(),
+      &mut 0,
+      &mut starts,
+    )?;
+
+    if starts.first().is_none_or(|start| start.block > 0) {
+      let standard = self
+        .styles
+        .master_page("Standard")
+        .or(self.styles.master_pages().first());
+      if let Some(master_page) = standard {
+        starts.insert(
+          0,
+          MasterPageStart {
+            block: 0,
+            path: vec![0],
+            master_page,
+          },
+        );
+      }
+    }
+    Ok(starts)
+  }
+
+  fn find_master_page_starts<'a>(
+    &'a self,
+    resolver: &StyleResolver,
+    items: &'a [OfficeTextItem],
+    path: &mut Vec<usize>,
+    block: &mut usize,
+    starts: &mut Vec<MasterPageStart<'a>>,
+  ) -> Result<(), OdtError> {
+    for (index, item) in items.iter().enumerate() {
+      path.push(index);
+      if let OfficeTextItem::TextSection(section) = item {
+        self.find_master_page_starts(
+          resolver,
+          &section.list,
+          path,
+          block,
+          starts,
+        )?;
+        path.pop();
+        continue;
+      }
+
+      let item_blocks = blocks(std::slice::from_ref(item));
+      let table = match item {
+        OfficeTextItem::TableTable(table) => table.style_name.as_deref(),
+        _ => None,
+      };
+      let styles = table.map(|name| (0, name, "table")).into_iter().chain(
+        item_blocks
+          .iter()
+          .enumerate()
+          .filter_map(|(offset, text_block)| {
+            Some((offset, text_block.style_name()?, "paragraph"))
+          }),
+      );
+      for (offset, style_name, family) in styles {
+        let style = resolver.resolve(style_name, family)?;
+        let master_page = style
+          .master_page_name
+          .as_deref()
+          .and_then(|name| self.styles.master_page(name));
+        if let Some(master_page) = master_page {
+          starts.push(MasterPageStart {
+            block: *block + offset,
+            path: path.clone(),
+            master_page,
+          });
+          break;
+        }
+      }
+      *block += item_blocks.len();
+      path.pop();
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::open;
+  use crate::core::parse_styles;
+  use crate::core::tests::styles_xml;
+  use crate::ods_structs::PrintOrientation;
+  use crate::ods_structs::WritingMode;
+  use crate::units::Length;
+  use crate::units::LengthUnit;
+  use crate::units::RelativeWidth;
+  use crate::writer::write_content;
+  use crate::writer::write_styles;
+
+  const STYLES: &str = r##"
+    <style:default-style style:family="paragraph">
+      <style:text-properties fo:font-size="12pt"/>
+    </style:default-style>
+    <style:default-page-layout>
+      <style:page-layout-properties style:writing-mode="lr-tb"/>
+    </style:default-page-layout>
+    <style:style style:name="Standard" style:family="paragraph"/>"##;
+
+  const AUTOMATIC_STYLES: &str = r##"
+    <style:style style:name="MP1" style:family="paragraph" style:parent-style-name="Standard">
+      <style:paragraph-properties fo:text-align="end"/>
+    </style:style>
+    <style:page-layout style:name="Mpm1" style:page-usage="mirrored">
+      <style:page-layout-properties fo:page-width="29.7cm" fo:page-height="21cm" style:num-format="1" style:print-orientation="landscape" fo:margin-top="2cm" fo:margin-bottom="2cm" fo:margin-left="2.5cm" fo:margin-right="2.5cm" fo:background-color="transparent" style:writing-mode="bt-lr">
+        <style:columns fo:column-count="2" fo:column-gap="0.5cm">
+          <style:column style:rel-width="4822*" fo:start-indent="0cm" fo:end-indent="0.25cm"/>
+          <style:column style:rel-width="4822*" fo:start-indent="0.25cm" fo:end-indent="0cm"/>
+        </style:columns>
+      </style:page-layout-properties>
+      <style:header-style>
+        <style:header-footer-properties fo:min-height="1.27cm" fo:margin-bottom="0.5cm" style:dynamic-spacing="true"/>
+      </style:header-style>
+      <style:footer-style/>
+    </style:page-layout>"##;
+
+  const MASTER_STYLES: &str = r##"
+    <style:master-page style:name="Standard" style:page-layout-name="Mpm1" draw:style-name="Mdp1">
+      <style:header>
+        <text:p text:style-name="MP1">ACME Ltd.</text:p>
+        <table:table table:name="Letterhead">
+          <table:table-column/>
+          <table:table-row><table:table-cell><text:p>Street 1</text:p></table:table-cell></table:table-row>
+        </table:table>
+      </style:header>
+      <style:footer style:display="false"><text:p>Page</text:p></style:footer>
+    </style:master-page>
+    <style:master-page style:name="Endnote" style:display-name="Endnote page" style:page-layout-name="Missing" style:next-style-name="Standard"/>"##;
+
+  #[test]
+  fn parses_master_pages() {
+    let styles =
+      parse_styles(styles_xml(STYLES, AUTOMATIC_STYLES, MASTER_STYLES))
+        .unwrap();
+    assert_eq!(styles.styles.default_styles.len(), 1);
+    assert_eq!(styles.automatic_styles.styles.len(), 1);
+
+    let standard = styles.master_page("Standard").unwrap();
+    assert_eq!(standard.draw_style_name.as_deref(), Some("Mdp1"));
+    let layout = styles.master_page_layout(standard).unwrap();
+    assert_eq!(layout.page_usage.as_deref(), Some("mirrored"));
+    let properties = layout.page_layout_properties.as_ref().unwrap();
+    assert_eq!(
+      properties
+        .page_width
+        .as_ref()
+        .and_then(|width| width.value()),
+      Some(&Length::new(29.7, LengthUnit::Cm))
+    );
+    assert_eq!(properties.page_height, Some("21cm".into()));
+    assert_eq!(
+      properties.print_orientation,
+      Some(PrintOrientation::Landscape)
+    );
+    assert_eq!(properties.margin_left, Some("2.5cm".into()));
+    assert_eq!(
+      properties.writing_mode,
+      Some(WritingMode::Raw("bt-lr".to_string()))
+    );
+    let columns = properties.columns.as_ref().unwrap();
+    assert_eq!(columns.column_count, Some(2));
+    assert_eq!(columns.columns[1].start_indent, Some("0.25cm".into()));
+    assert_eq!(
+      columns.columns[1]
+        .rel_width
+        .as_ref()
+        .and_then(|width| width.value()),
+      Some(&RelativeWidth(4822))
+    );
+    let header_style = layout.header_style.as_ref().unwrap();
+    let header_properties =
+      header_style.header_footer_properties.as_ref().unwrap();
+    assert_eq!(header_properties.min_height, Some("1.27cm".into()));
+    assert_eq!(header_properties.dynamic_spacing, Some(true));
+    assert!(layout
+      .footer_style
+      .as_ref()
+      .unwrap()
+      .header_footer_properties
+      .is_none());
+
+    let header = standard.header.as_ref().unwrap();
+    assert!(matches!(header.list[0], OfficeTextItem::TextParagraph(_)));
+    assert!(matches!(header.list[1], OfficeTextItem::TableTable(_)));
+    assert_eq!(standard.footer.as_ref().unwrap().display, Some(false));
+
+    let endnote = styles.master_page("Endnote").unwrap();
+    assert_eq!(endnote.display_name.as_deref(), Some("Endnote page"));
+    assert_eq!(endnote.next_style_name.as_deref(), Some("Standard"));
+    let default = styles.master_page_layout(endnote).unwrap();
+    assert_eq!(Some(default), styles.styles.default_page_layout.as_ref());
+    assert!(styles.master_page("Missing").is_none());
+
+    let xml = write_styles(&styles).unwrap();
+    assert_eq!(parse_styles(xml).unwrap(), styles);
+  }
+
+  #[test]
+  fn keeps_unknown_print_orientations() {
+    let automatic_styles = r##"
+    <style:page-layout style:name="Mpm1">
+      <style:page-layout-properties style:print-orientation="Portrait"/>
+    </style:page-layout>"##;
+    let styles =
+      parse_styles(styles_xml(STYLES, automatic_styles, "")).unwrap();
+    let layout = &styles.automatic_styles.page_layouts[0];
+    let properties = layout.page_layout_properties.as_ref().unwrap();
+    assert_eq!(
+      properties.print_orientation,
+      Some(PrintOrientation::Raw("Portrait".to_string()))
+    );
+
+    let xml = write_styles(&styles).unwrap();
+    assert!(String::from_utf8_lossy(&xml)
+      .contains(r#"style:print-orientation="Portrait""#));
+    assert_eq!(parse_styles(xml).unwrap(), styles);
+  }
+
+  #[test]
+  fn lettered_list_fixture() {
+    let document = open(
+      std::fs::read("../../extension-odt/test/wgd/lettered-list.odt").unwrap(),
+    )
+    .unwrap();
+    let standard = document.styles.master_page("Standard").unwrap();
+    assert_eq!(standard.page_layout_name.as_deref(), Some("Mpm1"));
+    assert_eq!(standard.header.as_ref().unwrap().list.len(), 1);
+    assert_eq!(standard.footer.as_ref().unwrap().list.len(), 1);
+    let layout = document.styles.master_page_layout(standard).unwrap();
+    assert!(layout.page_layout_properties.is_some());
+  }
+
+  const LETTERHEAD: &[u8] = br#"<office:document
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+  xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+  xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+  xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0"
+  office:mimetype="application/vnd.oasis.opendocument.text">
+  <office:font-face-decls/>
+  <office:styles>
+    <style:style style:name="Standard" style:family="paragraph"/>
+    <style:style style:name="Annex" style:family="paragraph" style:parent-style-name="Standard" style:master-page-name="Wide"/>
+  </office:styles>
+  <office:automatic-styles>
+    <style:style style:name="P1" style:family="paragraph" style:parent-style-name="Annex" style:master-page-name=""/>
+    <style:style style:name="MP1" style:family="paragraph" style:parent-style-name="Standard">
+      <style:text-properties fo:font-weight="bold"/>
+    </style:style>
+    <style:page-layout style:name="Mpm1"/>
+  </office:automatic-styles>
+  <office:master-styles>
+    <style:master-page style:name="Standard" style:page-layout-name="Mpm1">
+      <style:header>
+        <text:p text:style-name="MP1">ACME Ltd.</text:p>
+        <table:table><table:table-column/><table:table-row><table:table-cell><text:p>Street 1</text:p></table:table-cell></table:table-row></table:table>
+      </style:header>
+      <style:header-first><text:p>Letterhead</text:p></style:header-first>
+      <style:footer style:display="false"><text:p>Hidden</text:p></style:footer>
+      <style:footer-first><text:p>Registered in Poland</text:p></style:footer-first>
+    </style:master-page>
+    <style:master-page style:name="Wide" style:page-layout-name="Mpm1">
+      <style:header-left><text:p>Annexes</text:p></style:header-left>
+      <style:footer-left><text:p>Left</text:p></style:footer-left>
+    </style:master-page>
+  </office:master-styles>
+  <office:body><office:text>
+    <text:p>Dear customer,</text:p>
+    <text:p text:style-name="P1">Kept on the page</text:p>
+    <text:p text:style-name="Annex">Annex A</text:p>
+    <text:p text:style-name="Annex">Annex B</text:p>
+  </office:text></office:body>
+</office:document>"#;
+
+  #[test]
+  fn imports_headers_and_footers() {
+    let document = open(LETTERHEAD.to_vec()).unwrap();
+    let starts: Vec<(usize, Option<&str>)> = document
+      .master_page_starts()
+      .unwrap()
+      .iter()
+      .map(|start| (start.block, start.master_page.name.as_deref()))
+      .collect();
+    assert_eq!(
+      starts,
+      vec![(0, Some("Standard")), (2, Some("Wide")), (3, Some("Wide"))]
+    );
+    let paths: Vec<Vec<usize>> = document
+      .master_page_starts()
+      .unwrap()
+      .into_iter()
+      .map(|start| start.path)
+      .collect();
+    assert_eq!(paths, vec![vec![0], vec![2], vec![3]]);
+
+    let standard = document.styles.master_page("Standard").unwrap();
+    let kinds: Vec<HeaderFooterKind> = standard
+      .headers_footers()
+      .iter()
+      .map(|(kind, _)| *kind)
+      .collect();
+    assert_eq!(
+      kinds,
+      vec![
+        HeaderFooterKind::Header,
+        HeaderFooterKind::HeaderFirst,
+        HeaderFooterKind::FooterFirst
+      ]
+    );
+    let header = standard.header.as_ref().unwrap().text_blocks();
+    let texts: Vec<String> = header.iter().map(TextBlock::text).collect();
+    assert_eq!(texts, vec!["ACME Ltd.", "Street 1"]);
+
+    let resolver = StyleResolver::for_master_pages(&document.styles);
+    let style = resolver
+      .resolve(header[0].style_name().unwrap(), "paragraph")
+      .unwrap();
+    assert!(style.text_properties.is_bold());
+    assert_eq!(style.chain, vec!["MP1", "Standard"]);
+
+    let wide = document.styles.master_page("Wide").unwrap();
+    assert!(wide.header.is_none());
+    let left = wide.header_left.as_ref().unwrap().text_blocks();
+    assert_eq!(left[0].text(), "Annexes");
+    assert_eq!(wide.headers_footers().len(), 2);
+
+    let xml = write_styles(&document.styles).unwrap();
+    assert_eq!(parse_styles(xml).unwrap(), document.styles);
+  }
+
+  const TABLES: &[u8] = br#"<office:document
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+  xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+  xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+  office:mimetype="application/vnd.oasis.opendocument.text">
+  <office:font-face-decls/>
+  <office:styles>
+    <style:style style:name="Standard" style:family="paragraph"/>
+  </office:styles>
+  <office:automatic-styles>
+    <style:style style:name="Table1" style:family="table" style:master-page-name="Wide"/>
+    <style:style style:name="Table2" style:family="table" style:master-page-name="Standard"/>
+    <style:style style:name="P1" style:family="paragraph" style:parent-style-name="Standard" style:master-page-name="Wide"/>
+  </office:automatic-styles>
+  <office:master-styles>
+    <style:master-page style:name="Standard"/>
+    <style:master-page style:name="Wide"/>
+  </office:master-styles>
+  <office:body><office:text>
+    <text:p>Intro</text:p>
+    <table:table table:style-name="Table1">
+      <table:table-column/>
+      <table:table-row><table:table-cell><text:p text:style-name="P1">Wide</text:p></table:table-cell></table:table-row>
+    </table:table>
+    <text:section text:name="Back">
+      <text:p>Before</text:p>
+      <table:table table:style-name="Table2">
+        <table:table-column/>
+        <table:table-row><table:table-cell><text:p>Narrow</text:p></table:table-cell></table:table-row>
+      </table:table>
+    </text:section>
+  </office:text></office:body>
+</office:document>"#;
+
+  #[test]
+  fn starts_master_pages_at_tables() {
+    let document = open(TABLES.to_vec()).unwrap();
+    let starts: Vec<(usize, Vec<usize>, Option<&str>)> = document
+      .master_page_starts()
+      .unwrap()
+      .into_iter()
+      .map(|start| (start.block, start.path, start.master_page.name.as_deref()))
+      .collect();
+    assert_eq!(
+      starts,
+      vec![
+        (0, vec![0], Some("Standard")),
+        (1, vec![1], Some("Wide")),
+        (3, vec![2, 1], Some("Standard")),
+      ]
+    );
+
+    let content = write_content(&document.content).unwrap();
+    assert!(String::from_utf8_lossy(&content)
+      .contains(r#"table:style-name="Table1""#));
+  }
+}
@@ -0,0 +1,291 @@
+use quick_xml::events::attributes::Attribute;
+use quick_xml::events::BytesEnd;
+use quick_xml::events::BytesStart;
+use quick_xml::events::Event;
+use quick_xml::name::QName;
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+use quick_xml::Writer;
+
+use crate::error::OdtError;
+
+// Canonical prefixes, as written by LibreOffice.
+pub const NAMESPACES: &[(&str, &str)] = &[
+  ("office", "urn:oasis:names:tc:opendocument:xmlns:office:1.0"),
+  ("style", "urn:oasis:names:tc:opendocument:xmlns:style:1.0"),
+  ("text", "urn:oasis:names:tc:opendocument:xmlns:text:1.0"),
+  ("table", "urn:oasis:names:tc:opendocument:xmlns:table:1.0"),
+  ("draw", "urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"),
+  (
+    "fo",
+    "urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0",
+  ),
+  ("xlink", "http://www.w3.org/1999/xlink"),
+  ("dc", "http://purl.org/dc/elements/1.1/"),
+  ("meta", "urn:oasis:names:tc:opendocument:xmlns:meta:1.0"),
+  (
+    "number",
+    "urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0",
+  ),
+  (
+    "svg",
+    "urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0",
+  ),
+  ("chart", "urn:oasis:names:tc:opendocument:xmlns:chart:1.0"),
+  ("dr3d", "urn:oasis:names:tc:opendocument:xmlns:dr3d:1.0"),
+  ("math", "http://www.w3.org/1998/Math/MathML"),
+  ("form", "urn:oasis:names:tc:opendocument:xmlns:form:1.0"),
+  ("script", "urn:oasis:names:tc:opendocument:xmlns:script:1.0"),
+  (
+    "presentation",
+    "urn:oasis:names:tc:opendocument:xmlns:presentation:1.0",
+  ),
+  ("config", "urn:oasis:names:tc:opendocument:xmlns:config:1.0"),
+  (
+    "manifest",
+    "urn:oasis:names:tc:opendocument:xmlns:manifest:1.0",
+  ),
+  (
+    "smil",
+    "urn:oasis:names:tc:opendocument:xmlns:smil-compatible:1.0",
+  ),
+  (
+    "anim",
+    "urn:oasis:names:tc:opendocument:xmlns:animation:1.0",
+  ),
+  ("db", "urn:oasis:names:tc:opendocument:xmlns:database:1.0"),
+  ("ooo", "http://openoffice.org/2004/office"),
+  ("ooow", "http://openoffice.org/2004/writer"),
+  ("oooc", "http://openoffice.org/2004/calc"),
+  ("dom", "http://www.w3.org/2001/xml-events"),
+  ("xforms", "http://www.w3.org/2002/xforms"),
+  ("xsd", "http://www.w3.org/2001/XMLSchema"),
+  ("xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+  ("rpt", "http://openoffice.org/2005/report"),
+  ("of", "urn:oasis:names:tc:opendocument:xmlns:of:1.2"),
+  ("xhtml", "http://www.w3.org/1999/xhtml"),
+  ("grddl", "http://www.w3.org/2003/g/data-view#"),
+  ("officeooo", "http://openoffice.org/2009/office"),
+  ("tableooo", "http://openoffice.org/2009/table"),
+  ("drawooo", "http://openoffice.org/2010/draw"),
+  (
+    "calcext",
+    "urn:org:documentfoundation:names:experimental:calc:xmlns:calcext:1.0",
+  ),
+  (
+    "loext",
+    "urn:org:documentfoundation:names:experimental:office:xmlns:loext:1.0",
+  ),
+  (
+    "field",
+    "urn:openoffice:names:experimental:ooo-ms-interop:xmlns:field:1.0",
+  ),
+  (
+    "formx",
+    "urn:openoffice:names:experimental:ooxml-odf-interop:xmlns:form:1.0",
+  ),
+  ("css3t", "http://www.w3.org/TR/css3-text/"),
+  ("xml", "http://www.w3.org/XML/1998/namespace"),
+];
+
+pub fn canonical_prefix(uri: &[u8]) -> Option<&'static str> {
+  NAMESPACES
+    .iter()
+    .find(|(_, ns)| ns.as_bytes() == uri)
+    .map(|(prefix, _)| *prefix)
+}
+
+pub fn namespace_uri(prefix: &str) -> Option<&'static str> {
+  NAMESPACES
+    .iter()
+    .find(|(p, _)| *p == prefix)
+    .map(|(_, uri)| *uri)
+}
+
+/// Turns a normalized name (`:text:p`) back into its qualified form.
+pub fn qualified_name(name: &[u8]) -> String {
+  let name = name.strip_prefix(b":").unwrap_or(name);
+  String::from_utf8_lossy(name).to_string()
+}
+
+/// Start tag of a normalized element with its names in qualified form.
+pub fn qualified_start(start: &BytesStart) -> BytesStart<'static> {
+  let mut qualified = BytesStart::new(qualified_name(start.name().as_ref()));
+  for attr in start.attributes().flatten() {
+    let key = qualified_name(attr.key.as_ref());
+    qualified.push_attribute(Attribute {
+      key: QName(key.as_bytes()),
+      value: attr.value,
+    });
+  }
+  qualified.into_owned()
+}
+
+/// Declares every namespace of [`NAMESPACES`] on a root element.
+pub fn declare_namespaces(start: &mut BytesStart) {
+  for (prefix, uri) in NAMESPACES.iter().filter(|(prefix, _)| *prefix != "xml")
+  {
+    start.push_attribute((format!("xmlns:{}", prefix).as_str(), *uri));
+  }
+}
+
+/// XML with every name rewritten to its canonical `prefix:local` form.
+pub struct NormalizedXml {
+  pub xml: Vec<u8>,
+  // (normalized offset, source offset) of every element start.
+  offsets: Vec<(usize, usize)>,
+}
+
+impl NormalizedXml {
+  /// Maps an offset in `xml` back to the offset in the source document.
+  pub fn source_offset(&self, offset: usize) -> usize {
+    let idx = self.offsets.partition_point(|(pos, _)| *pos <= offset);
+    match idx {
+      0 => offset,
+      idx => {
+        let (pos, source) = self.offsets[idx - 1];
+        source + (offset - pos)
+      }
+    }
+  }
+}
+
+/// Resolves element and attribute names by namespace URI, so the serde model
+/// can match on `text:p` no matter which prefix the producer declared.
+///
+/// quick-xml's deserializer keys elements and attributes by the part after
+/// the first `:`, so resolved names are written as `:text:p` and reach serde
+/// as `text:p`. Names in namespaces not listed in [`NAMESPACES`] keep the
+/// prefix they were written with, and so does their declaration. Declarations
+/// of the listed namespaces are dropped.
+pub fn normalize(xml_bytes: &[u8]) -> Result<NormalizedXml, OdtError> {
+  let mut reader = NsReader::from_reader(xml_bytes);
+  let mut writer = Writer::new(Vec::with_capacity(xml_bytes.len()));
+  let mut offsets = Vec::new();
+
+  loop {
+    let source_pos = reader.buffer_position() as usize;
+    let event = reader.read_event().map_err(|err| OdtError::XmlSyntax {
+      message: err.to_string(),
+      offset: reader.error_position(),
+    })?;
+
+    let event = match event {
+      Event::Start(e) => {
+        offsets.push((writer.get_ref().len(), source_pos));
+        Event::Start(resolve_start(&reader, &e)?)
+      }
+      Event::Empty(e) => {
+        offsets.push((writer.get_ref().len(), source_pos));
+        Event::Empty(resolve_start(&reader, &e)?)
+      }
+      Event::End(e) => {
+        let name =
+          resolve_name(reader.resolver().resolve_element(e.name()), e.name());
+        Event::End(BytesEnd::new(name))
+      }
+      Event::Eof => break,
+      event => event,
+    };
+
+    writer
+      .write_event(event)
+      .map_err(|err| OdtError::XmlSyntax {
+        message: err.to_string(),
+        offset: source_pos as u64,
+      })?;
+  }
+
+  Ok(NormalizedXml {
+    xml: writer.into_inner(),
+    offsets,
+  })
+}
+
+fn resolve_start(
+  reader: &NsReader<&[u8]>,
+  e: &BytesStart,
+) -> Result<BytesStart<'static>, OdtError> {
+  let name =
+    resolve_name(reader.resolver().resolve_element(e.name()), e.name());
+  let mut start = BytesStart::new(name);
+
+  for attr in e.attributes() {
+    let attr = attr.map_err(|err| OdtError::XmlSyntax {
+      message: err.to_string(),
+      offset: reader.buffer_position(),
+    })?;
+    if attr.key.as_namespace_binding().is_some() {
+      // Other namespaces are kept declared for the raw XML of unknown
+      // elements, see lossless.rs.
+      if canonical_prefix(&attr.value).is_none() {
+        start.push_attribute(attr);
+      }
+      continue;
+    }
+    let key =
+      resolve_name(reader.resolver().resolve_attribute(attr.key), attr.key);
+    start.push_attribute(Attribute {
+      key: QName(key.as_bytes()),
+      value: attr.value,
+    });
+  }
+
+  Ok(start.into_owned())
+}
+
+fn resolve_name(
+  (result, local): (ResolveResult, quick_xml::name::LocalName),
+  name: QName,
+) -> String {
+  let local = String::from_utf8_lossy(local.as_ref());
+  let prefix = match result {
+    ResolveResult::Bound(ns) => canonical_prefix(ns.as_ref())
+      .map(|prefix| prefix.to_string())
+      .or_else(|| name.prefix().map(|p| prefix_str(p.as_ref()))),
+    // Undeclared prefix, take it as written.
+    ResolveResult::Unknown(prefix) => Some(prefix_str(&prefix)),
+    ResolveResult::Unbound => None,
+  };
+
+  match prefix {
+    Some(prefix) => format!(":{}:{}", prefix, local),
+    None => local.to_string(),
+  }
+}
+
+fn prefix_str(prefix: &[u8]) -> String {
+  String::from_utf8_lossy(prefix).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn producer_prefixes() {
+    let xml = br#"<o:document-content
+  xmlns:o="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:t="urn:oasis:names:tc:opendocument:xmlns:text:1.0">
+  <o:body><t:p t:style-name="P1">a &amp; b</t:p></o:body>
+</o:document-content>"#;
+    let normalized = normalize(xml).unwrap();
+    assert_eq!(
+      String::from_utf8(normalized.xml).unwrap(),
+      r#"<:office:document-content>
+  <:office:body><:text:p :text:style-name="P1">a &amp; b</:text:p></:office:body>
+</:office:document-content>"#
+    );
+  }
+
+  #[test]
+  fn default_namespace() {
+    let xml = br#"<p xmlns="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+  xml:id="x"><span/></p>"#;
+    let normalized = normalize(xml).unwrap();
+    assert_eq!(
+      String::from_utf8(normalized.xml).unwrap(),
+      r#"<:text:p :xml:id="x"><:text:span/></:text:p>"#
+    );
+  }
+}
@@ -1,69 +1,186 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::units::Color;
+use crate::units::Length;
+use crate::units::LengthOrPercent;
+use crate::units::Parsed;
+use crate::units::Percent;
+use crate::units::RelativeWidth;
+
 // https://en.wikipedia.org/wiki/OpenDocument_technical_specification
 // TODO https://git.libreoffice.org/core/+/refs/heads/master/schema/libreoffice/OpenDocument-v1.4+libreoffice-schema.rng
 // TODO https://git.libreoffice.org/core/+/refs/heads/master/schema/odf1.4/OpenDocument-v1.4-schema.rng
 // https://docs.libreoffice.org/schema.html
 // https://wiki.documentfoundation.org/Development/ODF_Implementer_Notes/List_of_LibreOffice_ODF_Extensions
 
-// @TODO Serde prefixes: https://github.com/tafia/quick-xml/issues/218
+// Element and attribute names are resolved by namespace URI before
+// deserialization (see namespaces.rs), so renames use the canonical prefixes.
+
+// Every element struct has an `extra` field (`$extra`) and every enum of
+// child elements a `Raw` variant (`odt:raw`) next to `Unknown`, both added by
+// `lossless!`. The default parser leaves them empty and drops what the model
+// does not know as `Unknown`. The lossless parser (see lossless.rs) keeps
+// unknown attributes and elements there, and the writer puts them back
+// verbatim.
+macro_rules! lossless {
+  ($(#[$meta:meta])* $vis:vis struct $name:ident { $($field:tt)* }) => {
+    $(#[$meta])*
+    $vis struct $name {
+      $($field)*
+
+      #[serde(default)]
+      #[serde(rename = "$extra")]
+      #[serde(skip_serializing_if = "Extra::is_empty")]
+      pub extra: Extra,
+    }
+  };
+  ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:tt)* }) => {
+    $(#[$meta])*
+    $vis enum $name {
+      $($variant)*
+
+      #[serde(rename = "odt:raw")]
+      Raw(String),
+
+      #[serde(other)]
+      Unknown,
+    }
+  };
+}
+
+/// Attributes and child elements of a node that the model does not know.
+/// Only filled by the lossless parser, and written back verbatim.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Extra {
+  /// (qualified name, value)
+  #[serde(default)]
+  pub attributes: Vec<(String, String)>,
+  /// Each unknown child element.
+  #[serde(default)]
+  pub elements: Vec<RawElement>,
+}
+
+/// An unknown child element, see [`Extra`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RawElement {
+  /// Raw XML of the element.
+  pub xml: String,
+  /// The known child element that follows it, as (qualified name,
+  /// occurrence), so it is written back in its place. `None` after the last
+  /// one.
+  #[serde(default)]
+  pub before: Option<(String, usize)>,
+}
+
+impl Extra {
+  pub fn is_empty(&self) -> bool {
+    self.attributes.is_empty() && self.elements.is_empty()
+  }
+}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub enum TableCellItem {
-  //     #[serde(rename = "text:p")]
-  #[serde(rename = "p")]
-  TextParagraph(TextParagraph),
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum TableCellItem {
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
 
-  //     #[serde(rename = "table:table")]
-  #[serde(rename = "table")]
-  TableTable(TableTable),
+    #[serde(rename = "text:h")]
+    TextHeading(TextHeading),
 
-  //     #[serde(rename = "text:list")]
-  #[serde(rename = "list")]
-  TextList(TextList),
+    #[serde(rename = "table:table")]
+    TableTable(TableTable),
 
-  #[serde(other)]
-  Unknown,
+    #[serde(rename = "text:list")]
+    TextList(TextList),
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TableCell {
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  pub list: Vec<TableCellItem>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableCell {
+    #[serde(rename = "@table:number-columns-repeated")]
+    pub number_columns_repeated: Option<u32>,
+
+    #[serde(rename = "@table:number-columns-spanned")]
+    pub number_columns_spanned: Option<u32>,
+
+    #[serde(rename = "@table:number-rows-spanned")]
+    pub number_rows_spanned: Option<u32>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TableCellItem>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TableColumn {
-  //     #[serde(rename = "@table:number-columns-repeated")]
-  #[serde(default)]
-  #[serde(rename = "@number-columns-repeated")]
-  pub number_columns: u32,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableColumn {
+    #[serde(rename = "@table:number-columns-repeated")]
+    pub number_columns: Option<u32>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum TableRowItem {
+    #[serde(rename = "table:table-cell")]
+    TableCell(TableCell),
+
+    /// A cell hidden by the span of a previous one. It may still hold content.
+    #[serde(rename = "table:covered-table-cell")]
+    CoveredTableCell(TableCell),
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TableRow {
-  //     #[serde(rename = "table:table-cell")]
-  #[serde(rename = "table-cell")]
-  pub cells: Vec<TableCell>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableRow {
+    #[serde(rename = "@table:number-rows-repeated")]
+    pub number_rows_repeated: Option<u32>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TableRowItem>,
+  }
+}
+
+lossless! {
+  /// Rows repeated on each page the table spans.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableHeaderRows {
+    #[serde(default)]
+    #[serde(rename = "table:table-row")]
+    pub rows: Vec<TableRow>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TableTable {
-  //     #[serde(rename = "table:table-column")]
-  #[serde(rename = "table-column")]
-  pub columns: Vec<TableColumn>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableTable {
+    #[serde(rename = "@table:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "table:table-column")]
+    pub columns: Vec<TableColumn>,
 
-  //     #[serde(rename = "table:table-row")]
-  #[serde(rename = "table-row")]
-  pub rows: Vec<TableRow>,
+    #[serde(rename = "table:table-header-rows")]
+    pub header_rows: Option<TableHeaderRows>,
+
+    #[serde(default)]
+    #[serde(rename = "table:table-row")]
+    pub rows: Vec<TableRow>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct FontFaceDecl {}
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct FontFaceDecl {
+  }
+}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 struct Chars(u32);
 impl Default for Chars {
   fn default() -> Self {
@@ -71,125 +188,485 @@ impl Default for Chars {
   }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextSpace {
-  //     #[serde(rename = "@text:c")]
-  #[serde(default)]
-  #[serde(rename = "@c")]
-  chars: Chars,
+impl Chars {
+  fn is_default(&self) -> bool {
+    self.0 == 1
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub enum TextSpanItem {
-  //     #[serde(rename = "text:s")]
-  #[serde(rename = "s")]
-  TextSpace(TextSpace),
-  //     #[serde(rename = "text:tab")]
-  #[serde(rename = "tab")]
-  TextTab,
-  //     #[serde(rename = "text:line-break")]
-  #[serde(rename = "line-break")]
-  TextLineBreak,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextSpace {
+    #[serde(default)]
+    #[serde(rename = "@text:c")]
+    #[serde(skip_serializing_if = "Chars::is_default")]
+    chars: Chars,
+  }
+}
 
-  #[serde(rename = "$text")]
-  Text(String),
+impl TextSpace {
+  /// Number of spaces.
+  pub fn count(&self) -> usize {
+    self.chars.0 as usize
+  }
+}
 
-  #[serde(other)]
-  Unknown,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum TextSpanItem {
+    #[serde(rename = "text:s")]
+    TextSpace(TextSpace),
+    #[serde(rename = "text:tab")]
+    TextTab,
+    #[serde(rename = "text:line-break")]
+    TextLineBreak,
+
+    #[serde(rename = "office:annotation")]
+    OfficeAnnotation(OfficeAnnotation),
+    #[serde(rename = "office:annotation-end")]
+    OfficeAnnotationEnd(OfficeAnnotationEnd),
+
+    #[serde(rename = "text:change-start")]
+    TextChangeStart(TextChangeStart),
+    #[serde(rename = "text:change-end")]
+    TextChangeEnd(TextChangeEnd),
+    #[serde(rename = "text:change")]
+    TextChange(TextChange),
+
+    #[serde(rename = "text:page-number")]
+    TextPageNumber(TextPageNumber),
+    #[serde(rename = "text:date")]
+    TextDate(TextDate),
+    #[serde(rename = "text:time")]
+    TextTime(TextTime),
+    #[serde(rename = "text:author-name")]
+    TextAuthorName(TextMetaField),
+    #[serde(rename = "text:title")]
+    TextTitle(TextMetaField),
+    #[serde(rename = "text:chapter")]
+    TextChapter(TextChapter),
+    #[serde(rename = "text:bookmark-ref")]
+    TextBookmarkRef(TextReference),
+    #[serde(rename = "text:sequence")]
+    TextSequence(TextSequence),
+    #[serde(rename = "text:sequence-ref")]
+    TextSequenceRef(TextReference),
+    #[serde(rename = "text:variable-set")]
+    TextVariableSet(TextVariableSet),
+    #[serde(rename = "text:variable-get")]
+    TextVariableGet(TextVariableGet),
+    #[serde(rename = "text:user-field-get")]
+    TextUserFieldGet(TextVariableGet),
+
+    #[serde(rename = "$text")]
+    Text(String),
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextSpan {
-  //     #[serde(rename = "office:annotation")]
-  #[serde(default)]
-  #[serde(rename = "annotation")]
-  pub annotations: Vec<OfficeAnnotation>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextSpan {
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TextSpanItem>,
 
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  pub list: Vec<TextSpanItem>,
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+  }
+}
 
-  //     #[serde(rename = "@text:style-name")]
-  #[serde(rename = "@style-name")]
-  pub style_name: Option<String>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum TextLinkItem {
+    #[serde(rename = "text:s")]
+    TextSpace(TextSpace),
+
+    #[serde(rename = "text:tab")]
+    TextTab,
+
+    #[serde(rename = "text:span")]
+    TextSpan(TextSpan),
+
+    #[serde(rename = "office:annotation")]
+    OfficeAnnotation(OfficeAnnotation),
+    #[serde(rename = "office:annotation-end")]
+    OfficeAnnotationEnd(OfficeAnnotationEnd),
+
+    #[serde(rename = "text:change-start")]
+    TextChangeStart(TextChangeStart),
+    #[serde(rename = "text:change-end")]
+    TextChangeEnd(TextChangeEnd),
+    #[serde(rename = "text:change")]
+    TextChange(TextChange),
+
+    #[serde(rename = "text:page-number")]
+    TextPageNumber(TextPageNumber),
+    #[serde(rename = "text:date")]
+    TextDate(TextDate),
+    #[serde(rename = "text:time")]
+    TextTime(TextTime),
+    #[serde(rename = "text:author-name")]
+    TextAuthorName(TextMetaField),
+    #[serde(rename = "text:title")]
+    TextTitle(TextMetaField),
+    #[serde(rename = "text:chapter")]
+    TextChapter(TextChapter),
+    #[serde(rename = "text:bookmark-ref")]
+    TextBookmarkRef(TextReference),
+    #[serde(rename = "text:sequence")]
+    TextSequence(TextSequence),
+    #[serde(rename = "text:sequence-ref")]
+    TextSequenceRef(TextReference),
+    #[serde(rename = "text:variable-set")]
+    TextVariableSet(TextVariableSet),
+    #[serde(rename = "text:variable-get")]
+    TextVariableGet(TextVariableGet),
+    #[serde(rename = "text:user-field-get")]
+    TextUserFieldGet(TextVariableGet),
+
+    #[serde(rename = "$text")]
+    Text(String),
+
+    #[serde(rename = "draw:frame")]
+    DrawFrame(DrawFrame),
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub enum TextLinkItem {
-  #[serde(rename = "s")]
-  TextSpace(TextSpace),
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextLink {
+    /// Always "simple".
+    #[serde(rename = "@xlink:type")]
+    pub link_type: Option<String>,
+
+    /// A URL, or `#name` for a place in the document, see
+    /// [`DocumentContent::resolve_anchor`].
+    #[serde(rename = "@xlink:href")]
+    pub href: String,
+
+    /// "_self", "_blank", "_parent", "_top" or a frame name.
+    #[serde(rename = "@office:target-frame-name")]
+    pub target_frame_name: Option<String>,
 
-  #[serde(rename = "tab")]
-  TextTab,
+    /// "new" or "replace", following the target frame.
+    #[serde(rename = "@xlink:show")]
+    pub show: Option<String>,
 
-  //     #[serde(rename = "text:span")]
-  #[serde(rename = "span")]
-  TextSpan(TextSpan),
+    #[serde(rename = "@office:name")]
+    pub name: Option<String>,
 
-  #[serde(rename = "$text")]
-  Text(String),
+    /// Tooltip of the link.
+    #[serde(rename = "@office:title")]
+    pub title: Option<String>,
 
-  #[serde(rename = "frame")]
-  DrawFrame(DrawFrame),
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:visited-style-name")]
+    pub visited_style_name: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TextLinkItem>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextLink {
-  //     #[serde(rename = "@xlink:href")]
-  #[serde(rename = "@href")]
-  pub href: String,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextBookmark {
+    #[serde(rename = "@text:name")]
+    pub name: String,
+  }
+}
 
-  //     #[serde(rename = "@text:style-name")]
-  #[serde(rename = "@style-name")]
-  pub style_name: Option<String>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextBookmarkStart {
+    #[serde(rename = "@text:name")]
+    pub name: String,
+  }
+}
 
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  pub list: Vec<TextLinkItem>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextBookmarkEnd {
+    #[serde(rename = "@text:name")]
+    pub name: String,
+  }
+}
+
+// Fields: the text of each is the value displayed when the document was
+// saved, the attributes tell how to compute it again.
+
+lossless! {
+  /// `text:page-number`
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextPageNumber {
+    /// "previous", "current" (the default) or "next"
+    #[serde(rename = "@text:select-page")]
+    pub select_page: Option<String>,
+
+    #[serde(rename = "@text:page-adjust")]
+    pub page_adjust: Option<i32>,
+
+    #[serde(rename = "@style:num-format")]
+    pub num_format: Option<String>,
+
+    #[serde(rename = "@text:fixed")]
+    pub fixed: Option<bool>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
+}
+
+lossless! {
+  /// `text:date`
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextDate {
+    /// ISO 8601 date of a fixed field.
+    #[serde(rename = "@text:date-value")]
+    pub date_value: Option<String>,
+
+    /// ISO 8601 duration added to the date.
+    #[serde(rename = "@text:date-adjust")]
+    pub date_adjust: Option<String>,
+
+    #[serde(rename = "@style:data-style-name")]
+    pub data_style_name: Option<String>,
+
+    #[serde(rename = "@text:fixed")]
+    pub fixed: Option<bool>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextBookmark {
-  //     #[serde(rename = "@text:name")]
-  #[serde(rename = "@name")]
-  pub name: String,
+lossless! {
+  /// `text:time`
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextTime {
+    /// ISO 8601 time of a fixed field.
+    #[serde(rename = "@text:time-value")]
+    pub time_value: Option<String>,
+
+    /// ISO 8601 duration added to the time.
+    #[serde(rename = "@text:time-adjust")]
+    pub time_adjust: Option<String>,
+
+    #[serde(rename = "@style:data-style-name")]
+    pub data_style_name: Option<String>,
+
+    #[serde(rename = "@text:fixed")]
+    pub fixed: Option<bool>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextBookmarkStart {
-  //     #[serde(rename = "@text:name")]
-  #[serde(rename = "@name")]
-  pub name: String,
+lossless! {
+  /// A field showing document metadata: `text:author-name` or `text:title`.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextMetaField {
+    #[serde(rename = "@text:fixed")]
+    pub fixed: Option<bool>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextBookmarkEnd {
-  //     #[serde(rename = "@text:name")]
-  #[serde(rename = "@name")]
-  pub name: String,
+lossless! {
+  /// `text:chapter`, the heading the field is under.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextChapter {
+    /// "name", "number", "number-and-name", "plain-number" or
+    /// "plain-number-and-name"
+    #[serde(rename = "@text:display")]
+    pub display: Option<String>,
+
+    #[serde(rename = "@text:outline-level")]
+    pub outline_level: Option<u32>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct NoteCitation {
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  value: Option<String>,
+lossless! {
+  /// A cross-reference: `text:bookmark-ref` to a bookmark, or
+  /// `text:sequence-ref` to a `text:sequence` of the same `ref_name`.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextReference {
+    #[serde(rename = "@text:ref-name")]
+    pub ref_name: Option<String>,
+
+    /// What is shown: "page", "chapter", "direction", "text", or for
+    /// sequences "category-and-value", "caption" and "value".
+    #[serde(rename = "@text:reference-format")]
+    pub reference_format: Option<String>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
+}
+
+lossless! {
+  /// `text:sequence`, a number of a named sequence such as the figures.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextSequence {
+    #[serde(rename = "@text:name")]
+    pub name: String,
+
+    #[serde(rename = "@text:formula")]
+    pub formula: Option<String>,
+
+    #[serde(rename = "@style:num-format")]
+    pub num_format: Option<String>,
+
+    /// Target of `text:sequence-ref`.
+    #[serde(rename = "@text:ref-name")]
+    pub ref_name: Option<String>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
+}
+
+lossless! {
+  /// `text:variable-set`, sets a variable from here on in the document.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextVariableSet {
+    #[serde(rename = "@text:name")]
+    pub name: String,
+
+    #[serde(rename = "@text:formula")]
+    pub formula: Option<String>,
+
+    #[serde(rename = "@office:value-type")]
+    pub value_type: Option<String>,
+
+    /// Number value of "float", "percentage" and "currency" variables.
+    #[serde(rename = "@office:value")]
+    pub office_value: Option<String>,
+
+    #[serde(rename = "@office:string-value")]
+    pub string_value: Option<String>,
+
+    /// "value" (the default) or "none"
+    #[serde(rename = "@text:display")]
+    pub display: Option<String>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
+}
+
+lossless! {
+  /// Shows the value of a variable: `text:variable-get`, or
+  /// `text:user-field-get` for a user field declared in the document.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextVariableGet {
+    #[serde(rename = "@text:name")]
+    pub name: String,
+
+    /// "value" (the default) or "formula"
+    #[serde(rename = "@text:display")]
+    pub display: Option<String>,
+
+    #[serde(rename = "@style:data-style-name")]
+    pub data_style_name: Option<String>,
+
+    /// Display value when the document was last saved.
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct NoteCitation {
+    /// Set when the citation is not the automatic number.
+    #[serde(rename = "@text:label")]
+    pub label: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub value: Option<String>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum NoteBodyItem {
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
+
+    #[serde(rename = "text:h")]
+    TextHeading(TextHeading),
+
+    #[serde(rename = "text:list")]
+    TextList(TextList),
+
+    #[serde(rename = "table:table")]
+    TableTable(TableTable),
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct NoteBody {
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<NoteBodyItem>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct NoteBody {
-  #[serde(rename = "p")]
-  // #[serde(default)]
-  // #[serde(rename = "$value")]
-  value: Vec<TextParagraph>,
+#[derive(
+  Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq,
+)]
+pub enum NoteClass {
+  #[default]
+  #[serde(rename = "footnote")]
+  Footnote,
+  #[serde(rename = "endnote")]
+  Endnote,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextNote {
-  #[serde(rename = "note-citation")]
-  note_citation: Option<NoteCitation>,
-  #[serde(rename = "note-body")]
-  note_body: Option<NoteBody>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextNote {
+    #[serde(rename = "@text:id")]
+    pub id: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "@text:note-class")]
+    pub note_class: NoteClass,
+
+    #[serde(rename = "text:note-citation")]
+    pub note_citation: Option<NoteCitation>,
+    #[serde(rename = "text:note-body")]
+    pub note_body: Option<NoteBody>,
+  }
 }
 
 /* Sample HR:
@@ -201,484 +678,1856 @@ pub struct TextNote {
   svg:width="0.0012in"
   svg:height="0.0213in"
 */
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawRect {
-  //     #[serde(rename = "@style:rel-width")]
-  #[serde(rename = "@rel-width")]
-  pub width: String,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawRect {
+    #[serde(rename = "@style:rel-width")]
+    pub width: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawObject {
-  //     #[serde(rename = "@xlink:href")]
-  #[serde(rename = "@href")]
-  pub href: String,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawObject {
+    #[serde(rename = "@xlink:href")]
+    pub href: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawImage {
-  //     #[serde(rename = "@xlink:href")]
-  #[serde(rename = "@href")]
-  pub href: String,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawImage {
+    #[serde(rename = "@xlink:href")]
+    pub href: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct SvgDesc {
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  value: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawFrame {
-  //     #[serde(rename = "draw:object")]
-  #[serde(rename = "object")]
-  object: Option<DrawObject>,
-  //     #[serde(rename = "draw:image")]
-  #[serde(rename = "image")]
-  image: Option<DrawImage>,
-  //     #[serde(rename = "svg:desc")]
-  #[serde(rename = "desc")]
-  desc: Option<SvgDesc>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawEquation {
-  #[serde(rename = "@draw:name")]
-  name: String,
-  #[serde(rename = "@draw:formula")]
-  formula: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawEnhancedGeometry {
-  #[serde(rename = "draw:equation")]
-  equations: Vec<DrawEquation>,
-
-  #[serde(rename = "@draw:enhanced-path")]
-  path: String,
-  #[serde(rename = "@drawooo:enhanced-path")]
-  path2: Option<String>,
-  #[serde(rename = "@drawooo:sub-view-size")]
-  sub_view_size: String, // = ''
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub enum DrawCustomShapeItem {
-  #[serde(rename = "draw:enhanced-geometry")]
-  DrawEnhancedGeometry,
-  #[serde(rename = "text:p")]
-  TextParagraph,
-  #[serde(other)]
-  Unknown,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawCustomShape {
-  #[serde(rename = "@svg:x")]
-  x: String, //  = ''
-  #[serde(rename = "@svg:y")]
-  y: String, //  = ''
-  #[serde(rename = "@svg:width")]
-  width: String, //  = ''
-  #[serde(rename = "@svg:height")]
-  height: String, //  = ''
-  #[serde(rename = "@draw:style-name")]
-  style_name: String,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct SvgDesc {
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    value: Option<String>,
+  }
+}
 
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  list: Vec<DrawCustomShapeItem>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawFrame {
+    #[serde(rename = "draw:object")]
+    object: Option<DrawObject>,
+    #[serde(rename = "draw:image")]
+    image: Option<DrawImage>,
+    #[serde(rename = "svg:desc")]
+    desc: Option<SvgDesc>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawEquation {
+    #[serde(rename = "@draw:name")]
+    pub name: String,
+    #[serde(rename = "@draw:formula")]
+    pub formula: String,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawEnhancedGeometry {
+    #[serde(default)]
+    #[serde(rename = "draw:equation")]
+    pub equations: Vec<DrawEquation>,
+
+    #[serde(rename = "@draw:type")]
+    pub shape_type: Option<String>,
+    #[serde(rename = "@svg:viewBox")]
+    pub view_box: Option<String>,
+    #[serde(rename = "@draw:enhanced-path")]
+    pub path: Option<String>,
+    #[serde(rename = "@drawooo:enhanced-path")]
+    pub path2: Option<String>,
+    #[serde(rename = "@drawooo:sub-view-size")]
+    pub sub_view_size: Option<String>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum DrawCustomShapeItem {
+    #[serde(rename = "draw:enhanced-geometry")]
+    DrawEnhancedGeometry(DrawEnhancedGeometry),
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawCustomShape {
+    #[serde(rename = "@svg:x")]
+    pub x: Option<Parsed<Length>>,
+    #[serde(rename = "@svg:y")]
+    pub y: Option<Parsed<Length>>,
+    #[serde(rename = "@svg:width")]
+    pub width: Option<Parsed<Length>>,
+    #[serde(rename = "@svg:height")]
+    pub height: Option<Parsed<Length>>,
+    #[serde(rename = "@draw:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<DrawCustomShapeItem>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DrawG {
-  #[serde(rename = "draw:custom-shape")]
-  list: Option<Vec<DrawCustomShape>>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct DrawG {
+    #[serde(rename = "draw:custom-shape")]
+    pub list: Option<Vec<DrawCustomShape>>,
 
-  #[serde(rename = "@draw:style-name")]
-  style_name: Option<String>,
+    #[serde(rename = "@draw:style-name")]
+    pub style_name: Option<String>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TextTab {}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TextLineBreak {}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SoftPageBreak {}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextChangeStart {
-  //     #[serde(rename = "@text:change-id")]
-  #[serde(rename = "@change-id")]
-  change_id: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextChangeEnd {
-  //     #[serde(rename = "@text:change-id")]
-  #[serde(rename = "@change-id")]
-  change_id: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub enum TextParagraphItem {
-  //     #[serde(rename = "text:a")]
-  #[serde(rename = "a")]
-  TextLink(TextLink),
-  //     #[serde(rename = "text:span")]
-  #[serde(rename = "span")]
-  TextSpan(TextSpan),
-  //     #[serde(rename = "text:tab")]
-  #[serde(rename = "tab")]
-  TextTab,
-  //     #[serde(rename = "text:line-break")]
-  #[serde(rename = "line-break")]
-  TextLineBreak,
-
-  //     #[serde(rename = "text:soft-page-break")]
-  #[serde(rename = "soft-page-break")]
-  SoftPageBreak,
-  //     #[serde(rename = "text:s")]
-  #[serde(rename = "s")]
-  TextSpace(TextSpace),
-  //     #[serde(rename = "text:change-start")]
-  #[serde(rename = "change-start")]
-  TextChangeStart(TextChangeStart),
-  //     #[serde(rename = "text:change-end")]
-  #[serde(rename = "change-end")]
-  TextChangeEnd(TextChangeEnd),
-  //     #[serde(rename = "text:bookmark")]
-  #[serde(rename = "bookmark")]
-  TextBookmark(TextBookmark),
-  #[serde(rename = "bookmark-start")]
-  TextBookmarkStart(TextBookmarkStart),
-  #[serde(rename = "bookmark-end")]
-  TextBookmarkEnd(TextBookmarkEnd),
-
-  #[serde(rename = "note")]
-  TextNote(TextNote),
-
-  //     #[serde(rename = "draw:rect")]
-  #[serde(rename = "rect")]
-  DrawRect(DrawRect),
-  //     #[serde(rename = "draw:frame")]
-  #[serde(rename = "frame")]
-  DrawFrame(DrawFrame),
-  //     #[serde(rename = "draw:g")]
-  #[serde(rename = "g")]
-  DrawG(DrawG),
-  //     #[serde(rename = "draw:custom-shape")]
-  #[serde(rename = "custom-shape")]
-  DrawCustomShape(DrawCustomShape),
-
-  #[serde(rename = "$text")]
-  Text(String),
-
-  #[serde(other)]
-  Unknown,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextParagraph {
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  //     #[serde(flatten)]
-  pub list: Vec<TextParagraphItem>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextChangeStart {
+    #[serde(rename = "@text:change-id")]
+    pub change_id: String,
+  }
+}
 
-  #[serde(default)]
-  //     #[serde(rename = "text:annotation")]
-  #[serde(rename = "annotation")]
-  pub annotations: Vec<OfficeAnnotation>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextChangeEnd {
+    #[serde(rename = "@text:change-id")]
+    pub change_id: String,
+  }
+}
 
-  //     #[serde(rename = "@text:style-name")]
-  #[serde(rename = "@style-name")]
-  pub style_name: String,
+lossless! {
+  /// Where deleted text was, see [`Deletion`].
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextChange {
+    #[serde(rename = "@text:change-id")]
+    pub change_id: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextIndexBody {
-  //     #[serde(rename = "text:p")]
-  #[serde(default)]
-  #[serde(rename = "p")]
-  pub list: Vec<TextParagraph>,
+lossless! {
+  /// `text:tracked-changes`, the changes recorded in the text. The changed
+  /// text is marked in the paragraphs with `text:change-start` and
+  /// `text:change-end` (insertions, format changes) or `text:change`
+  /// (deletions) carrying the id of a region.
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct TrackedChanges {
+    /// Whether new changes are recorded.
+    #[serde(rename = "@text:track-changes")]
+    pub track_changes: Option<bool>,
+
+    #[serde(default)]
+    #[serde(rename = "text:changed-region")]
+    pub regions: Vec<ChangedRegion>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TableOfContent {
-  //     #[serde(rename = "text:index-body")]
-  #[serde(rename = "index-body")]
-  pub index_body: TextIndexBody,
+lossless! {
+  /// One change, holding one of `insertion`, `deletion` or `format_change`.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ChangedRegion {
+    #[serde(rename = "@text:id")]
+    pub text_id: Option<String>,
+
+    #[serde(rename = "@xml:id")]
+    pub xml_id: Option<String>,
+
+    #[serde(rename = "text:insertion")]
+    pub insertion: Option<Insertion>,
+
+    #[serde(rename = "text:deletion")]
+    pub deletion: Option<Deletion>,
+
+    #[serde(rename = "text:format-change")]
+    pub format_change: Option<FormatChange>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct OfficeAnnotation {}
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ChangeInfo {
+    #[serde(rename = "dc:creator")]
+    pub creator: Option<String>,
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub enum TextListItemEnum {
-  //     #[serde(rename = "text:p")]
-  #[serde(rename = "p")]
-  TextParagraph(TextParagraph),
+    /// ISO 8601 date and time
+    #[serde(rename = "dc:date")]
+    pub date: Option<String>,
 
-  //     #[serde(rename = "text:list")]
-  #[serde(rename = "list")]
-  TextList(TextList),
+    /// Comment on the change
+    #[serde(default)]
+    #[serde(rename = "text:p")]
+    pub comment: Vec<TextParagraph>,
+  }
+}
 
-  #[serde(other)]
-  Unknown,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct Insertion {
+    #[serde(rename = "office:change-info")]
+    pub change_info: ChangeInfo,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextListItem {
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  list: Vec<TextListItemEnum>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum DeletionItem {
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
+
+    #[serde(rename = "text:h")]
+    TextHeading(TextHeading),
+
+    #[serde(rename = "text:list")]
+    TextList(TextList),
+
+    #[serde(rename = "table:table")]
+    TableTable(TableTable),
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextList {
-  #[serde(default)]
-  //     #[serde(rename = "text:list-item")]
-  #[serde(rename = "list-item")]
-  pub list: Vec<TextListItem>,
+lossless! {
+  /// The deleted content is kept here, out of the text.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct Deletion {
+    #[serde(rename = "office:change-info")]
+    pub change_info: ChangeInfo,
 
-  #[serde(rename = "@xml:id")] // https://github.com/tafia/quick-xml/issues/841
-  pub id: Option<String>,
-  //     #[serde(rename = "@text:continue-numbering")]
-  #[serde(rename = "@continue-numbering")]
-  pub continue_numbering: Option<String>,
-  //     #[serde(rename = "@text:continue-list")]
-  #[serde(rename = "@continue-list")]
-  pub continue_list: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<DeletionItem>,
+  }
+}
 
-  //     #[serde(rename = "@text:style-name")]
-  #[serde(rename = "@style-name")]
-  pub style_name: Option<String>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct FormatChange {
+    #[serde(rename = "office:change-info")]
+    pub change_info: ChangeInfo,
+  }
 }
 
-// #[derive(Debug, Serialize, Deserialize, PartialEq)]
-// // #[serde(untagged)]
-// pub struct Unknown {
-// }
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum TextParagraphItem {
+    #[serde(rename = "text:a")]
+    TextLink(TextLink),
+    #[serde(rename = "text:span")]
+    TextSpan(TextSpan),
+    #[serde(rename = "text:tab")]
+    TextTab,
+    #[serde(rename = "text:line-break")]
+    TextLineBreak,
+
+    #[serde(rename = "text:soft-page-break")]
+    SoftPageBreak,
+    #[serde(rename = "text:s")]
+    TextSpace(TextSpace),
+    #[serde(rename = "text:change-start")]
+    TextChangeStart(TextChangeStart),
+    #[serde(rename = "text:change-end")]
+    TextChangeEnd(TextChangeEnd),
+    #[serde(rename = "text:change")]
+    TextChange(TextChange),
+    #[serde(rename = "text:bookmark")]
+    TextBookmark(TextBookmark),
+    #[serde(rename = "text:bookmark-start")]
+    TextBookmarkStart(TextBookmarkStart),
+    #[serde(rename = "text:bookmark-end")]
+    TextBookmarkEnd(TextBookmarkEnd),
+
+    #[serde(rename = "text:page-number")]
+    TextPageNumber(TextPageNumber),
+    #[serde(rename = "text:date")]
+    TextDate(TextDate),
+    #[serde(rename = "text:time")]
+    TextTime(TextTime),
+    #[serde(rename = "text:author-name")]
+    TextAuthorName(TextMetaField),
+    #[serde(rename = "text:title")]
+    TextTitle(TextMetaField),
+    #[serde(rename = "text:chapter")]
+    TextChapter(TextChapter),
+    #[serde(rename = "text:bookmark-ref")]
+    TextBookmarkRef(TextReference),
+    #[serde(rename = "text:sequence")]
+    TextSequence(TextSequence),
+    #[serde(rename = "text:sequence-ref")]
+    TextSequenceRef(TextReference),
+    #[serde(rename = "text:variable-set")]
+    TextVariableSet(TextVariableSet),
+    #[serde(rename = "text:variable-get")]
+    TextVariableGet(TextVariableGet),
+    #[serde(rename = "text:user-field-get")]
+    TextUserFieldGet(TextVariableGet),
+
+    #[serde(rename = "text:note")]
+    TextNote(TextNote),
+
+    #[serde(rename = "office:annotation")]
+    OfficeAnnotation(OfficeAnnotation),
+    #[serde(rename = "office:annotation-end")]
+    OfficeAnnotationEnd(OfficeAnnotationEnd),
+
+    #[serde(rename = "draw:rect")]
+    DrawRect(DrawRect),
+    #[serde(rename = "draw:frame")]
+    DrawFrame(DrawFrame),
+    #[serde(rename = "draw:g")]
+    DrawG(DrawG),
+    #[serde(rename = "draw:custom-shape")]
+    DrawCustomShape(DrawCustomShape),
+
+    #[serde(rename = "$text")]
+    Text(String),
+  }
+}
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-// #[serde(skip_serializing_if = "Option::is_none")]
-// #[serde(untagged)]
-pub enum OfficeTextItem {
-  //     #[serde(rename = "text:p")]
-  #[serde(rename = "p")]
-  TextParagraph(TextParagraph),
-
-  //     #[serde(rename = "table:table")]
-  #[serde(rename = "table")]
-  TableTable(TableTable),
-
-  //     #[serde(rename = "text:list")]
-  #[serde(rename = "list")]
-  TextList(TextList),
-
-  //     #[serde(rename = "text:table-of-content")]
-  #[serde(rename = "table-of-content")]
-  TableOfContent(TableOfContent),
-
-  #[serde(other)]
-  Unknown,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct TextProperty {
-  //     #[serde(rename = "@style:font-name")]
-  #[serde(rename = "@font-name")]
-  pub font_name: Option<String>, // 'Courier New' | 'Arial'
-  //     #[serde(rename = "@fo:font-weight")]
-  #[serde(rename = "@font-weight")]
-  pub font_weight: Option<String>, // 'bold'
-  //     #[serde(rename = "@fo:font-style")]
-  #[serde(rename = "@font-style")]
-  pub font_style: Option<String>, // 'italic'
-  //     #[serde(rename = "@style:text-underline-style")]
-  #[serde(rename = "@text-underline-style")]
-  pub underline_style: Option<String>, // 'solid'
-  //     #[serde(rename = "@fo:font-size")]
-  #[serde(rename = "@font-size")]
-  pub font_size: Option<String>,
-  //     #[serde(rename = "@fo:color")]
-  #[serde(rename = "@color")]
-  pub font_color: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct GraphicProperty {
-  //     #[serde(rename = "@svg:stroke-color")]
-  #[serde(rename = "@stroke-color")]
-  pub stroke_color: Option<String>,
-  //     #[serde(rename = "@svg:stroke-width")]
-  #[serde(rename = "@stroke-width")]
-  pub stroke_width: Option<String>,
-  //     #[serde(rename = "@draw:stroke-linejoin")]
-  #[serde(rename = "@stroke-linejoin")]
-  pub stroke_linejoin: Option<String>,
-  //     #[serde(rename = "@draw:stroke")]
-  #[serde(rename = "@stroke")]
-  pub stroke: Option<String>,
-  //     #[serde(rename = "@draw:fill")]
-  #[serde(rename = "@fill")]
-  pub fill: Option<String>,
-  //     #[serde(rename = "@draw:fill-color")]
-  #[serde(rename = "@fill-color")]
-  pub fill_color: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ParagraphProperty {
-  //     #[serde(rename = "@fo:break-before")]
-  #[serde(rename = "@break-before")]
-  pub break_before: Option<String>, // 'auto'
-  //     #[serde(rename = "@fo:break-after")]
-  #[serde(rename = "@break-after")]
-  pub break_after: Option<String>, // 'auto'
-  //     #[serde(rename = "@fo:margin-left")]
-  #[serde(rename = "@margin-left")]
-  pub margin_left: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct OfficeText {
-  #[serde(default)]
-  #[serde(rename = "$value")]
-  pub list: Vec<OfficeTextItem>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextParagraph {
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TextParagraphItem>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Body {
-  //     #[serde(rename = "office:text")]
-  #[serde(rename = "text")]
-  //     pub list: Vec<OfficeTextItem>,
-  pub text: OfficeText,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextHeading {
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TextParagraphItem>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:outline-level")]
+    pub outline_level: Option<u32>,
+
+    #[serde(rename = "@text:is-list-header")]
+    pub is_list_header: Option<bool>,
+
+    #[serde(rename = "@text:restart-numbering")]
+    pub restart_numbering: Option<bool>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct Style {
-  //     #[serde(rename = "@style:name")]
-  #[serde(rename = "@name")]
-  pub name: Option<String>,
+lossless! {
+  /// `text:index-title`, the heading of an index, regenerated with it.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextIndexTitle {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
 
-  //     #[serde(rename = "@style:list-style-name")]
-  #[serde(rename = "@list-style-name")]
-  pub list_style_name: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "text:p")]
+    pub list: Vec<TextParagraph>,
+  }
+}
 
-  //     #[serde(rename = "@style:parent-style-name")]
-  #[serde(rename = "@parent-style-name")]
-  pub parent_style_name: Option<String>,
+lossless! {
+  /// The generated content of an index, as of its last update.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextIndexBody {
+    #[serde(rename = "text:index-title")]
+    pub index_title: Option<TextIndexTitle>,
 
-  //     #[serde(rename = "style:text-properties")]
-  #[serde(rename = "text-properties")]
-  pub text_properties: Option<TextProperty>,
+    #[serde(default)]
+    #[serde(rename = "text:p")]
+    pub list: Vec<TextParagraph>,
+  }
+}
 
-  //     #[serde(rename = "style:paragraph-properties")]
-  #[serde(rename = "paragraph-properties")]
-  pub paragraph_properties: Option<ParagraphProperty>,
+lossless! {
+  /// Paragraph of the index title.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexTitleTemplate {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
 
-  //     #[serde(rename = "style:graphic-properties")]
-  #[serde(rename = "graphic-properties")]
-  pub graphic_properties: Option<GraphicProperty>,
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct AutomaticStyle {
-  //     #[serde(rename = "style:style")]
-  #[serde(default)]
-  #[serde(rename = "style")]
-  pub styles: Vec<Style>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct DocumentContent {
-  //     #[serde(rename = "{urn:oasis:names:tc:opendocument:xmlns:office:1.0}body")]
-  //     #[serde(rename = "office:body")]
-  #[serde(rename = "body")]
-  pub body: Body,
-
-  //     #[serde(rename = "office:font-face-decls")]
-  #[serde(rename = "font-face-decls")]
-  pub font_face_decls: Vec<FontFaceDecl>,
-
-  //     #[serde(rename = "office:automatic-styles")]
-  #[serde(rename = "automatic-styles")]
-  pub automatic_styles: AutomaticStyle,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ListLLevelLabelAlignment {
-  //     #[serde(rename = "@fo:margin-left")]
-  #[serde(rename = "@margin-left")]
-  pub margin_left: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ListLevelProperties {
-  #[serde(rename = "list-level-label-alignment")]
-  pub list_level_alignment: ListLLevelLabelAlignment,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ListLevelStyleBullet {
-  //     #[serde(rename = "@text:level")]
-  #[serde(rename = "@level")]
-  pub level: u32,
-  #[serde(rename = "list-level-properties")]
-  pub list_level_properties: ListLevelProperties,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ListLevelStyleNumber {
-  //     #[serde(rename = "@text:level")]
-  #[serde(rename = "@level")]
-  pub level: u32,
-  //     #[serde(rename = "@text:start-value")]
-  #[serde(rename = "@start-value")]
-  pub start_value: Option<u32>,
-  //     #[serde(rename = "@style:num-format")]
-  #[serde(rename = "@num-format")]
-  pub num_format: String, // = 1
-  #[serde(rename = "list-level-properties")]
-  pub list_level_properties: ListLevelProperties,
-}
-
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct ListStyle {
-  //     #[serde(rename = "@style:name")]
-  #[serde(rename = "@name")]
-  pub name: Option<String>,
-  //     #[serde(rename = "text:list-level-style-bullet")]
-  #[serde(default)]
-  #[serde(rename = "list-level-style-bullet")]
-  pub list_level_style_bullet: Vec<ListLevelStyleBullet>,
-  //     #[serde(rename = "text:list-level-style-number")]
-  #[serde(default)]
-  #[serde(rename = "list-level-style-number")]
-  pub list_level_style_number: Vec<ListLevelStyleNumber>,
+lossless! {
+  /// A part of an index entry that only sets a text style.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexEntryStyled {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-pub struct OfficeStyles {
-  //     #[serde(rename = "text:list-style")]
-  #[serde(default)]
-  #[serde(rename = "list-style")]
-  pub list_styles: Vec<ListStyle>,
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexEntryChapter {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
 
-  //     #[serde(rename = "style:style")]
-  #[serde(default)]
-  #[serde(rename = "style")]
-  pub styles: Vec<Style>,
+    /// "number" (the default), "name", "number-and-name", "plain-number" or
+    /// "plain-number-and-name"
+    #[serde(rename = "@text:display")]
+    pub display: Option<String>,
+
+    #[serde(rename = "@text:outline-level")]
+    pub outline_level: Option<u32>,
+  }
+}
+
+lossless! {
+  /// Fixed text of each entry.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexEntrySpan {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexEntryTabStop {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    /// "left" or "right"
+    #[serde(rename = "@style:type")]
+    pub tab_type: Option<String>,
+
+    /// Position of a left tab stop.
+    #[serde(rename = "@style:position")]
+    pub position: Option<Parsed<Length>>,
+
+    #[serde(rename = "@style:leader-char")]
+    pub leader_char: Option<String>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexEntryBibliography {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    /// Field of the bibliography entry, eg. "author" or "title".
+    #[serde(rename = "@text:bibliography-data-field")]
+    pub data_field: String,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum IndexEntryTemplateItem {
+    #[serde(rename = "text:index-entry-chapter")]
+    IndexEntryChapter(IndexEntryChapter),
+    /// The heading, caption or name the entry is for.
+    #[serde(rename = "text:index-entry-text")]
+    IndexEntryText(IndexEntryStyled),
+    #[serde(rename = "text:index-entry-page-number")]
+    IndexEntryPageNumber(IndexEntryStyled),
+    #[serde(rename = "text:index-entry-span")]
+    IndexEntrySpan(IndexEntrySpan),
+    #[serde(rename = "text:index-entry-tab-stop")]
+    IndexEntryTabStop(IndexEntryTabStop),
+    #[serde(rename = "text:index-entry-link-start")]
+    IndexEntryLinkStart(IndexEntryStyled),
+    #[serde(rename = "text:index-entry-link-end")]
+    IndexEntryLinkEnd(IndexEntryStyled),
+    #[serde(rename = "text:index-entry-bibliography")]
+    IndexEntryBibliography(IndexEntryBibliography),
+  }
+}
+
+lossless! {
+  /// How the entries of one outline level, or one bibliography type, are
+  /// built. Every index kind has its own element name for it.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexEntryTemplate {
+    /// 1 to 10, or "separator" for the letters of an alphabetical index. See
+    /// [`IndexEntryTemplate::level`].
+    #[serde(rename = "@text:outline-level")]
+    pub outline_level: Option<String>,
+
+    /// "book", "article" etc. for bibliography entries.
+    #[serde(rename = "@text:bibliography-type")]
+    pub bibliography_type: Option<String>,
+
+    /// Paragraph style of the entries.
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<IndexEntryTemplateItem>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexSourceStyle {
+    #[serde(rename = "@text:style-name")]
+    pub style_name: String,
+  }
+}
+
+lossless! {
+  /// Paragraph styles taken as headings of an outline level.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IndexSourceStyles {
+    #[serde(rename = "@text:outline-level")]
+    pub outline_level: u32,
+
+    #[serde(default)]
+    #[serde(rename = "text:index-source-style")]
+    pub styles: Vec<IndexSourceStyle>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableOfContentSource {
+    /// Deepest outline level listed, 1 to 10.
+    #[serde(rename = "@text:outline-level")]
+    pub outline_level: Option<u32>,
+
+    /// Whether headings are listed (the default).
+    #[serde(rename = "@text:use-outline-level")]
+    pub use_outline_level: Option<bool>,
+
+    #[serde(rename = "@text:use-index-marks")]
+    pub use_index_marks: Option<bool>,
+
+    #[serde(rename = "@text:use-index-source-styles")]
+    pub use_index_source_styles: Option<bool>,
+
+    /// "document" (the default) or "chapter"
+    #[serde(rename = "@text:index-scope")]
+    pub index_scope: Option<String>,
+
+    /// Whether tab stops are relative to the paragraph indent.
+    #[serde(rename = "@text:relative-tab-stop-position")]
+    pub relative_tab_stop_position: Option<bool>,
+
+    #[serde(rename = "text:index-title-template")]
+    pub index_title_template: Option<IndexTitleTemplate>,
+
+    #[serde(default)]
+    #[serde(rename = "text:table-of-content-entry-template")]
+    pub entry_templates: Vec<IndexEntryTemplate>,
+
+    #[serde(default)]
+    #[serde(rename = "text:index-source-styles")]
+    pub index_source_styles: Vec<IndexSourceStyles>,
+  }
+}
+
+lossless! {
+  /// `text:table-of-content`
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableOfContent {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:protected")]
+    pub protected: Option<bool>,
+
+    #[serde(rename = "text:table-of-content-source")]
+    pub source: Option<TableOfContentSource>,
+
+    #[serde(rename = "text:index-body")]
+    pub index_body: TextIndexBody,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct AlphabeticalIndexSource {
+    /// "document" (the default) or "chapter"
+    #[serde(rename = "@text:index-scope")]
+    pub index_scope: Option<String>,
+
+    /// Whether tab stops are relative to the paragraph indent.
+    #[serde(rename = "@text:relative-tab-stop-position")]
+    pub relative_tab_stop_position: Option<bool>,
+
+    #[serde(rename = "@text:ignore-case")]
+    pub ignore_case: Option<bool>,
+
+    #[serde(rename = "@text:main-entry-style-name")]
+    pub main_entry_style_name: Option<String>,
+
+    /// Whether entries are grouped under their first letter.
+    #[serde(rename = "@text:alphabetical-separators")]
+    pub alphabetical_separators: Option<bool>,
+
+    #[serde(rename = "@text:combine-entries")]
+    pub combine_entries: Option<bool>,
+
+    #[serde(rename = "@text:capitalize-entries")]
+    pub capitalize_entries: Option<bool>,
+
+    #[serde(rename = "@text:sort-algorithm")]
+    pub sort_algorithm: Option<String>,
+
+    #[serde(rename = "text:index-title-template")]
+    pub index_title_template: Option<IndexTitleTemplate>,
+
+    #[serde(default)]
+    #[serde(rename = "text:alphabetical-index-entry-template")]
+    pub entry_templates: Vec<IndexEntryTemplate>,
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
-#[serde(rename = "office:document-styles")]
-pub struct DocumentStyles {
-  //     #[serde(rename = "office:styles")]
-  #[serde(rename = "styles")]
-  pub styles: OfficeStyles,
+lossless! {
+  /// `text:alphabetical-index`, built from `text:alphabetical-index-mark`s.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct AlphabeticalIndex {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:protected")]
+    pub protected: Option<bool>,
+
+    #[serde(rename = "text:alphabetical-index-source")]
+    pub source: Option<AlphabeticalIndexSource>,
+
+    #[serde(rename = "text:index-body")]
+    pub index_body: TextIndexBody,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IllustrationIndexSource {
+    /// "document" (the default) or "chapter"
+    #[serde(rename = "@text:index-scope")]
+    pub index_scope: Option<String>,
+
+    /// Whether tab stops are relative to the paragraph indent.
+    #[serde(rename = "@text:relative-tab-stop-position")]
+    pub relative_tab_stop_position: Option<bool>,
+
+    /// Whether entries are the captions of the objects, rather than their
+    /// names.
+    #[serde(rename = "@text:use-caption")]
+    pub use_caption: Option<bool>,
+
+    /// Sequence numbering the captions, eg. "Illustration".
+    #[serde(rename = "@text:caption-sequence-name")]
+    pub caption_sequence_name: Option<String>,
+
+    /// "text" (the default), "category-and-value" or "caption"
+    #[serde(rename = "@text:caption-sequence-format")]
+    pub caption_sequence_format: Option<String>,
+
+    #[serde(rename = "text:index-title-template")]
+    pub index_title_template: Option<IndexTitleTemplate>,
+
+    #[serde(rename = "text:illustration-index-entry-template")]
+    pub entry_template: Option<IndexEntryTemplate>,
+  }
+}
+
+lossless! {
+  /// `text:illustration-index`, the list of figures.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct IllustrationIndex {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:protected")]
+    pub protected: Option<bool>,
+
+    #[serde(rename = "text:illustration-index-source")]
+    pub source: Option<IllustrationIndexSource>,
+
+    #[serde(rename = "text:index-body")]
+    pub index_body: TextIndexBody,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableIndexSource {
+    /// "document" (the default) or "chapter"
+    #[serde(rename = "@text:index-scope")]
+    pub index_scope: Option<String>,
+
+    /// Whether tab stops are relative to the paragraph indent.
+    #[serde(rename = "@text:relative-tab-stop-position")]
+    pub relative_tab_stop_position: Option<bool>,
+
+    /// Whether entries are the captions of the objects, rather than their
+    /// names.
+    #[serde(rename = "@text:use-caption")]
+    pub use_caption: Option<bool>,
+
+    /// Sequence numbering the captions, eg. "Illustration".
+    #[serde(rename = "@text:caption-sequence-name")]
+    pub caption_sequence_name: Option<String>,
+
+    /// "text" (the default), "category-and-value" or "caption"
+    #[serde(rename = "@text:caption-sequence-format")]
+    pub caption_sequence_format: Option<String>,
+
+    #[serde(rename = "text:index-title-template")]
+    pub index_title_template: Option<IndexTitleTemplate>,
+
+    #[serde(rename = "text:table-index-entry-template")]
+    pub entry_template: Option<IndexEntryTemplate>,
+  }
+}
+
+lossless! {
+  /// `text:table-index`, the list of tables.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TableIndex {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:protected")]
+    pub protected: Option<bool>,
+
+    #[serde(rename = "text:table-index-source")]
+    pub source: Option<TableIndexSource>,
+
+    #[serde(rename = "text:index-body")]
+    pub index_body: TextIndexBody,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct BibliographySource {
+    #[serde(rename = "text:index-title-template")]
+    pub index_title_template: Option<IndexTitleTemplate>,
+
+    #[serde(default)]
+    #[serde(rename = "text:bibliography-entry-template")]
+    pub entry_templates: Vec<IndexEntryTemplate>,
+  }
+}
+
+lossless! {
+  /// `text:bibliography`
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct Bibliography {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    #[serde(rename = "@text:protected")]
+    pub protected: Option<bool>,
+
+    #[serde(rename = "text:bibliography-source")]
+    pub source: Option<BibliographySource>,
+
+    #[serde(rename = "text:index-body")]
+    pub index_body: TextIndexBody,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum OfficeAnnotationItem {
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
+
+    #[serde(rename = "text:list")]
+    TextList(TextList),
+  }
+}
+
+lossless! {
+  /// A comment. With a name it spans the text up to the
+  /// `office:annotation-end` of the same name, otherwise it marks a point.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct OfficeAnnotation {
+    #[serde(rename = "@office:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "dc:creator")]
+    pub creator: Option<String>,
+
+    #[serde(rename = "meta:creator-initials")]
+    pub creator_initials: Option<String>,
+
+    /// ISO 8601 date and time
+    #[serde(rename = "dc:date")]
+    pub date: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<OfficeAnnotationItem>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct OfficeAnnotationEnd {
+    #[serde(rename = "@office:name")]
+    pub name: String,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub enum TextListItemEnum {
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
+
+    #[serde(rename = "text:h")]
+    TextHeading(TextHeading),
+
+    #[serde(rename = "text:list")]
+    TextList(TextList),
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextListItem {
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<TextListItemEnum>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextList {
+    #[serde(default)]
+    #[serde(rename = "text:list-item")]
+    pub list: Vec<TextListItem>,
+
+    #[serde(rename = "@xml:id")] // https://github.com/tafia/quick-xml/issues/841
+    pub id: Option<String>,
+    #[serde(rename = "@text:continue-numbering")]
+    pub continue_numbering: Option<String>,
+    #[serde(rename = "@text:continue-list")]
+    pub continue_list: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+  }
+}
+
+// #[derive(Debug, Serialize, Deserialize, PartialEq)]
+// // #[serde(untagged)]
+// pub struct Unknown {
+// }
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  // #[serde(skip_serializing_if = "Option::is_none")]
+  // #[serde(untagged)]
+  pub enum OfficeTextItem {
+    #[serde(rename = "text:p")]
+    TextParagraph(TextParagraph),
+
+    #[serde(rename = "text:h")]
+    TextHeading(TextHeading),
+
+    #[serde(rename = "table:table")]
+    TableTable(TableTable),
+
+    #[serde(rename = "text:list")]
+    TextList(TextList),
+
+    #[serde(rename = "text:table-of-content")]
+    TableOfContent(TableOfContent),
+
+    #[serde(rename = "text:alphabetical-index")]
+    AlphabeticalIndex(AlphabeticalIndex),
+
+    #[serde(rename = "text:illustration-index")]
+    IllustrationIndex(IllustrationIndex),
+
+    #[serde(rename = "text:table-index")]
+    TableIndex(TableIndex),
+
+    #[serde(rename = "text:bibliography")]
+    Bibliography(Bibliography),
+
+    #[serde(rename = "text:section")]
+    TextSection(TextSection),
+  }
+}
+
+lossless! {
+  /// Where a linked section takes its content from. The content of the last
+  /// update is kept in the section.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct SectionSource {
+    /// Document to link, the current one when missing.
+    #[serde(rename = "@xlink:href")]
+    pub href: Option<String>,
+
+    #[serde(rename = "@text:filter-name")]
+    pub filter_name: Option<String>,
+
+    /// Section of the linked document, the whole document when missing.
+    #[serde(rename = "@text:section-name")]
+    pub section_name: Option<String>,
+  }
+}
+
+lossless! {
+  /// A named part of the body. Its style sets columns and background, and
+  /// sections nest.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct TextSection {
+    #[serde(rename = "@text:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@text:style-name")]
+    pub style_name: Option<String>,
+
+    /// Whether the content may not be edited.
+    #[serde(rename = "@text:protected")]
+    pub protected: Option<bool>,
+
+    /// Hash of the password that lifts the protection.
+    #[serde(rename = "@text:protection-key")]
+    pub protection_key: Option<String>,
+
+    /// "true", "none" (hidden) or "condition".
+    #[serde(rename = "@text:display")]
+    pub display: Option<String>,
+
+    #[serde(rename = "text:section-source")]
+    pub section_source: Option<SectionSource>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<OfficeTextItem>,
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FontWeight {
+  #[serde(rename = "normal")]
+  Normal,
+  #[serde(rename = "bold")]
+  Bold,
+  #[serde(rename = "100")]
+  W100,
+  #[serde(rename = "200")]
+  W200,
+  #[serde(rename = "300")]
+  W300,
+  #[serde(rename = "400")]
+  W400,
+  #[serde(rename = "500")]
+  W500,
+  #[serde(rename = "600")]
+  W600,
+  #[serde(rename = "700")]
+  W700,
+  #[serde(rename = "800")]
+  W800,
+  #[serde(rename = "900")]
+  W900,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FontStyle {
+  #[serde(rename = "normal")]
+  Normal,
+  #[serde(rename = "italic")]
+  Italic,
+  #[serde(rename = "oblique")]
+  Oblique,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FontVariant {
+  #[serde(rename = "normal")]
+  Normal,
+  #[serde(rename = "small-caps")]
+  SmallCaps,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TextTransform {
+  #[serde(rename = "none")]
+  None,
+  #[serde(rename = "lowercase")]
+  Lowercase,
+  #[serde(rename = "uppercase")]
+  Uppercase,
+  #[serde(rename = "capitalize")]
+  Capitalize,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+/// Style of an underline, overline or strikethrough line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LineStyle {
+  #[serde(rename = "none")]
+  None,
+  #[serde(rename = "solid")]
+  Solid,
+  #[serde(rename = "dotted")]
+  Dotted,
+  #[serde(rename = "dash")]
+  Dash,
+  #[serde(rename = "long-dash")]
+  LongDash,
+  #[serde(rename = "dot-dash")]
+  DotDash,
+  #[serde(rename = "dot-dot-dash")]
+  DotDotDash,
+  #[serde(rename = "wave")]
+  Wave,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+/// Whether a line is drawn once or twice.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LineType {
+  #[serde(rename = "none")]
+  None,
+  #[serde(rename = "single")]
+  Single,
+  #[serde(rename = "double")]
+  Double,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+/// Width of an underline or strikethrough line: a keyword or a length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineWidth {
+  Auto,
+  Normal,
+  Bold,
+  Thin,
+  Medium,
+  Thick,
+  Length(LengthOrPercent),
+}
+
+/// How far text is raised or lowered from the baseline.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum VerticalShift {
+  Super,
+  Sub,
+  /// Percentage of the font height, negative for lowering.
+  Percent(Percent),
+}
+
+/// `style:text-position`: the shift of the text and its font height as a
+/// percentage of the current one, eg. "super 58%".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerticalPosition {
+  pub shift: VerticalShift,
+  pub font_height: Option<Percent>,
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct TextProperty {
+    #[serde(rename = "@style:font-name")]
+    pub font_name: Option<String>, // 'Courier New' | 'Arial'
+    #[serde(rename = "@fo:font-weight")]
+    pub font_weight: Option<FontWeight>,
+    #[serde(rename = "@fo:font-style")]
+    pub font_style: Option<FontStyle>,
+    #[serde(rename = "@style:text-underline-style")]
+    pub underline_style: Option<LineStyle>,
+    #[serde(rename = "@fo:font-size")]
+    pub font_size: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:color")]
+    pub font_color: Option<Parsed<Color>>,
+
+    /// Font of Asian (CJK) scripts.
+    #[serde(rename = "@style:font-name-asian")]
+    pub font_name_asian: Option<String>,
+    /// Font of complex (CTL) scripts, such as Arabic or Hindi.
+    #[serde(rename = "@style:font-name-complex")]
+    pub font_name_complex: Option<String>,
+    #[serde(rename = "@fo:font-variant")]
+    pub font_variant: Option<FontVariant>,
+    #[serde(rename = "@fo:text-transform")]
+    pub text_transform: Option<TextTransform>,
+
+    #[serde(rename = "@style:text-underline-type")]
+    pub underline_type: Option<LineType>,
+    #[serde(rename = "@style:text-underline-width")]
+    pub underline_width: Option<Parsed<LineWidth>>,
+    /// "font-color" or a color.
+    #[serde(rename = "@style:text-underline-color")]
+    pub underline_color: Option<Parsed<Color>>,
+    #[serde(rename = "@style:text-line-through-style")]
+    pub line_through_style: Option<LineStyle>,
+    #[serde(rename = "@style:text-line-through-type")]
+    pub line_through_type: Option<LineType>,
+
+    /// Superscript and subscript.
+    #[serde(rename = "@style:text-position")]
+    pub text_position: Option<Parsed<VerticalPosition>>,
+    /// "transparent" or a color.
+    #[serde(rename = "@fo:background-color")]
+    pub background_color: Option<Parsed<Color>>,
+
+    #[serde(rename = "@fo:language")]
+    pub language: Option<String>, // 'en'
+    #[serde(rename = "@fo:country")]
+    pub country: Option<String>, // 'US'
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Stroke {
+  #[serde(rename = "none")]
+  None,
+  #[serde(rename = "dash")]
+  Dash,
+  #[serde(rename = "solid")]
+  Solid,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LineJoin {
+  #[serde(rename = "miter")]
+  Miter,
+  #[serde(rename = "round")]
+  Round,
+  #[serde(rename = "bevel")]
+  Bevel,
+  #[serde(rename = "middle")]
+  Middle,
+  #[serde(rename = "none")]
+  None,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Fill {
+  #[serde(rename = "none")]
+  None,
+  #[serde(rename = "solid")]
+  Solid,
+  #[serde(rename = "bitmap")]
+  Bitmap,
+  #[serde(rename = "gradient")]
+  Gradient,
+  #[serde(rename = "hatch")]
+  Hatch,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct GraphicProperty {
+    #[serde(rename = "@svg:stroke-color")]
+    pub stroke_color: Option<Parsed<Color>>,
+    #[serde(rename = "@svg:stroke-width")]
+    pub stroke_width: Option<Parsed<Length>>,
+    #[serde(rename = "@draw:stroke-linejoin")]
+    pub stroke_linejoin: Option<LineJoin>,
+    #[serde(rename = "@draw:stroke")]
+    pub stroke: Option<Stroke>,
+    #[serde(rename = "@draw:fill")]
+    pub fill: Option<Fill>,
+    #[serde(rename = "@draw:fill-color")]
+    pub fill_color: Option<Parsed<Color>>,
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TextAlign {
+  #[serde(rename = "start")]
+  Start,
+  #[serde(rename = "end")]
+  End,
+  #[serde(rename = "left")]
+  Left,
+  #[serde(rename = "right")]
+  Right,
+  #[serde(rename = "center")]
+  Center,
+  #[serde(rename = "justify")]
+  Justify,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+/// Whether a paragraph is kept on the same page as the next one
+/// (`fo:keep-with-next`) or has its lines kept together (`fo:keep-together`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Keep {
+  #[serde(rename = "auto")]
+  Auto,
+  #[serde(rename = "always")]
+  Always,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+/// Direction of lines and of the text in them, "page" taking the one of the
+/// page.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WritingMode {
+  #[serde(rename = "lr-tb")]
+  LrTb,
+  #[serde(rename = "rl-tb")]
+  RlTb,
+  #[serde(rename = "tb-rl")]
+  TbRl,
+  #[serde(rename = "tb-lr")]
+  TbLr,
+  #[serde(rename = "lr")]
+  Lr,
+  #[serde(rename = "rl")]
+  Rl,
+  #[serde(rename = "tb")]
+  Tb,
+  #[serde(rename = "page")]
+  Page,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+/// A border written "width style color", eg. "0.06pt solid #000000".
+/// Other forms, such as "none", stay raw in [`Parsed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Border {
+  /// A length, or a keyword such as "thin".
+  pub width: Parsed<Length>,
+  /// "solid", "double", "dotted"...
+  pub style: String,
+  pub color: Parsed<Color>,
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct ParagraphProperty {
+    #[serde(rename = "@fo:break-before")]
+    pub break_before: Option<String>, // 'auto'
+    #[serde(rename = "@fo:break-after")]
+    pub break_after: Option<String>, // 'auto'
+    #[serde(rename = "@fo:margin-left")]
+    pub margin_left: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-right")]
+    pub margin_right: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-top")]
+    pub margin_top: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-bottom")]
+    pub margin_bottom: Option<Parsed<LengthOrPercent>>,
+
+    #[serde(rename = "@fo:text-align")]
+    pub text_align: Option<TextAlign>,
+    /// Indent of the first line, negative for a hanging indent.
+    #[serde(rename = "@fo:text-indent")]
+    pub text_indent: Option<Parsed<LengthOrPercent>>,
+    /// "normal", a length or a percentage of the font height.
+    #[serde(rename = "@fo:line-height")]
+    pub line_height: Option<Parsed<LengthOrPercent>>,
+    /// "transparent" or a color.
+    #[serde(rename = "@fo:background-color")]
+    pub background_color: Option<Parsed<Color>>,
+
+    /// Border of all sides, eg. "0.06pt solid #000000" or "none". The side
+    /// borders override it.
+    #[serde(rename = "@fo:border")]
+    pub border: Option<Parsed<Border>>,
+    #[serde(rename = "@fo:border-top")]
+    pub border_top: Option<Parsed<Border>>,
+    #[serde(rename = "@fo:border-bottom")]
+    pub border_bottom: Option<Parsed<Border>>,
+    #[serde(rename = "@fo:border-left")]
+    pub border_left: Option<Parsed<Border>>,
+    #[serde(rename = "@fo:border-right")]
+    pub border_right: Option<Parsed<Border>>,
+    /// Padding of all sides, overridden by the side paddings.
+    #[serde(rename = "@fo:padding")]
+    pub padding: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:padding-top")]
+    pub padding_top: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:padding-bottom")]
+    pub padding_bottom: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:padding-left")]
+    pub padding_left: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:padding-right")]
+    pub padding_right: Option<Parsed<Length>>,
+
+    #[serde(rename = "@fo:keep-with-next")]
+    pub keep_with_next: Option<Keep>,
+    #[serde(rename = "@fo:keep-together")]
+    pub keep_together: Option<Keep>,
+    #[serde(rename = "@style:writing-mode")]
+    pub writing_mode: Option<WritingMode>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct OfficeText {
+    #[serde(rename = "text:tracked-changes")]
+    pub tracked_changes: Option<TrackedChanges>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<OfficeTextItem>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct Body {
+    #[serde(rename = "office:text")]
+    //     pub list: Vec<OfficeTextItem>,
+    pub text: OfficeText,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct Style {
+    #[serde(rename = "@style:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@style:family")]
+    pub family: Option<String>, // 'paragraph' | 'text' | 'table' ...
+
+    #[serde(rename = "@style:list-style-name")]
+    pub list_style_name: Option<String>,
+
+    #[serde(rename = "@style:parent-style-name")]
+    pub parent_style_name: Option<String>,
+
+    /// Master page a paragraph of this style starts, on a new page. Empty
+    /// when it keeps the current one.
+    #[serde(rename = "@style:master-page-name")]
+    pub master_page_name: Option<String>,
+
+    // Properties are in schema order, the writer follows it.
+    #[serde(rename = "style:graphic-properties")]
+    pub graphic_properties: Option<GraphicProperty>,
+
+    #[serde(rename = "style:paragraph-properties")]
+    pub paragraph_properties: Option<ParagraphProperty>,
+
+    #[serde(rename = "style:text-properties")]
+    pub text_properties: Option<TextProperty>,
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PrintOrientation {
+  #[serde(rename = "portrait")]
+  Portrait,
+  #[serde(rename = "landscape")]
+  Landscape,
+  /// Any other value, kept as written.
+  #[serde(untagged)]
+  Raw(String),
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct StyleColumn {
+    /// Relative width, eg. "4822*".
+    #[serde(rename = "@style:rel-width")]
+    pub rel_width: Option<Parsed<RelativeWidth>>,
+    #[serde(rename = "@fo:start-indent")]
+    pub start_indent: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:end-indent")]
+    pub end_indent: Option<Parsed<Length>>,
+  }
+}
+
+lossless! {
+  /// Text columns of a page or a section. `columns` is empty when they have
+  /// the same width.
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct StyleColumns {
+    #[serde(rename = "@fo:column-count")]
+    pub column_count: Option<u32>,
+    #[serde(rename = "@fo:column-gap")]
+    pub column_gap: Option<Parsed<Length>>,
+    #[serde(default)]
+    #[serde(rename = "style:column")]
+    pub columns: Vec<StyleColumn>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct PageLayoutProperty {
+    #[serde(rename = "@fo:page-width")]
+    pub page_width: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:page-height")]
+    pub page_height: Option<Parsed<Length>>,
+    /// Format of page numbers, eg. "1" or "i".
+    #[serde(rename = "@style:num-format")]
+    pub num_format: Option<String>,
+    #[serde(rename = "@style:print-orientation")]
+    pub print_orientation: Option<PrintOrientation>,
+    #[serde(rename = "@fo:margin-top")]
+    pub margin_top: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-bottom")]
+    pub margin_bottom: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-left")]
+    pub margin_left: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-right")]
+    pub margin_right: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@style:writing-mode")]
+    pub writing_mode: Option<WritingMode>,
+    /// "transparent" or a color.
+    #[serde(rename = "@fo:background-color")]
+    pub background_color: Option<Parsed<Color>>,
+
+    #[serde(rename = "style:columns")]
+    pub columns: Option<StyleColumns>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct HeaderFooterProperty {
+    #[serde(rename = "@fo:min-height")]
+    pub min_height: Option<Parsed<Length>>,
+    #[serde(rename = "@svg:height")]
+    pub height: Option<Parsed<Length>>,
+    #[serde(rename = "@fo:margin-top")]
+    pub margin_top: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-bottom")]
+    pub margin_bottom: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-left")]
+    pub margin_left: Option<Parsed<LengthOrPercent>>,
+    #[serde(rename = "@fo:margin-right")]
+    pub margin_right: Option<Parsed<LengthOrPercent>>,
+    /// Whether the spacing to the body shrinks when the content grows.
+    #[serde(rename = "@style:dynamic-spacing")]
+    pub dynamic_spacing: Option<bool>,
+  }
+}
+
+lossless! {
+  /// Size of the header or footer area. Empty when the page has none.
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct HeaderFooterStyle {
+    #[serde(rename = "style:header-footer-properties")]
+    pub header_footer_properties: Option<HeaderFooterProperty>,
+  }
+}
+
+lossless! {
+  /// Page geometry, used by master pages.
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct PageLayout {
+    #[serde(rename = "@style:name")]
+    pub name: Option<String>,
+
+    /// "all", "left", "right" or "mirrored".
+    #[serde(rename = "@style:page-usage")]
+    pub page_usage: Option<String>,
+
+    #[serde(rename = "style:page-layout-properties")]
+    pub page_layout_properties: Option<PageLayoutProperty>,
+
+    #[serde(rename = "style:header-style")]
+    pub header_style: Option<HeaderFooterStyle>,
+
+    #[serde(rename = "style:footer-style")]
+    pub footer_style: Option<HeaderFooterStyle>,
+  }
+}
+
+lossless! {
+  /// Header or footer content, in the same block model as the body.
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct HeaderFooter {
+    /// Hidden when false.
+    #[serde(rename = "@style:display")]
+    pub display: Option<bool>,
+
+    #[serde(default)]
+    #[serde(rename = "$value")]
+    pub list: Vec<OfficeTextItem>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct MasterPage {
+    #[serde(rename = "@style:name")]
+    pub name: Option<String>,
+
+    #[serde(rename = "@style:display-name")]
+    pub display_name: Option<String>,
+
+    #[serde(rename = "@style:page-layout-name")]
+    pub page_layout_name: Option<String>,
+
+    /// Master page of the page after this one.
+    #[serde(rename = "@style:next-style-name")]
+    pub next_style_name: Option<String>,
+
+    /// Drawing page style, for the page background.
+    #[serde(rename = "@draw:style-name")]
+    pub draw_style_name: Option<String>,
+
+    #[serde(rename = "style:header")]
+    pub header: Option<HeaderFooter>,
+
+    /// Header of left pages, when it differs from `header`.
+    #[serde(rename = "style:header-left")]
+    pub header_left: Option<HeaderFooter>,
+
+    /// Header of the first page, when it differs from `header`.
+    #[serde(rename = "style:header-first")]
+    pub header_first: Option<HeaderFooter>,
+
+    #[serde(rename = "style:footer")]
+    pub footer: Option<HeaderFooter>,
+
+    #[serde(rename = "style:footer-left")]
+    pub footer_left: Option<HeaderFooter>,
+
+    #[serde(rename = "style:footer-first")]
+    pub footer_first: Option<HeaderFooter>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct MasterStyles {
+    #[serde(default)]
+    #[serde(rename = "style:master-page")]
+    pub master_pages: Vec<MasterPage>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct AutomaticStyle {
+    #[serde(default)]
+    #[serde(rename = "style:style")]
+    pub styles: Vec<Style>,
+
+    /// Only in styles.xml.
+    #[serde(default)]
+    #[serde(rename = "style:page-layout")]
+    pub page_layouts: Vec<PageLayout>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  #[serde(rename = "office:document-content")]
+  pub struct DocumentContent {
+    // Fields are in document order, the writer follows it.
+    #[serde(rename = "office:font-face-decls")]
+    pub font_face_decls: Vec<FontFaceDecl>,
+
+    #[serde(rename = "office:automatic-styles")]
+    pub automatic_styles: AutomaticStyle,
+
+    #[serde(rename = "office:body")]
+    pub body: Body,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ListLLevelLabelAlignment {
+    #[serde(rename = "@fo:margin-left")]
+    pub margin_left: Option<Parsed<LengthOrPercent>>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ListLevelProperties {
+    #[serde(rename = "style:list-level-label-alignment")]
+    pub list_level_alignment: ListLLevelLabelAlignment,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ListLevelStyleBullet {
+    #[serde(rename = "@text:level")]
+    pub level: u32,
+    #[serde(rename = "style:list-level-properties")]
+    pub list_level_properties: ListLevelProperties,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ListLevelStyleNumber {
+    #[serde(rename = "@text:level")]
+    pub level: u32,
+    #[serde(rename = "@text:start-value")]
+    pub start_value: Option<u32>,
+    #[serde(rename = "@style:num-format")]
+    pub num_format: String, // = 1
+    #[serde(rename = "style:list-level-properties")]
+    pub list_level_properties: ListLevelProperties,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct ListStyle {
+    #[serde(rename = "@style:name")]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "text:list-level-style-bullet")]
+    pub list_level_style_bullet: Vec<ListLevelStyleBullet>,
+    #[serde(default)]
+    #[serde(rename = "text:list-level-style-number")]
+    pub list_level_style_number: Vec<ListLevelStyleNumber>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct OfficeStyles {
+    #[serde(default)]
+    #[serde(rename = "style:default-style")]
+    pub default_styles: Vec<Style>,
+
+    /// Defaults of the page layouts.
+    #[serde(rename = "style:default-page-layout")]
+    pub default_page_layout: Option<PageLayout>,
+
+    #[serde(default)]
+    #[serde(rename = "text:list-style")]
+    pub list_styles: Vec<ListStyle>,
+
+    #[serde(default)]
+    #[serde(rename = "style:style")]
+    pub styles: Vec<Style>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  #[serde(rename = "office:document-styles")]
+  pub struct DocumentStyles {
+    #[serde(rename = "office:styles")]
+    pub styles: OfficeStyles,
+
+    /// Page layouts, and the styles used by headers and footers.
+    #[serde(default)]
+    #[serde(rename = "office:automatic-styles")]
+    pub automatic_styles: AutomaticStyle,
+
+    #[serde(rename = "office:master-styles")]
+    pub master_styles: Option<MasterStyles>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  #[serde(rename = "office:document-meta")]
+  pub struct DocumentMeta {
+    #[serde(default)]
+    #[serde(rename = "office:meta")]
+    pub meta: OfficeMeta,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct OfficeMeta {
+    // Fields are in the order LibreOffice writes them, the writer follows it.
+    #[serde(rename = "meta:generator")]
+    pub generator: Option<String>,
+
+    #[serde(rename = "dc:title")]
+    pub title: Option<String>,
+
+    #[serde(rename = "dc:description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "dc:subject")]
+    pub subject: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "meta:keyword")]
+    pub keywords: Vec<String>,
+
+    #[serde(rename = "meta:initial-creator")]
+    pub initial_creator: Option<String>,
+
+    /// Last author
+    #[serde(rename = "dc:creator")]
+    pub creator: Option<String>,
+
+    #[serde(rename = "meta:printed-by")]
+    pub printed_by: Option<String>,
+
+    /// ISO 8601 date and time
+    #[serde(rename = "meta:creation-date")]
+    pub creation_date: Option<String>,
+
+    /// Last modification, ISO 8601 date and time
+    #[serde(rename = "dc:date")]
+    pub date: Option<String>,
+
+    #[serde(rename = "meta:print-date")]
+    pub print_date: Option<String>,
+
+    #[serde(rename = "dc:language")]
+    pub language: Option<String>,
+
+    #[serde(rename = "meta:editing-cycles")]
+    pub editing_cycles: Option<u32>,
+
+    /// ISO 8601 duration, eg. `PT1H2M3S`
+    #[serde(rename = "meta:editing-duration")]
+    pub editing_duration: Option<String>,
+
+    #[serde(rename = "meta:document-statistic")]
+    pub document_statistic: Option<DocumentStatistic>,
+
+    #[serde(default)]
+    #[serde(rename = "meta:user-defined")]
+    pub user_defined: Vec<UserDefined>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+  pub struct DocumentStatistic {
+    #[serde(rename = "@meta:page-count")]
+    pub page_count: Option<u32>,
+
+    #[serde(rename = "@meta:table-count")]
+    pub table_count: Option<u32>,
+
+    #[serde(rename = "@meta:draw-count")]
+    pub draw_count: Option<u32>,
+
+    #[serde(rename = "@meta:image-count")]
+    pub image_count: Option<u32>,
+
+    #[serde(rename = "@meta:ole-object-count")]
+    pub ole_object_count: Option<u32>,
+
+    #[serde(rename = "@meta:object-count")]
+    pub object_count: Option<u32>,
+
+    #[serde(rename = "@meta:paragraph-count")]
+    pub paragraph_count: Option<u32>,
+
+    #[serde(rename = "@meta:word-count")]
+    pub word_count: Option<u32>,
+
+    #[serde(rename = "@meta:character-count")]
+    pub character_count: Option<u32>,
+
+    #[serde(rename = "@meta:non-whitespace-character-count")]
+    pub non_whitespace_character_count: Option<u32>,
+
+    #[serde(rename = "@meta:sentence-count")]
+    pub sentence_count: Option<u32>,
+
+    #[serde(rename = "@meta:syllable-count")]
+    pub syllable_count: Option<u32>,
+
+    #[serde(rename = "@meta:frame-count")]
+    pub frame_count: Option<u32>,
+
+    #[serde(rename = "@meta:row-count")]
+    pub row_count: Option<u32>,
+
+    #[serde(rename = "@meta:cell-count")]
+    pub cell_count: Option<u32>,
+  }
+}
+
+lossless! {
+  #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+  pub struct UserDefined {
+    #[serde(rename = "@meta:name")]
+    pub name: String,
+
+    /// `float`, `date`, `time`, `boolean` or `string` (the default)
+    #[serde(rename = "@meta:value-type")]
+    pub value_type: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "$text")]
+    pub value: String,
+  }
 }
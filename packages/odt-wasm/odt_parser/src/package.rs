@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::io::Read;
+
+use zip::ZipArchive;
+
+use crate::error::OdtError;
+use crate::manifest::guess_media_type;
+use crate::manifest::FileEntry;
+use crate::manifest::Manifest;
+use crate::manifest::MANIFEST_PATH;
+
+/// A zipped package with its parsed manifest. Parts are only decompressed
+/// when read.
+pub struct Package {
+  archive: ZipArchive<Cursor<Vec<u8>>>,
+  manifest: Option<Manifest>,
+}
+
+impl Package {
+  /// Opens the zip and parses `META-INF/manifest.xml`. Encrypted parts
+  /// are listed, only reading them fails.
+  pub fn new(zip_data: Vec<u8>) -> Result<Self, OdtError> {
+    let archive = ZipArchive::new(Cursor::new(zip_data))?;
+    let mut package = Self {
+      archive,
+      manifest: None,
+    };
+
+    if package.archive.index_for_name(MANIFEST_PATH).is_some() {
+      let manifest = Manifest::parse(&package.read(MANIFEST_PATH)?)?;
+      package.manifest = Some(manifest);
+    }
+
+    Ok(package)
+  }
+
+  pub fn manifest(&self) -> Option<&Manifest> {
+    self.manifest.as_ref()
+  }
+
+  pub fn into_manifest(self) -> Option<Manifest> {
+    self.manifest
+  }
+
+  /// Every file of the zip, listed in the manifest or not.
+  pub fn file_names(&self) -> Vec<String> {
+    self
+      .archive
+      .file_names()
+      .filter(|name| !name.ends_with('/'))
+      .map(|name| name.to_string())
+      .collect()
+  }
+
+  /// The manifest's file entries that are present in the zip. Without a
+  /// manifest, one entry per file with its media type guessed from the
+  /// extension.
+  pub fn entries(&self) -> Vec<FileEntry> {
+    match &self.manifest {
+      Some(manifest) => manifest
+        .files()
+        .filter(|entry| self.contains(&entry.full_path))
+        .cloned()
+        .collect(),
+      None => self
+        .file_names()
+        .into_iter()
+        .map(|name| FileEntry::new(&name, guess_media_type(&name)))
+        .collect(),
+    }
+  }
+
+  /// Entries whose media type starts with `prefix`, eg. `image/`.
+  pub fn entries_of_type(&self, prefix: &str) -> Vec<FileEntry> {
+    self
+      .entries()
+      .into_iter()
+      .filter(|entry| entry.media_type.starts_with(prefix))
+      .collect()
+  }
+
+  pub fn contains(&self, path: &str) -> bool {
+    self.archive.index_for_name(path).is_some()
+  }
+
+  /// Decompresses one part. Fails with
+  /// [`OdtError::UnsupportedEncryption`] if the manifest says it is
+  /// encrypted.
+  pub fn read(&mut self, path: &str) -> Result<Vec<u8>, OdtError> {
+    let encrypted = self
+      .manifest
+      .as_ref()
+      .and_then(|manifest| manifest.entry(path))
+      .is_some_and(|entry| entry.encryption_data.is_some());
+    if encrypted {
+      return Err(OdtError::UnsupportedEncryption {
+        path: path.to_string(),
+      });
+    }
+
+    let mut file = self.archive.by_name(path).map_err(|err| match err {
+      zip::result::ZipError::FileNotFound => OdtError::MissingPart {
+        path: path.to_string(),
+      },
+      err => match err.into() {
+        OdtError::UnsupportedEncryption { .. } => {
+          OdtError::UnsupportedEncryption {
+            path: path.to_string(),
+          }
+        }
+        err => err,
+      },
+    })?;
+
+    let mut content = Vec::new();
+    file
+      .read_to_end(&mut content)
+      .map_err(|err| OdtError::InvalidZip {
+        message: err.to_string(),
+      })?;
+    Ok(content)
+  }
+
+  /// Reads the given parts by path.
+  pub fn read_all(
+    &mut self,
+    paths: impl IntoIterator<Item = String>,
+  ) -> Result<HashMap<String, Vec<u8>>, OdtError> {
+    paths
+      .into_iter()
+      .map(|path| {
+        let content = self.read(&path)?;
+        Ok((path, content))
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Write;
+  use zip::write::SimpleFileOptions;
+  use zip::ZipWriter;
+
+  fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, bytes) in files {
+      zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+      zip.write_all(bytes).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+  }
+
+  const MANIFEST: &[u8] = br#"<manifest:manifest
+  xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0">
+  <manifest:file-entry manifest:full-path="/"
+    manifest:media-type="application/vnd.oasis.opendocument.text"/>
+  <manifest:file-entry manifest:full-path="content.xml"
+    manifest:media-type="text/xml"/>
+  <manifest:file-entry manifest:full-path="Pictures/a.svg"
+    manifest:media-type="image/svg+xml"/>
+  <manifest:file-entry manifest:full-path="Pictures/b.emf"
+    manifest:media-type="image/x-emf"/>
+  <manifest:file-entry manifest:full-path="Pictures/missing.png"
+    manifest:media-type="image/png"/>
+</manifest:manifest>"#;
+
+  #[test]
+  fn lists_and_reads_entries() {
+    let mut package = Package::new(zip(&[
+      ("mimetype", b"application/vnd.oasis.opendocument.text"),
+      ("content.xml", b"<a/>"),
+      ("Pictures/a.svg", b"<svg/>"),
+      ("Pictures/b.emf", b"emf"),
+      (MANIFEST_PATH, MANIFEST),
+    ]))
+    .unwrap();
+
+    let paths: Vec<String> = package
+      .entries()
+      .into_iter()
+      .map(|entry| entry.full_path)
+      .collect();
+    assert_eq!(paths, ["content.xml", "Pictures/a.svg", "Pictures/b.emf"]);
+
+    let images = package.entries_of_type("image/");
+    assert_eq!(images.len(), 2);
+    assert_eq!(images[1].media_type, "image/x-emf");
+    assert_eq!(package.read(&images[0].full_path).unwrap(), b"<svg/>");
+
+    let err = package.read("Pictures/missing.png").unwrap_err();
+    assert_eq!(err.kind(), "missing-part");
+  }
+
+  #[test]
+  fn without_manifest() {
+    let package =
+      Package::new(zip(&[("content.xml", b"<a/>"), ("x.jpeg", b"")])).unwrap();
+    assert!(package.manifest().is_none());
+    let entries = package.entries();
+    assert_eq!(entries[1], FileEntry::new("x.jpeg", "image/jpeg"));
+  }
+
+  #[test]
+  fn rejects_reading_encrypted_parts() {
+    let manifest = br#"<manifest:manifest
+  xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0">
+  <manifest:file-entry manifest:full-path="content.xml"
+    manifest:media-type="text/xml">
+    <manifest:encryption-data manifest:checksum="x">
+      <manifest:algorithm manifest:algorithm-name="aes"/>
+    </manifest:encryption-data>
+  </manifest:file-entry>
+  <manifest:file-entry manifest:full-path="Pictures/a.svg"
+    manifest:media-type="image/svg+xml"/>
+</manifest:manifest>"#;
+    let mut package = Package::new(zip(&[
+      ("content.xml", b""),
+      ("Pictures/a.svg", b"<svg/>"),
+      (MANIFEST_PATH, manifest),
+    ]))
+    .unwrap();
+    assert_eq!(package.entries().len(), 2);
+    assert_eq!(package.read("Pictures/a.svg").unwrap(), b"<svg/>");
+    assert_eq!(
+      package.read("content.xml").unwrap_err(),
+      OdtError::UnsupportedEncryption {
+        path: "content.xml".to_string()
+      }
+    );
+  }
+}
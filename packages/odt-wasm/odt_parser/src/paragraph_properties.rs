@@ -0,0 +1,176 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ods_structs::Border;
+use crate::ods_structs::ParagraphProperty;
+use crate::units::Length;
+use crate::units::Parsed;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Side {
+  Top,
+  Bottom,
+  Left,
+  Right,
+}
+
+impl ParagraphProperty {
+  /// Border of one side, from `fo:border-*` or the `fo:border` shorthand.
+  pub fn border(&self, side: Side) -> Option<&Parsed<Border>> {
+    let border = match side {
+      Side::Top => &self.border_top,
+      Side::Bottom => &self.border_bottom,
+      Side::Left => &self.border_left,
+      Side::Right => &self.border_right,
+    };
+    border.as_ref().or(self.border.as_ref())
+  }
+
+  /// Padding of one side, from `fo:padding-*` or the `fo:padding`
+  /// shorthand.
+  pub fn padding(&self, side: Side) -> Option<&Parsed<Length>> {
+    let padding = match side {
+      Side::Top => &self.padding_top,
+      Side::Bottom => &self.padding_bottom,
+      Side::Left => &self.padding_left,
+      Side::Right => &self.padding_right,
+    };
+    padding.as_ref().or(self.padding.as_ref())
+  }
+
+  /// Whether a line is drawn on the side, as quotes often have on the left.
+  pub fn has_border(&self, side: Side) -> bool {
+    self
+      .border(side)
+      .and_then(Parsed::value)
+      .is_some_and(|border| !matches!(border.style.as_str(), "none" | "hidden"))
+  }
+}
+
+impl FromStr for Border {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    match parts[..] {
+      [width, style, color] => Ok(Border {
+        width: width.into(),
+        style: style.to_string(),
+        color: color.into(),
+      }),
+      _ => Err(format!("invalid border: {:?}", value)),
+    }
+  }
+}
+
+impl fmt::Display for Border {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} {} {}", self.width, self.style, self.color)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::tests::styled_content_xml;
+  use crate::ods_structs::Keep;
+  use crate::ods_structs::TextAlign;
+  use crate::ods_structs::WritingMode;
+  use crate::writer::write_content;
+
+  const STYLES: &str = r##"
+    <style:style style:name="Quote" style:family="paragraph">
+      <style:paragraph-properties fo:margin-left="0.5in" fo:margin-right="0.5in" fo:margin-top="0.1in" fo:margin-bottom="0.1in" fo:text-align="justify" fo:text-indent="-0.25in" fo:line-height="115%" fo:background-color="#eeeeee" fo:border="none" fo:border-left="0.06pt solid #999999" fo:padding="0.02in" fo:padding-left="0.1in" fo:keep-with-next="always" fo:keep-together="auto" style:writing-mode="lr-tb"/>
+    </style:style>"##;
+
+  #[test]
+  fn parses_paragraph_properties() {
+    let content = parse_content(styled_content_xml(STYLES, "")).unwrap();
+    let properties = content.automatic_styles.styles[0]
+      .paragraph_properties
+      .as_ref()
+      .unwrap();
+
+    assert_eq!(properties.margin_right, Some("0.5in".into()));
+    assert_eq!(properties.margin_top, Some("0.1in".into()));
+    assert_eq!(properties.margin_bottom, Some("0.1in".into()));
+    assert_eq!(properties.text_align, Some(TextAlign::Justify));
+    assert_eq!(properties.text_indent, Some("-0.25in".into()));
+    assert_eq!(properties.line_height, Some("115%".into()));
+    assert_eq!(properties.background_color, Some("#eeeeee".into()));
+    assert_eq!(properties.keep_with_next, Some(Keep::Always));
+    assert_eq!(properties.keep_together, Some(Keep::Auto));
+    assert_eq!(properties.writing_mode, Some(WritingMode::LrTb));
+
+    assert!(properties.has_border(Side::Left));
+    assert!(!properties.has_border(Side::Top));
+    let left = properties.border(Side::Left).unwrap();
+    assert_eq!(left.text(), "0.06pt solid #999999");
+    let left = left.value().unwrap();
+    assert_eq!(left.width, "0.06pt".into());
+    assert_eq!(left.style, "solid");
+    assert_eq!(left.color.value(), Some(&"#999999".parse().unwrap()));
+    assert_eq!(properties.padding(Side::Left), Some(&"0.1in".into()));
+    assert_eq!(properties.padding(Side::Right), Some(&"0.02in".into()));
+
+    let xml = write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn keeps_unknown_values() {
+    let styles = STYLES
+      .replace(r#"fo:text-align="justify""#, r#"fo:text-align="inside""#)
+      .replace(r#"fo:keep-together="auto""#, r#"fo:keep-together="never""#)
+      .replace(
+        r#"style:writing-mode="lr-tb""#,
+        r#"style:writing-mode="bt-lr""#,
+      );
+    let content = parse_content(styled_content_xml(&styles, "")).unwrap();
+    let properties = content.automatic_styles.styles[0]
+      .paragraph_properties
+      .as_ref()
+      .unwrap();
+    assert_eq!(
+      properties.text_align,
+      Some(TextAlign::Raw("inside".to_string()))
+    );
+    assert_eq!(
+      properties.keep_together,
+      Some(Keep::Raw("never".to_string()))
+    );
+    assert_eq!(
+      properties.writing_mode,
+      Some(WritingMode::Raw("bt-lr".to_string()))
+    );
+
+    let xml = write_content(&content).unwrap();
+    assert!(
+      String::from_utf8_lossy(&xml).contains(r#"style:writing-mode="bt-lr""#)
+    );
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+  #[test]
+  fn writes_values_as_read() {
+    let styles = STYLES
+      .replace(r#"fo:margin-left="0.5in""#, r#"fo:margin-left="0.50in""#)
+      .replace("0.06pt solid #999999", "0.060pt  solid #999999")
+      .replace("#eeeeee", "#EEEEEE");
+    let content = parse_content(styled_content_xml(&styles, "")).unwrap();
+    let properties = content.automatic_styles.styles[0]
+      .paragraph_properties
+      .as_ref()
+      .unwrap();
+    assert!(properties.has_border(Side::Left));
+
+    let xml = write_content(&content).unwrap();
+    let written = String::from_utf8_lossy(&xml);
+    assert!(written.contains(r#"fo:margin-left="0.50in""#));
+    assert!(written.contains(r#"fo:border-left="0.060pt  solid #999999""#));
+    assert!(written.contains(r##"fo:background-color="#EEEEEE""##));
+  }
+}
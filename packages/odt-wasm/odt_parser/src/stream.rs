@@ -0,0 +1,328 @@
+use quick_xml::errors::IllFormedError;
+use quick_xml::events::BytesStart;
+use quick_xml::events::Event;
+use quick_xml::name::ResolveResult;
+use quick_xml::NsReader;
+use quick_xml::Reader;
+
+use crate::core::parse_xml;
+use crate::error::OdtError;
+use crate::namespaces::canonical_prefix;
+use crate::ods_structs::AutomaticStyle;
+use crate::ods_structs::OfficeText;
+use crate::ods_structs::OfficeTextItem;
+
+/// Pull-parser over content.xml that yields the block-level items of
+/// `office:text` (paragraphs, headings, lists, tables, ...) one at a time,
+/// instead of building the whole [`crate::ods_structs::DocumentContent`].
+///
+/// Each block is deserialized on its own, so errors carry the same path and
+/// offset as [`crate::core::parse_content`] would report.
+pub struct ContentStream {
+  xml: Vec<u8>,
+  // Offset of the next block, `None` once `office:text` is closed.
+  pos: Option<usize>,
+  // Name of the `office:text` element as written, with the namespace
+  // declarations in scope, to wrap each block in.
+  text_name: String,
+  declarations: String,
+  automatic_styles: AutomaticStyle,
+}
+
+impl ContentStream {
+  /// Reads content.xml up to the start of `office:text`, parsing the
+  /// automatic styles on the way.
+  pub fn new(xml: Vec<u8>) -> Result<Self, OdtError> {
+    let mut reader = NsReader::from_reader(&xml[..]);
+    let mut path: Vec<String> = Vec::new();
+    let mut declarations = String::new();
+    let mut automatic_styles = AutomaticStyle::default();
+
+    let (text_name, pos) = loop {
+      let event = reader.read_event().map_err(|err| OdtError::XmlSyntax {
+        message: err.to_string(),
+        offset: reader.error_position(),
+      })?;
+      let (start, is_empty) = match event {
+        Event::Start(e) => (e, false),
+        Event::Empty(e) => (e, true),
+        Event::End(_) => {
+          path.pop();
+          continue;
+        }
+        Event::Eof => {
+          return Err(OdtError::SchemaMismatch {
+            message: "missing office:text".to_string(),
+            path: path.join("/"),
+            offset: reader.buffer_position(),
+          });
+        }
+        _ => continue,
+      };
+
+      let name = canonical_name(&reader, &start);
+      let parent = path.join("/");
+      match (path.len(), name.as_str()) {
+        (0, _) | (1, "office:body") | (2, "office:text") => {
+          push_declarations(&mut declarations, &start);
+        }
+        _ => {}
+      }
+
+      match (path.len(), name.as_str()) {
+        (2, "office:text") if is_empty => break (raw_name(&start), None),
+        (2, "office:text") => {
+          break (raw_name(&start), Some(reader.buffer_position() as usize))
+        }
+        (1, "office:automatic-styles") if !is_empty => {
+          let span = reader.read_to_end(start.name()).map_err(|err| {
+            OdtError::XmlSyntax {
+              message: err.to_string(),
+              offset: reader.error_position(),
+            }
+          })?;
+          automatic_styles = parse_fragment(
+            &raw_name(&start),
+            &declarations,
+            &xml,
+            span.start as usize..span.end as usize,
+            &parent,
+          )?;
+        }
+        (0, _) | (1, "office:body") if !is_empty => path.push(name),
+        _ if !is_empty => {
+          reader.read_to_end(start.name()).map_err(|err| {
+            OdtError::XmlSyntax {
+              message: err.to_string(),
+              offset: reader.error_position(),
+            }
+          })?;
+        }
+        _ => {}
+      }
+    };
+
+    Ok(Self {
+      xml,
+      pos,
+      text_name,
+      declarations,
+      automatic_styles,
+    })
+  }
+
+  pub fn automatic_styles(&self) -> &AutomaticStyle {
+    &self.automatic_styles
+  }
+
+  fn next_block(&mut self) -> Result<Option<OfficeTextItem>, OdtError> {
+    let Some(pos) = self.pos else {
+      return Ok(None);
+    };
+
+    let mut reader = Reader::from_reader(&self.xml[pos..]);
+    let syntax =
+      |reader: &Reader<&[u8]>, err: quick_xml::Error| OdtError::XmlSyntax {
+        message: err.to_string(),
+        offset: pos as u64 + reader.error_position(),
+      };
+
+    loop {
+      let start = reader.buffer_position() as usize;
+      let event = match reader.read_event() {
+        // The reader starts inside office:text, so its end tag is unmatched.
+        Err(quick_xml::Error::IllFormed(IllFormedError::UnmatchedEndTag(
+          _,
+        ))) => Event::Eof,
+        event => event.map_err(|err| syntax(&reader, err))?,
+      };
+      match event {
+        Event::Start(e) => {
+          reader
+            .read_to_end(e.name())
+            .map_err(|err| syntax(&reader, err))?;
+        }
+        Event::Empty(_) => {}
+        Event::Eof => {
+          self.pos = None;
+          return Ok(None);
+        }
+        _ => continue,
+      }
+
+      let end = pos + reader.buffer_position() as usize;
+      self.pos = Some(end);
+
+      let text: OfficeText = parse_fragment(
+        &self.text_name,
+        &self.declarations,
+        &self.xml,
+        pos + start..end,
+        "office:document-content/office:body",
+      )?;
+      if let Some(item) = text.list.into_iter().next() {
+        return Ok(Some(item));
+      }
+    }
+  }
+}
+
+impl Iterator for ContentStream {
+  type Item = Result<OfficeTextItem, OdtError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let result = self.next_block();
+    if result.is_err() {
+      self.pos = None;
+    }
+    result.transpose()
+  }
+}
+
+fn canonical_name(reader: &NsReader<&[u8]>, start: &BytesStart) -> String {
+  let (result, local) = reader.resolver().resolve_element(start.name());
+  let local = String::from_utf8_lossy(local.as_ref());
+  match result {
+    ResolveResult::Bound(ns) => match canonical_prefix(ns.as_ref()) {
+      Some(prefix) => format!("{}:{}", prefix, local),
+      None => local.to_string(),
+    },
+    _ => local.to_string(),
+  }
+}
+
+fn raw_name(start: &BytesStart) -> String {
+  String::from_utf8_lossy(start.name().as_ref()).to_string()
+}
+
+fn push_declarations(declarations: &mut String, start: &BytesStart) {
+  for attr in start.attributes().flatten() {
+    if attr.key.as_namespace_binding().is_some() {
+      declarations.push_str(&format!(
+        r#" {}="{}""#,
+        String::from_utf8_lossy(attr.key.as_ref()),
+        String::from_utf8_lossy(&attr.value)
+      ));
+    }
+  }
+}
+
+/// Deserializes `xml[range]` wrapped in a `name` element that carries the
+/// namespace declarations. Errors are mapped back to the whole document,
+/// `parent` being the path of the wrapping element's parent.
+fn parse_fragment<T: serde::de::DeserializeOwned>(
+  name: &str,
+  declarations: &str,
+  xml: &[u8],
+  range: std::ops::Range<usize>,
+  parent: &str,
+) -> Result<T, OdtError> {
+  let open = format!("<{}{}>", name, declarations);
+  let source_start = range.start;
+  let mut fragment = open.clone().into_bytes();
+  fragment.extend_from_slice(&xml[range]);
+  fragment.extend_from_slice(format!("</{}>", name).as_bytes());
+
+  let relocate = |offset: u64| {
+    (offset as usize).saturating_sub(open.len()) as u64 + source_start as u64
+  };
+  parse_xml(&fragment).map_err(|err| match err {
+    OdtError::XmlSyntax { message, offset } => OdtError::XmlSyntax {
+      message,
+      offset: relocate(offset),
+    },
+    OdtError::SchemaMismatch {
+      message,
+      path,
+      offset,
+    } => OdtError::SchemaMismatch {
+      message,
+      path: format!("{}/{}", parent, path),
+      offset: relocate(offset),
+    },
+    err => err,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::unzip;
+
+  #[test]
+  fn streams_blocks() {
+    let mut paths = vec![std::path::PathBuf::from("src/example.odt")];
+    for entry in std::fs::read_dir("../../extension-odt/test/wgd").unwrap() {
+      let path = entry.unwrap().path();
+      if path.extension().is_some_and(|ext| ext == "odt") {
+        paths.push(path);
+      }
+    }
+
+    for path in paths {
+      let files = unzip(std::fs::read(&path).unwrap()).unwrap();
+      let xml = files["content.xml"].clone();
+      let content = parse_content(xml.clone()).unwrap();
+
+      let stream = ContentStream::new(xml).unwrap();
+      assert_eq!(
+        stream.automatic_styles(),
+        &content.automatic_styles,
+        "{:?}",
+        path
+      );
+      let blocks: Vec<OfficeTextItem> =
+        stream.collect::<Result<_, _>>().unwrap();
+      assert_eq!(blocks, content.body.text.list, "{:?}", path);
+    }
+  }
+
+  #[test]
+  fn same_errors_as_parse_content() {
+    let xml = br#"<o:document-content
+  xmlns:o="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0">
+  <o:automatic-styles/>
+  <o:body><o:text>
+    <text:p>one</text:p>
+    <text:list><text:list-item>
+      <text:p><text:s text:c="many"/></text:p>
+    </text:list-item></text:list>
+    <text:p>three</text:p>
+  </o:text></o:body>
+</o:document-content>"#;
+
+    let mut stream = ContentStream::new(xml.to_vec()).unwrap();
+    assert!(matches!(
+      stream.next(),
+      Some(Ok(OfficeTextItem::TextParagraph(_)))
+    ));
+    let err = stream.next().unwrap().unwrap_err();
+    assert_eq!(stream.next(), None);
+
+    let OdtError::SchemaMismatch { path, offset, .. } = err else {
+      panic!("unexpected error: {:?}", err);
+    };
+    assert_eq!(
+      path,
+      "office:document-content/office:body/office:text/text:list/\
+text:list-item/text:p/text:s"
+    );
+    assert_eq!(
+      offset,
+      xml.windows(7).position(|w| w == b"<text:s").unwrap() as u64
+    );
+  }
+
+  #[test]
+  fn empty_text() {
+    let xml = br#"<office:document-content
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0">
+  <office:body><office:text/></office:body>
+</office:document-content>"#;
+    let mut stream = ContentStream::new(xml.to_vec()).unwrap();
+    assert!(stream.automatic_styles().styles.is_empty());
+    assert_eq!(stream.next(), None);
+  }
+}
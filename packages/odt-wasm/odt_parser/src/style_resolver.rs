@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::error::OdtError;
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::DocumentStyles;
+use crate::ods_structs::ParagraphProperty;
+use crate::ods_structs::Style;
+use crate::ods_structs::TextProperty;
+use crate::units::Length;
+use crate::units::LengthOrPercent;
+use crate::units::Parsed;
+use crate::units::Percent;
+
+/// Fills the properties missing in `self` from an ancestor style.
+pub trait Inherit {
+  fn inherit(&mut self, parent: &Self);
+}
+
+macro_rules! inherit_fields {
+  ($self:ident, $parent:ident, $($field:ident),* $(,)?) => {
+    $(
+      if $self.$field.is_none() {
+        $self.$field = $parent.$field.clone();
+      }
+    )*
+  };
+}
+
+impl Inherit for TextProperty {
+  fn inherit(&mut self, parent: &Self) {
+    // A percentage font size is relative to the size of the parent style.
+    if let (Some(size), Some(parent_size)) =
+      (&self.font_size, &parent.font_size)
+    {
+      if let Some(LengthOrPercent::Percent(percent)) = size.value() {
+        if let Some(scaled) = scale(parent_size, *percent) {
+          self.font_size = Some(Parsed::new(scaled));
+        }
+      }
+    }
+    inherit_fields!(
+      self,
+      parent,
+      font_name,
+      font_weight,
+      font_style,
+      underline_style,
+      font_size,
+      font_color,
+      font_name_asian,
+      font_name_complex,
+      font_variant,
+      text_transform,
+      underline_type,
+      underline_width,
+      underline_color,
+      line_through_style,
+      line_through_type,
+      text_position,
+      background_color,
+      language,
+      country,
+    );
+  }
+}
+
+// Applies a percentage to a font size, `None` when the size does not parse.
+fn scale(
+  size: &Parsed<LengthOrPercent>,
+  percent: Percent,
+) -> Option<LengthOrPercent> {
+  Some(match size.value()? {
+    LengthOrPercent::Length(length) => LengthOrPercent::Length(Length::new(
+      length.value * percent.0 / 100.0,
+      length.unit,
+    )),
+    LengthOrPercent::Percent(parent) => {
+      LengthOrPercent::Percent(Percent(parent.0 * percent.0 / 100.0))
+    }
+  })
+}
+
+impl Inherit for ParagraphProperty {
+  fn inherit(&mut self, parent: &Self) {
+    // A shorthand set closer to the paragraph hides the sides set by the
+    // ancestors.
+    if self.border.is_none() {
+      inherit_fields!(
+        self,
+        parent,
+        border_top,
+        border_bottom,
+        border_left,
+        border_right
+      );
+    }
+    if self.padding.is_none() {
+      inherit_fields!(
+        self,
+        parent,
+        padding_top,
+        padding_bottom,
+        padding_left,
+        padding_right
+      );
+    }
+    inherit_fields!(
+      self,
+      parent,
+      break_before,
+      break_after,
+      margin_left,
+      margin_right,
+      margin_top,
+      margin_bottom,
+      text_align,
+      text_indent,
+      line_height,
+      background_color,
+      border,
+      padding,
+      keep_with_next,
+      keep_together,
+      writing_mode,
+    );
+  }
+}
+
+/// Effective properties of a style after applying its parent chain and the
+/// default style of its family.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ResolvedStyle {
+  pub name: String,
+  pub family: String,
+  /// The style itself followed by its ancestors.
+  pub chain: Vec<String>,
+  pub list_style_name: Option<String>,
+  pub master_page_name: Option<String>,
+  pub text_properties: TextProperty,
+  pub paragraph_properties: ParagraphProperty,
+}
+
+/// Resolves style names used in content.xml against the automatic styles of
+/// content.xml and the common and default styles of styles.xml.
+pub struct StyleResolver<'a> {
+  defaults: HashMap<&'a str, &'a Style>,
+  common: HashMap<(&'a str, &'a str), &'a Style>,
+  automatic: HashMap<(&'a str, &'a str), &'a Style>,
+}
+
+fn by_family_and_name(styles: &[Style]) -> HashMap<(&str, &str), &Style> {
+  styles
+    .iter()
+    .filter_map(|style| {
+      let name = style.name.as_deref()?;
+      Some(((style.family.as_deref().unwrap_or(""), name), style))
+    })
+    .collect()
+}
+
+impl<'a> StyleResolver<'a> {
+  pub fn new(
+    styles: &'a DocumentStyles,
+    content: Option<&'a DocumentContent>,
+  ) -> Self {
+    let defaults = styles
+      .styles
+      .default_styles
+      .iter()
+      .filter_map(|style| Some((style.family.as_deref()?, style)))
+      .collect();
+
+    Self {
+      defaults,
+      common: by_family_and_name(&styles.styles.styles),
+      automatic: content
+        .map(|content| by_family_and_name(&content.automatic_styles.styles))
+        .unwrap_or_default(),
+    }
+  }
+
+  /// Resolves style names used in headers and footers, which refer to the
+  /// automatic styles of styles.xml instead of content.xml.
+  pub fn for_master_pages(styles: &'a DocumentStyles) -> Self {
+    Self {
+      automatic: by_family_and_name(&styles.automatic_styles.styles),
+      ..Self::new(styles, None)
+    }
+  }
+
+  /// Looks up a style the way a reference from the body does: automatic
+  /// styles first, then common styles.
+  pub fn get(&self, name: &str, family: &str) -> Option<&'a Style> {
+    self
+      .automatic
+      .get(&(family, name))
+      .or_else(|| self.common.get(&(family, name)))
+      .copied()
+  }
+
+  pub fn resolve(
+    &self,
+    name: &str,
+    family: &str,
+  ) -> Result<ResolvedStyle, OdtError> {
+    let mut resolved = ResolvedStyle {
+      name: name.to_string(),
+      family: family.to_string(),
+      ..Default::default()
+    };
+
+    let mut next = self.get(name, family);
+    while let Some(style) = next {
+      let style_name = style.name.clone().unwrap_or_default();
+      if resolved.chain.contains(&style_name) {
+        resolved.chain.push(style_name);
+        return Err(OdtError::StyleCycle {
+          path: resolved.chain.join(" -> "),
+        });
+      }
+      resolved.chain.push(style_name);
+      self.apply(&mut resolved, style);
+
+      // Parents are always common styles.
+      next = style
+        .parent_style_name
+        .as_deref()
+        .and_then(|parent| self.common.get(&(family, parent)).copied());
+    }
+
+    if let Some(default) = self.defaults.get(family) {
+      self.apply(&mut resolved, default);
+    }
+
+    Ok(resolved)
+  }
+
+  fn apply(&self, resolved: &mut ResolvedStyle, style: &Style) {
+    if resolved.list_style_name.is_none() {
+      resolved.list_style_name = style.list_style_name.clone();
+    }
+    if resolved.master_page_name.is_none() {
+      resolved.master_page_name = style.master_page_name.clone();
+    }
+    if let Some(text_properties) = &style.text_properties {
+      resolved.text_properties.inherit(text_properties);
+    }
+    if let Some(paragraph_properties) = &style.paragraph_properties {
+      resolved.paragraph_properties.inherit(paragraph_properties);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::parse_styles;
+  use crate::core::tests::styled_content_xml;
+  use crate::core::tests::styles_xml;
+  use crate::ods_structs::FontStyle;
+  use crate::ods_structs::FontWeight;
+  use crate::paragraph_properties::Side;
+
+  #[test]
+  fn resolves_chain() {
+    let styles = parse_styles(styles_xml(
+      r#"<style:default-style style:family="paragraph">
+  <style:paragraph-properties fo:margin-left="0in"/>
+  <style:text-properties fo:font-size="12pt" style:font-name="Arial"/>
+</style:default-style>
+<style:style style:name="Standard" style:family="paragraph">
+  <style:text-properties style:font-name="Liberation Serif"/>
+</style:style>
+<style:style style:name="Heading" style:family="paragraph"
+  style:parent-style-name="Standard">
+  <style:text-properties fo:font-weight="bold" fo:font-size="14pt"/>
+</style:style>
+<style:style style:name="Heading" style:family="text">
+  <style:text-properties fo:font-style="italic"/>
+</style:style>"#,
+      "",
+      "",
+    ))
+    .unwrap();
+    let content = parse_content(styled_content_xml(
+      r#"<style:style style:name="P1" style:family="paragraph"
+  style:parent-style-name="Heading">
+  <style:paragraph-properties fo:margin-left="1in"/>
+  <style:text-properties fo:font-style="italic"/>
+</style:style>"#,
+      "",
+    ))
+    .unwrap();
+
+    let resolver = StyleResolver::new(&styles, Some(&content));
+    let p1 = resolver.resolve("P1", "paragraph").unwrap();
+    assert_eq!(p1.chain, vec!["P1", "Heading", "Standard"]);
+    assert_eq!(p1.text_properties.font_weight, Some(FontWeight::Bold));
+    assert_eq!(p1.text_properties.font_style, Some(FontStyle::Italic));
+    assert_eq!(p1.text_properties.font_size, Some("14pt".into()));
+    assert_eq!(
+      p1.text_properties.font_name.as_deref(),
+      Some("Liberation Serif")
+    );
+    assert_eq!(p1.paragraph_properties.margin_left, Some("1in".into()));
+
+    let heading = resolver.resolve("Heading", "text").unwrap();
+    assert_eq!(heading.text_properties.font_weight, None);
+    assert_eq!(heading.text_properties.font_style, Some(FontStyle::Italic));
+
+    let missing = resolver.resolve("Missing", "paragraph").unwrap();
+    assert!(missing.chain.is_empty());
+    assert_eq!(missing.text_properties.font_size, Some("12pt".into()));
+  }
+
+  #[test]
+  fn resolves_percentage_font_sizes() {
+    let styles = parse_styles(styles_xml(
+      r#"<style:default-style style:family="paragraph">
+  <style:text-properties fo:font-size="10pt"/>
+</style:default-style>
+<style:style style:name="Standard" style:family="paragraph">
+  <style:text-properties fo:font-size="120%"/>
+</style:style>
+<style:style style:name="Title" style:family="paragraph"
+  style:parent-style-name="Standard">
+  <style:text-properties fo:font-size="150%"/>
+</style:style>"#,
+      "",
+      "",
+    ))
+    .unwrap();
+
+    let resolver = StyleResolver::new(&styles, None);
+    let standard = resolver.resolve("Standard", "paragraph").unwrap();
+    assert_eq!(standard.text_properties.font_size, Some("12pt".into()));
+    let title = resolver.resolve("Title", "paragraph").unwrap();
+    assert_eq!(title.text_properties.font_size, Some("18pt".into()));
+  }
+
+  #[test]
+  fn shorthands_hide_ancestor_sides() {
+    let styles = parse_styles(styles_xml(
+      r#"<style:style style:name="Boxed" style:family="paragraph">
+  <style:paragraph-properties fo:border-left="0.06pt solid #000000"
+    fo:border-top="0.06pt solid #000000" fo:padding-left="0.1in"/>
+</style:style>
+<style:style style:name="Plain" style:family="paragraph"
+  style:parent-style-name="Boxed">
+  <style:paragraph-properties fo:border="none"
+    fo:border-top="0.5pt solid #ff0000"/>
+</style:style>"#,
+      "",
+      "",
+    ))
+    .unwrap();
+
+    let resolver = StyleResolver::new(&styles, None);
+    let plain = resolver.resolve("Plain", "paragraph").unwrap();
+    let properties = &plain.paragraph_properties;
+    assert_eq!(properties.border_left, None);
+    assert_eq!(properties.border(Side::Left), Some(&"none".into()));
+    assert_eq!(
+      properties.border(Side::Top),
+      Some(&"0.5pt solid #ff0000".into())
+    );
+    assert_eq!(properties.padding(Side::Left), Some(&"0.1in".into()));
+  }
+
+  #[test]
+  fn detects_cycles() {
+    let styles = parse_styles(styles_xml(
+      r#"<style:style style:name="A" style:family="paragraph"
+  style:parent-style-name="B"/>
+<style:style style:name="B" style:family="paragraph"
+  style:parent-style-name="A"/>"#,
+      "",
+      "",
+    ))
+    .unwrap();
+
+    let resolver = StyleResolver::new(&styles, None);
+    let err = resolver.resolve("A", "paragraph").unwrap_err();
+    assert_eq!(
+      err,
+      OdtError::StyleCycle {
+        path: "A -> B -> A".to_string()
+      }
+    );
+  }
+}
@@ -0,0 +1,377 @@
+use serde::Serialize;
+
+use crate::error::OdtError;
+use crate::ods_structs::TableCell;
+use crate::ods_structs::TableRow;
+use crate::ods_structs::TableRowItem;
+use crate::ods_structs::TableTable;
+
+/// A table with its repeated rows and cells expanded, see
+/// [`TableTable::to_grid`].
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TableGrid<'a> {
+  /// The first `header_rows` rows come from `table:table-header-rows`.
+  pub header_rows: usize,
+  /// Rows of the same length.
+  pub rows: Vec<Vec<GridCell<'a>>>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub enum GridCell<'a> {
+  /// Top left cell of a span (or a plain cell).
+  Anchor {
+    cell: &'a TableCell,
+    colspan: usize,
+    rowspan: usize,
+  },
+  /// Hidden by the span of the anchor at `row`, `column`.
+  Covered { row: usize, column: usize },
+  /// Padding of rows shorter than the table, or a covered cell no span
+  /// reaches.
+  Empty,
+}
+
+/// Largest grid `to_grid` expands, in cells.
+pub const MAX_GRID_CELLS: usize = 1 << 20;
+
+impl TableTable {
+  /// Expands `number-rows-repeated` and `number-columns-repeated`, and
+  /// places every cell at its position in a rectangular matrix, cells under
+  /// a span being [`GridCell::Covered`]. Spans are cut at the table edges.
+  ///
+  /// Empty rows at the end of the table are expanded once whatever their
+  /// repeat, and empty cells at the end of a row only up to the declared
+  /// `table:table-column`s: spreadsheets repeat them up to their maximum
+  /// size. Fails with [`OdtError::TableTooLarge`] when the grid would have
+  /// more than [`MAX_GRID_CELLS`] cells.
+  pub fn to_grid(&self) -> Result<TableGrid<'_>, OdtError> {
+    let header_rows: Vec<(&TableRow, usize)> = self
+      .header_rows
+      .iter()
+      .flat_map(|header| header.rows.iter())
+      .map(|row| (row, count(row.number_rows_repeated)))
+      .collect();
+    let header_count = sum(header_rows.iter().map(|(_, repeat)| *repeat));
+    let last_row = self.rows.iter().rposition(|row| !is_empty_row(row));
+    let rows: Vec<(&TableRow, usize)> = header_rows
+      .into_iter()
+      .chain(self.rows.iter().enumerate().map(|(index, row)| {
+        let trailing = last_row.is_none_or(|last| index > last);
+        (row, row_repeat(row, trailing))
+      }))
+      .collect();
+
+    let declared = sum(
+      self
+        .columns
+        .iter()
+        .map(|column| count(column.number_columns)),
+    );
+    let rows: Vec<_> = rows
+      .into_iter()
+      .map(|(row, repeat)| (cells(row, declared), repeat))
+      .collect();
+    let width = rows
+      .iter()
+      .map(|(cells, _)| sum(cells.iter().map(|(_, _, repeat)| *repeat)))
+      .chain(std::iter::once(declared))
+      .max()
+      .unwrap_or(0);
+    let height = sum(rows.iter().map(|(_, repeat)| *repeat));
+    if height.saturating_mul(width) > MAX_GRID_CELLS {
+      return Err(OdtError::TableTooLarge {
+        rows: height,
+        columns: width,
+      });
+    }
+
+    let mut grid: Vec<Vec<Option<GridCell>>> =
+      (0..height).map(|_| Vec::new()).collect();
+    let rows = rows
+      .iter()
+      .flat_map(|(cells, repeat)| std::iter::repeat_n(cells, *repeat));
+
+    for (r, cells) in rows.enumerate() {
+      let mut c = 0;
+      for &(cell, covered, repeat) in cells {
+        for _ in 0..repeat {
+          if !covered {
+            let rowspan = count(cell.number_rows_spanned).min(height - r);
+            let colspan = count(cell.number_columns_spanned).min(width - c);
+            for (rr, cells) in grid.iter_mut().enumerate().skip(r).take(rowspan)
+            {
+              for cc in c..c + colspan {
+                let slot = if rr == r && cc == c {
+                  GridCell::Anchor {
+                    cell,
+                    colspan,
+                    rowspan,
+                  }
+                } else {
+                  GridCell::Covered { row: r, column: c }
+                };
+                place(cells, cc, slot);
+              }
+            }
+          }
+          c += 1;
+        }
+      }
+    }
+
+    Ok(TableGrid {
+      header_rows: header_count,
+      rows: grid
+        .into_iter()
+        .map(|mut cells| {
+          cells.resize_with(width, || None);
+          cells
+            .into_iter()
+            .map(|cell| cell.unwrap_or(GridCell::Empty))
+            .collect()
+        })
+        .collect(),
+    })
+  }
+}
+
+fn count(value: Option<u32>) -> usize {
+  value.unwrap_or(1).max(1) as usize
+}
+
+fn sum(values: impl Iterator<Item = usize>) -> usize {
+  values.fold(0, usize::saturating_add)
+}
+
+fn row_repeat(row: &TableRow, trailing: bool) -> usize {
+  if trailing {
+    1
+  } else {
+    count(row.number_rows_repeated)
+  }
+}
+
+/// The cells of a row, whether they are covered and how many columns they
+/// repeat over. Empty cells at the end of the row only fill the `declared`
+/// columns, or are taken once past them.
+fn cells(row: &TableRow, declared: usize) -> Vec<(&TableCell, bool, usize)> {
+  let cells: Vec<(&TableCell, bool)> = row
+    .list
+    .iter()
+    .filter_map(|item| match item {
+      TableRowItem::TableCell(cell) => Some((cell, false)),
+      TableRowItem::CoveredTableCell(cell) => Some((cell, true)),
+      _ => None,
+    })
+    .collect();
+  let last = cells
+    .iter()
+    .rposition(|(cell, covered)| *covered || !is_empty_cell(cell));
+  let mut column: usize = 0;
+  cells
+    .into_iter()
+    .enumerate()
+    .map(|(index, (cell, covered))| {
+      let mut repeat = count(cell.number_columns_repeated);
+      if last.is_none_or(|last| index > last) {
+        repeat = repeat.min(declared.saturating_sub(column).max(1));
+      }
+      column = column.saturating_add(repeat);
+      (cell, covered, repeat)
+    })
+    .collect()
+}
+
+fn is_empty_cell(cell: &TableCell) -> bool {
+  cell.list.is_empty()
+    && count(cell.number_columns_spanned) == 1
+    && count(cell.number_rows_spanned) == 1
+}
+
+// Covered cells continue a span from a previous row, so rows with them are
+// kept.
+fn is_empty_row(row: &TableRow) -> bool {
+  row.list.iter().all(|item| match item {
+    TableRowItem::TableCell(cell) => is_empty_cell(cell),
+    TableRowItem::CoveredTableCell(_) => false,
+    _ => true,
+  })
+}
+
+// A later cell takes the place of a span that overlaps it.
+fn place<'a>(
+  cells: &mut Vec<Option<GridCell<'a>>>,
+  column: usize,
+  cell: GridCell<'a>,
+) {
+  if cells.len() <= column {
+    cells.resize_with(column + 1, || None);
+  }
+  cells[column] = Some(cell);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::ods_structs::OfficeTextItem;
+
+  fn table(body: &str) -> TableTable {
+    let body = format!("<table:table>{}</table:table>", body);
+    let mut content = parse_content(content_xml(&body)).unwrap();
+    match content.body.text.list.remove(0) {
+      OfficeTextItem::TableTable(table) => table,
+      item => panic!("expected table, got {:?}", item),
+    }
+  }
+
+  // Grid as text: the first letter of an anchor's content with its spans,
+  // `^` for covered and `.` for empty slots.
+  fn layout(grid: &TableGrid) -> Vec<String> {
+    grid
+      .rows
+      .iter()
+      .map(|cells| {
+        cells
+          .iter()
+          .map(|cell| match cell {
+            GridCell::Anchor {
+              cell,
+              colspan,
+              rowspan,
+            } => format!("{}{}{}", text(cell), colspan, rowspan),
+            GridCell::Covered { row, column } => format!("^{}{}", row, column),
+            GridCell::Empty => ".".to_string(),
+          })
+          .collect::<Vec<_>>()
+          .join(" ")
+      })
+      .collect()
+  }
+
+  fn text(cell: &TableCell) -> String {
+    let list = serde_json::to_value(&cell.list).unwrap();
+    list[0]["text:p"]["$value"][0]["$text"]
+      .as_str()
+      .unwrap_or("_")
+      .to_string()
+  }
+
+  #[test]
+  fn expands_spans() {
+    let table = table(
+      r#"<table:table-column table:number-columns-repeated="3"/>
+<table:table-header-rows><table:table-row>
+  <table:table-cell table:number-columns-spanned="2"><text:p>a</text:p></table:table-cell>
+  <table:covered-table-cell/>
+  <table:table-cell><text:p>b</text:p></table:table-cell>
+</table:table-row></table:table-header-rows>
+<table:table-row>
+  <table:table-cell table:number-rows-spanned="2" table:number-columns-spanned="2"><text:p>c</text:p></table:table-cell>
+  <table:covered-table-cell/>
+  <table:table-cell><text:p>d</text:p></table:table-cell>
+</table:table-row>
+<table:table-row>
+  <table:covered-table-cell table:number-columns-repeated="2"/>
+  <table:table-cell><text:p>e</text:p></table:table-cell>
+</table:table-row>"#,
+    );
+    let grid = table.to_grid().unwrap();
+    assert_eq!(grid.header_rows, 1);
+    assert_eq!(
+      layout(&grid),
+      vec!["a21 ^00 b11", "c22 ^10 d11", "^10 ^10 e11"]
+    );
+  }
+
+  #[test]
+  fn expands_repeats() {
+    let table = table(
+      r#"<table:table-column table:number-columns-repeated="3"/>
+<table:table-column/>
+<table:table-row table:number-rows-repeated="2">
+  <table:table-cell table:number-columns-repeated="2"><text:p>x</text:p></table:table-cell>
+  <table:table-cell/>
+</table:table-row>
+<table:table-row>
+  <table:table-cell table:number-rows-spanned="5"><text:p>y</text:p></table:table-cell>
+</table:table-row>"#,
+    );
+    assert_eq!(
+      layout(&table.to_grid().unwrap()),
+      vec!["x11 x11 _11 .", "x11 x11 _11 .", "y11 . . ."]
+    );
+  }
+
+  #[test]
+  fn fills_declared_columns() {
+    let columns = r#"<table:table-column table:number-columns-repeated="3"/>"#;
+    let spanned = table(&format!(
+      r#"{}<table:table-row>
+  <table:table-cell table:number-columns-spanned="3"><text:p>a</text:p></table:table-cell>
+  <table:covered-table-cell table:number-columns-repeated="2"/>
+</table:table-row>"#,
+      columns
+    ));
+    assert_eq!(layout(&spanned.to_grid().unwrap()), vec!["a31 ^00 ^00"]);
+
+    let padded = table(&format!(
+      r#"{}<table:table-row>
+  <table:table-cell><text:p>x</text:p></table:table-cell>
+  <table:table-cell table:number-columns-repeated="2"/>
+</table:table-row>"#,
+      columns
+    ));
+    assert_eq!(layout(&padded.to_grid().unwrap()), vec!["x11 _11 _11"]);
+
+    // Covered cells a span reaches count past the declared columns.
+    let undeclared = table(
+      r#"<table:table-column/>
+<table:table-row>
+  <table:table-cell table:number-columns-spanned="3"><text:p>a</text:p></table:table-cell>
+  <table:covered-table-cell table:number-columns-repeated="2"/>
+</table:table-row>"#,
+    );
+    assert_eq!(layout(&undeclared.to_grid().unwrap()), vec!["a31 ^00 ^00"]);
+  }
+
+  #[test]
+  fn cuts_padding_and_spans() {
+    let table = table(
+      r#"<table:table-column table:number-columns-repeated="2"/>
+<table:table-column/>
+<table:table-row>
+  <table:table-cell table:number-columns-spanned="5"><text:p>a</text:p></table:table-cell>
+  <table:covered-table-cell table:number-columns-repeated="2"/>
+</table:table-row>
+<table:table-row table:number-rows-repeated="1048575">
+  <table:table-cell table:number-columns-repeated="16384"/>
+</table:table-row>"#,
+    );
+    assert_eq!(
+      layout(&table.to_grid().unwrap()),
+      vec!["a31 ^00 ^00", "_11 _11 _11"]
+    );
+  }
+
+  #[test]
+  fn rejects_huge_repeats() {
+    let table = table(
+      r#"<table:table-column/>
+<table:table-row table:number-rows-repeated="4294967295">
+  <table:table-cell table:number-columns-repeated="4294967295"><text:p>a</text:p></table:table-cell>
+</table:table-row>
+<table:table-row>
+  <table:table-cell><text:p>b</text:p></table:table-cell>
+</table:table-row>"#,
+    );
+    assert_eq!(
+      table.to_grid().unwrap_err(),
+      OdtError::TableTooLarge {
+        rows: 4294967296,
+        columns: 4294967295
+      }
+    );
+  }
+}
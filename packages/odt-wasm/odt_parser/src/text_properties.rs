@@ -0,0 +1,283 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::ods_structs::FontStyle;
+use crate::ods_structs::FontVariant;
+use crate::ods_structs::FontWeight;
+use crate::ods_structs::LineStyle;
+use crate::ods_structs::LineType;
+use crate::ods_structs::LineWidth;
+use crate::ods_structs::TextProperty;
+use crate::ods_structs::VerticalPosition;
+use crate::ods_structs::VerticalShift;
+use crate::units::Percent;
+
+impl TextProperty {
+  /// Bold, or a numeric weight of at least 600.
+  pub fn is_bold(&self) -> bool {
+    matches!(
+      self.font_weight,
+      Some(
+        FontWeight::Bold
+          | FontWeight::W600
+          | FontWeight::W700
+          | FontWeight::W800
+          | FontWeight::W900
+      )
+    )
+  }
+
+  pub fn is_italic(&self) -> bool {
+    matches!(
+      self.font_style,
+      Some(FontStyle::Italic | FontStyle::Oblique)
+    )
+  }
+
+  pub fn is_underlined(&self) -> bool {
+    has_line(&self.underline_style, &self.underline_type)
+  }
+
+  pub fn is_struck(&self) -> bool {
+    has_line(&self.line_through_style, &self.line_through_type)
+  }
+
+  pub fn is_superscript(&self) -> bool {
+    self
+      .text_position
+      .as_ref()
+      .and_then(|position| position.value())
+      .is_some_and(|position| position.is_raised())
+  }
+
+  pub fn is_subscript(&self) -> bool {
+    self
+      .text_position
+      .as_ref()
+      .and_then(|position| position.value())
+      .is_some_and(|position| position.is_lowered())
+  }
+
+  pub fn is_small_caps(&self) -> bool {
+    self.font_variant == Some(FontVariant::SmallCaps)
+  }
+}
+
+// A line is drawn when it has a style, its type being single by default.
+fn has_line(style: &Option<LineStyle>, line_type: &Option<LineType>) -> bool {
+  style
+    .as_ref()
+    .is_some_and(|style| *style != LineStyle::None)
+    && *line_type != Some(LineType::None)
+}
+
+impl VerticalPosition {
+  pub fn is_raised(&self) -> bool {
+    match self.shift {
+      VerticalShift::Super => true,
+      VerticalShift::Sub => false,
+      VerticalShift::Percent(percent) => percent.0 > 0.0,
+    }
+  }
+
+  pub fn is_lowered(&self) -> bool {
+    match self.shift {
+      VerticalShift::Super => false,
+      VerticalShift::Sub => true,
+      VerticalShift::Percent(percent) => percent.0 < 0.0,
+    }
+  }
+}
+
+impl FromStr for VerticalPosition {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("invalid text position: {:?}", value);
+    let percent = |part: &str| part.parse::<Percent>().map_err(|_| invalid());
+    let mut parts = value.split_whitespace();
+    let shift = match parts.next().ok_or_else(invalid)? {
+      "super" => VerticalShift::Super,
+      "sub" => VerticalShift::Sub,
+      shift => VerticalShift::Percent(percent(shift)?),
+    };
+    let font_height = parts.next().map(percent).transpose()?;
+    if parts.next().is_some() {
+      return Err(invalid());
+    }
+    Ok(VerticalPosition { shift, font_height })
+  }
+}
+
+impl fmt::Display for VerticalPosition {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.shift {
+      VerticalShift::Super => write!(f, "super")?,
+      VerticalShift::Sub => write!(f, "sub")?,
+      VerticalShift::Percent(percent) => write!(f, "{}", percent)?,
+    }
+    if let Some(font_height) = self.font_height {
+      write!(f, " {}", font_height)?;
+    }
+    Ok(())
+  }
+}
+
+impl FromStr for LineWidth {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    Ok(match value {
+      "auto" => LineWidth::Auto,
+      "normal" => LineWidth::Normal,
+      "bold" => LineWidth::Bold,
+      "thin" => LineWidth::Thin,
+      "medium" => LineWidth::Medium,
+      "thick" => LineWidth::Thick,
+      _ => LineWidth::Length(
+        value
+          .parse()
+          .map_err(|_| format!("invalid line width: {:?}", value))?,
+      ),
+    })
+  }
+}
+
+impl fmt::Display for LineWidth {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LineWidth::Auto => write!(f, "auto"),
+      LineWidth::Normal => write!(f, "normal"),
+      LineWidth::Bold => write!(f, "bold"),
+      LineWidth::Thin => write!(f, "thin"),
+      LineWidth::Medium => write!(f, "medium"),
+      LineWidth::Thick => write!(f, "thick"),
+      LineWidth::Length(length) => length.fmt(f),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::tests::styled_content_xml;
+  use crate::ods_structs::TextTransform;
+  use crate::units::Parsed;
+  use crate::writer::write_content;
+
+  const STYLES: &str = r##"
+    <style:style style:name="T1" style:family="text">
+      <style:text-properties fo:font-weight="700" fo:font-style="oblique" style:text-line-through-style="solid" style:text-position="super 58%" fo:background-color="#ffff00" fo:font-variant="small-caps" fo:text-transform="uppercase" fo:language="pl" fo:country="PL" style:font-name-asian="SimSun" style:font-name-complex="Mangal"/>
+    </style:style>
+    <style:style style:name="T2" style:family="text">
+      <style:text-properties fo:font-weight="normal" style:text-underline-style="wave" style:text-underline-type="double" style:text-underline-width="0.5pt" style:text-underline-color="font-color" style:text-line-through-style="solid" style:text-line-through-type="none" style:text-position="-33% 100%"/>
+    </style:style>
+    <style:style style:name="T3" style:family="text">
+      <style:text-properties style:text-underline-style="none" style:text-underline-width="bold" style:text-position="0%"/>
+    </style:style>"##;
+
+  #[test]
+  fn parses_text_properties() {
+    let content = parse_content(styled_content_xml(STYLES, "")).unwrap();
+    let properties: Vec<&TextProperty> = content
+      .automatic_styles
+      .styles
+      .iter()
+      .map(|style| style.text_properties.as_ref().unwrap())
+      .collect();
+
+    let t1 = properties[0];
+    assert_eq!(t1.font_weight, Some(FontWeight::W700));
+    assert!(t1.is_bold() && t1.is_italic() && t1.is_struck());
+    assert!(t1.is_superscript() && !t1.is_subscript());
+    assert_eq!(
+      t1.text_position.as_ref().and_then(Parsed::value),
+      Some(&VerticalPosition {
+        shift: VerticalShift::Super,
+        font_height: Some(Percent(58.0))
+      })
+    );
+    assert!(t1.is_small_caps());
+    assert_eq!(t1.text_transform, Some(TextTransform::Uppercase));
+    assert_eq!(t1.background_color, Some("#ffff00".into()));
+    assert_eq!(t1.language.as_deref(), Some("pl"));
+    assert_eq!(t1.country.as_deref(), Some("PL"));
+    assert_eq!(t1.font_name_asian.as_deref(), Some("SimSun"));
+    assert_eq!(t1.font_name_complex.as_deref(), Some("Mangal"));
+
+    let t2 = properties[1];
+    assert!(!t2.is_bold() && t2.is_underlined() && !t2.is_struck());
+    assert_eq!(t2.underline_style, Some(LineStyle::Wave));
+    assert_eq!(t2.underline_type, Some(LineType::Double));
+    assert_eq!(
+      t2.underline_width.as_ref().and_then(Parsed::value),
+      Some(&LineWidth::Length("0.5pt".parse().unwrap()))
+    );
+    assert_eq!(
+      t2.underline_color,
+      Some(Parsed::Raw("font-color".to_string()))
+    );
+    assert!(t2.is_subscript());
+
+    let t3 = properties[2];
+    assert!(!t3.is_underlined());
+    assert_eq!(
+      t3.underline_width.as_ref().and_then(Parsed::value),
+      Some(&LineWidth::Bold)
+    );
+    assert!(!t3.is_superscript() && !t3.is_subscript());
+
+    let xml = write_content(&content).unwrap();
+    assert!(String::from_utf8_lossy(&xml)
+      .contains(r#"style:text-position="-33% 100%""#));
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn keeps_unknown_values() {
+    let styles = STYLES.replace(
+      r#"fo:font-weight="normal" style:text-underline-style="wave""#,
+      r#"fo:font-weight="bolder" style:text-underline-style="Solid""#,
+    );
+    let content = parse_content(styled_content_xml(&styles, "")).unwrap();
+    let t2 = content.automatic_styles.styles[1]
+      .text_properties
+      .as_ref()
+      .unwrap();
+    assert_eq!(t2.font_weight, Some(FontWeight::Raw("bolder".to_string())));
+    assert_eq!(
+      t2.underline_style,
+      Some(LineStyle::Raw("Solid".to_string()))
+    );
+    assert!(!t2.is_bold() && t2.is_underlined());
+
+    let xml = write_content(&content).unwrap();
+    let written = String::from_utf8_lossy(&xml);
+    assert!(written.contains(r#"fo:font-weight="bolder""#));
+    assert!(written.contains(r#"style:text-underline-style="Solid""#));
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn keeps_invalid_text_position() {
+    for value in ["up", "super 58", "sub 58% 1%"] {
+      let styles = STYLES.replace(
+        r#"style:text-position="super 58%""#,
+        &format!(r#"style:text-position="{}""#, value),
+      );
+      let content = parse_content(styled_content_xml(&styles, "")).unwrap();
+      let t1 = content.automatic_styles.styles[0]
+        .text_properties
+        .as_ref()
+        .unwrap();
+      assert_eq!(t1.text_position, Some(Parsed::Raw(value.to_string())));
+      assert!(!t1.is_superscript() && !t1.is_subscript());
+
+      let xml = write_content(&content).unwrap();
+      assert!(String::from_utf8_lossy(&xml)
+        .contains(&format!(r#"style:text-position="{}""#, value)));
+      assert_eq!(parse_content(xml).unwrap(), content);
+    }
+  }
+}
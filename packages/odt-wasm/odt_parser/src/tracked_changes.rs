@@ -0,0 +1,197 @@
+use serde::Serialize;
+
+use crate::ods_structs::ChangeInfo;
+use crate::ods_structs::ChangedRegion;
+use crate::ods_structs::DocumentContent;
+use crate::walk::markers;
+use crate::walk::Marker;
+use crate::walk::TextPosition;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum ChangeKind {
+  Insertion,
+  Deletion,
+  FormatChange,
+}
+
+impl ChangedRegion {
+  /// `text:id`, or `xml:id` which replaces it since ODF 1.2.
+  pub fn id(&self) -> Option<&str> {
+    self.text_id.as_deref().or(self.xml_id.as_deref())
+  }
+
+  pub fn kind(&self) -> Option<ChangeKind> {
+    if self.insertion.is_some() {
+      Some(ChangeKind::Insertion)
+    } else if self.deletion.is_some() {
+      Some(ChangeKind::Deletion)
+    } else if self.format_change.is_some() {
+      Some(ChangeKind::FormatChange)
+    } else {
+      None
+    }
+  }
+
+  /// Author and date of the change.
+  pub fn change_info(&self) -> Option<&ChangeInfo> {
+    self
+      .insertion
+      .as_ref()
+      .map(|change| &change.change_info)
+      .or(self.deletion.as_ref().map(|change| &change.change_info))
+      .or(
+        self
+          .format_change
+          .as_ref()
+          .map(|change| &change.change_info),
+      )
+  }
+}
+
+/// A changed region joined to its markers in the text. Deletions are a
+/// point (`start == end`) where the deleted content was.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TrackedChange<'a> {
+  pub region: &'a ChangedRegion,
+  pub kind: Option<ChangeKind>,
+  /// `None` when no marker in the text carries the region's id.
+  pub start: Option<TextPosition>,
+  pub end: Option<TextPosition>,
+}
+
+impl DocumentContent {
+  /// The regions of `text:tracked-changes` in their order, with the
+  /// position of their `text:change-start` and `text:change-end`, or
+  /// `text:change`.
+  pub fn tracked_changes(&self) -> Vec<TrackedChange<'_>> {
+    let Some(tracked) = &self.body.text.tracked_changes else {
+      return Vec::new();
+    };
+    let mut changes: Vec<TrackedChange> = tracked
+      .regions
+      .iter()
+      .map(|region| TrackedChange {
+        region,
+        kind: region.kind(),
+        start: None,
+        end: None,
+      })
+      .collect();
+
+    for (position, marker) in markers(&self.body.text) {
+      let (id, start, end) = match marker {
+        Marker::ChangeStart(marker) => (&marker.change_id, true, false),
+        Marker::ChangeEnd(marker) => (&marker.change_id, false, true),
+        Marker::Change(marker) => (&marker.change_id, true, true),
+        _ => continue,
+      };
+      let Some(change) = changes
+        .iter_mut()
+        .find(|change| change.region.id() == Some(id.as_str()))
+      else {
+        continue;
+      };
+      if start {
+        change.start = Some(position);
+      }
+      if end {
+        change.end = Some(position);
+      }
+    }
+    changes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::open;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::ods_structs::DeletionItem;
+  use crate::writer::write_content;
+
+  const BODY: &str = r#"
+    <text:tracked-changes text:track-changes="true">
+      <text:changed-region text:id="ct1">
+        <text:insertion><office:change-info>
+          <dc:creator>Ann</dc:creator><dc:date>2024-01-01T00:00:00</dc:date>
+        </office:change-info></text:insertion>
+      </text:changed-region>
+      <text:changed-region xml:id="ct2" text:id="ct2">
+        <text:deletion>
+          <office:change-info><dc:creator>Bob</dc:creator><text:p>why</text:p></office:change-info>
+          <text:p>gone</text:p>
+        </text:deletion>
+      </text:changed-region>
+      <text:changed-region text:id="ct3">
+        <text:format-change><office:change-info/></text:format-change>
+      </text:changed-region>
+      <text:changed-region text:id="ct4">
+        <text:insertion><office:change-info/></text:insertion>
+      </text:changed-region>
+    </text:tracked-changes>
+    <text:p>Hi <text:change-start text:change-id="ct1"/>new<text:change-end text:change-id="ct1"/> text</text:p>
+    <text:p>ab<text:change text:change-id="ct2"/>c<text:span><text:change-start text:change-id="ct3"/>d</text:span><text:change-end text:change-id="ct3"/></text:p>"#;
+
+  fn at(block: usize, offset: usize) -> Option<TextPosition> {
+    Some(TextPosition { block, offset })
+  }
+
+  #[test]
+  fn joins_regions_to_markers() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let tracked = content.body.text.tracked_changes.as_ref().unwrap();
+    assert_eq!(tracked.track_changes, Some(true));
+
+    let changes = content.tracked_changes();
+    let summary: Vec<_> = changes
+      .iter()
+      .map(|change| (change.region.id(), change.kind, change.start, change.end))
+      .collect();
+    assert_eq!(
+      summary,
+      vec![
+        (Some("ct1"), Some(ChangeKind::Insertion), at(0, 3), at(0, 6)),
+        (Some("ct2"), Some(ChangeKind::Deletion), at(1, 2), at(1, 2)),
+        (
+          Some("ct3"),
+          Some(ChangeKind::FormatChange),
+          at(1, 3),
+          at(1, 4)
+        ),
+        (Some("ct4"), Some(ChangeKind::Insertion), None, None),
+      ]
+    );
+
+    let info = changes[0].region.change_info().unwrap();
+    assert_eq!(info.creator.as_deref(), Some("Ann"));
+    assert_eq!(info.date.as_deref(), Some("2024-01-01T00:00:00"));
+    let deletion = changes[1].region.deletion.as_ref().unwrap();
+    assert_eq!(deletion.change_info.comment.len(), 1);
+    assert!(matches!(
+      deletion.list[..],
+      [DeletionItem::TextParagraph(_)]
+    ));
+
+    let xml = write_content(&content).unwrap();
+    assert_eq!(parse_content(xml).unwrap(), content);
+  }
+
+  #[test]
+  fn suggest_fixture() {
+    let document =
+      open(std::fs::read("../../extension-odt/test/wgd/suggest.odt").unwrap())
+        .unwrap();
+    let changes = document.content.tracked_changes();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].region.id(), Some("ct24081760"));
+    assert_eq!(changes[0].kind, Some(ChangeKind::Insertion));
+    assert_eq!(
+      changes[0].region.change_info().unwrap().creator.as_deref(),
+      Some("Doug Horner")
+    );
+    assert!(changes[0].start.is_some());
+    assert!(changes[0].start <= changes[0].end);
+  }
+}
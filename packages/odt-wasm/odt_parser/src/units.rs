@@ -0,0 +1,407 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Implements the string conversions serde goes through for a value type,
+/// from its `FromStr` and `Display` implementations.
+macro_rules! impl_string_serde {
+  ($type:ty) => {
+    impl TryFrom<String> for $type {
+      type Error = String;
+
+      fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+      }
+    }
+
+    impl From<$type> for String {
+      fn from(value: $type) -> Self {
+        value.to_string()
+      }
+    }
+  };
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LengthUnit {
+  #[serde(rename = "in")]
+  In,
+  #[serde(rename = "cm")]
+  Cm,
+  #[serde(rename = "mm")]
+  Mm,
+  #[serde(rename = "pt")]
+  Pt,
+  #[serde(rename = "pc")]
+  Pc,
+  #[serde(rename = "px")]
+  Px,
+  /// Relative to the font size.
+  #[serde(rename = "em")]
+  Em,
+}
+
+impl LengthUnit {
+  fn as_str(&self) -> &'static str {
+    match self {
+      LengthUnit::In => "in",
+      LengthUnit::Cm => "cm",
+      LengthUnit::Mm => "mm",
+      LengthUnit::Pt => "pt",
+      LengthUnit::Pc => "pc",
+      LengthUnit::Px => "px",
+      LengthUnit::Em => "em",
+    }
+  }
+
+  /// Points in one unit, `None` for `em`.
+  fn points(&self) -> Option<f64> {
+    match self {
+      LengthUnit::In => Some(72.0),
+      LengthUnit::Cm => Some(72.0 / 2.54),
+      LengthUnit::Mm => Some(72.0 / 25.4),
+      LengthUnit::Pt => Some(1.0),
+      LengthUnit::Pc => Some(12.0),
+      // CSS pixels, 96 per inch.
+      LengthUnit::Px => Some(0.75),
+      LengthUnit::Em => None,
+    }
+  }
+}
+
+/// A length such as "0.635cm" or "-0.25in".
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Length {
+  pub value: f64,
+  pub unit: LengthUnit,
+}
+
+impl Length {
+  pub fn new(value: f64, unit: LengthUnit) -> Self {
+    Self { value, unit }
+  }
+
+  /// The length in points. `em` lengths depend on the font size, see
+  /// `to_points_at`.
+  pub fn to_points(&self) -> Option<f64> {
+    self.unit.points().map(|points| self.value * points)
+  }
+
+  /// The length in points, `em` being `font_size` points.
+  pub fn to_points_at(&self, font_size: f64) -> f64 {
+    self.to_points().unwrap_or(self.value * font_size)
+  }
+}
+
+/// Parses a decimal number, without the exponents and names (`inf`, `NaN`)
+/// Rust accepts.
+fn parse_number(value: &str) -> Option<f64> {
+  let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+  if digits.is_empty()
+    || !digits
+      .chars()
+      .all(|char| char.is_ascii_digit() || char == '.')
+  {
+    return None;
+  }
+  value.parse().ok()
+}
+
+impl FromStr for Length {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let split = value
+      .find(|char: char| char.is_ascii_alphabetic())
+      .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let unit = match unit {
+      "in" => LengthUnit::In,
+      "cm" => LengthUnit::Cm,
+      "mm" => LengthUnit::Mm,
+      "pt" => LengthUnit::Pt,
+      "pc" => LengthUnit::Pc,
+      "px" => LengthUnit::Px,
+      "em" => LengthUnit::Em,
+      _ => return Err(format!("invalid length: {:?}", value)),
+    };
+    let value = parse_number(number)
+      .ok_or_else(|| format!("invalid length: {:?}", value))?;
+    Ok(Length { value, unit })
+  }
+}
+
+impl fmt::Display for Length {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}{}", self.value, self.unit.as_str())
+  }
+}
+
+impl_string_serde!(Length);
+
+/// A percentage such as "115%", kept as written (115.0).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Percent(pub f64);
+
+impl Percent {
+  /// The percentage as a ratio, 1.0 for "100%".
+  pub fn ratio(&self) -> f64 {
+    self.0 / 100.0
+  }
+}
+
+impl FromStr for Percent {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    value
+      .strip_suffix('%')
+      .and_then(parse_number)
+      .map(Percent)
+      .ok_or_else(|| format!("invalid percentage: {:?}", value))
+  }
+}
+
+impl fmt::Display for Percent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}%", self.0)
+  }
+}
+
+impl_string_serde!(Percent);
+
+/// An RGB color, written "#rrggbb".
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+  pub red: u8,
+  pub green: u8,
+  pub blue: u8,
+}
+
+impl FromStr for Color {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("invalid color: {:?}", value);
+    let hex = value
+      .strip_prefix('#')
+      .filter(|hex| hex.len() == 6 && hex.is_ascii())
+      .ok_or_else(invalid)?;
+    let channel = |index: usize| {
+      u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| invalid())
+    };
+    Ok(Color {
+      red: channel(0)?,
+      green: channel(2)?,
+      blue: channel(4)?,
+    })
+  }
+}
+
+impl fmt::Display for Color {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+  }
+}
+
+impl_string_serde!(Color);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum LengthOrPercent {
+  Length(Length),
+  /// Relative to the same property of the parent style, or to the font
+  /// size for line heights.
+  Percent(Percent),
+}
+
+impl LengthOrPercent {
+  pub fn length(&self) -> Option<Length> {
+    match self {
+      LengthOrPercent::Length(length) => Some(*length),
+      LengthOrPercent::Percent(_) => None,
+    }
+  }
+}
+
+impl FromStr for LengthOrPercent {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    if value.ends_with('%') {
+      value.parse().map(LengthOrPercent::Percent)
+    } else {
+      value.parse().map(LengthOrPercent::Length)
+    }
+  }
+}
+
+impl fmt::Display for LengthOrPercent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LengthOrPercent::Length(length) => length.fmt(f),
+      LengthOrPercent::Percent(percent) => percent.fmt(f),
+    }
+  }
+}
+
+impl_string_serde!(LengthOrPercent);
+
+/// A width relative to the other columns, such as "4822*".
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct RelativeWidth(pub u32);
+
+impl FromStr for RelativeWidth {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    value
+      .strip_suffix('*')
+      .filter(|digits| digits.chars().all(|char| char.is_ascii_digit()))
+      .and_then(|digits| digits.parse().ok())
+      .map(RelativeWidth)
+      .ok_or_else(|| format!("invalid relative width: {:?}", value))
+  }
+}
+
+impl fmt::Display for RelativeWidth {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}*", self.0)
+  }
+}
+
+impl_string_serde!(RelativeWidth);
+
+/// A property value with the text it was read from, or only the text when
+/// it does not parse, such as a keyword ("transparent", "normal") or a
+/// malformed value. The text is written back unchanged, "12.50pt" staying
+/// "12.50pt".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "String", into = "String")]
+#[serde(bound(serialize = "T: Clone", deserialize = "T: FromStr"))]
+pub enum Parsed<T> {
+  Value { value: T, text: String },
+  Raw(String),
+}
+
+impl<T> Parsed<T> {
+  /// A computed value, written as its `Display` form.
+  pub fn new(value: T) -> Self
+  where
+    T: fmt::Display,
+  {
+    let text = value.to_string();
+    Parsed::Value { value, text }
+  }
+
+  pub fn value(&self) -> Option<&T> {
+    match self {
+      Parsed::Value { value, .. } => Some(value),
+      Parsed::Raw(_) => None,
+    }
+  }
+
+  /// The text as written in the document.
+  pub fn text(&self) -> &str {
+    match self {
+      Parsed::Value { text, .. } | Parsed::Raw(text) => text,
+    }
+  }
+}
+
+impl<T: FromStr> From<String> for Parsed<T> {
+  fn from(text: String) -> Self {
+    match text.parse() {
+      Ok(value) => Parsed::Value { value, text },
+      Err(_) => Parsed::Raw(text),
+    }
+  }
+}
+
+impl<T: FromStr> From<&str> for Parsed<T> {
+  fn from(value: &str) -> Self {
+    value.to_string().into()
+  }
+}
+
+impl<T> fmt::Display for Parsed<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.text())
+  }
+}
+
+impl<T> From<Parsed<T>> for String {
+  fn from(value: Parsed<T>) -> Self {
+    match value {
+      Parsed::Value { text, .. } | Parsed::Raw(text) => text,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_values() {
+    let length: Length = "0.635cm".parse().unwrap();
+    assert_eq!(length, Length::new(0.635, LengthUnit::Cm));
+    assert!((length.to_points().unwrap() - 18.0).abs() < 1e-9);
+    assert_eq!("-0.25in".parse(), Ok(Length::new(-0.25, LengthUnit::In)));
+    assert_eq!("1pc".parse::<Length>().unwrap().to_points(), Some(12.0));
+    assert_eq!("8px".parse::<Length>().unwrap().to_points(), Some(6.0));
+    let em: Length = "1.5em".parse().unwrap();
+    assert_eq!(em.to_points(), None);
+    assert_eq!(em.to_points_at(12.0), 18.0);
+    for invalid in ["", "12", "pt", "1e5pt", "infpt", "12 pt", "12PT"] {
+      assert!(invalid.parse::<Length>().is_err(), "{:?}", invalid);
+    }
+
+    assert_eq!("115%".parse::<Percent>().unwrap().ratio(), 1.15);
+    assert!("NaN%".parse::<Percent>().is_err());
+
+    let color: Color = "#FF8000".parse().unwrap();
+    assert_eq!(
+      color,
+      Color {
+        red: 255,
+        green: 128,
+        blue: 0
+      }
+    );
+    assert_eq!(color.to_string(), "#ff8000");
+    for invalid in ["ff8000", "#f80", "#gg8000", "#ff80€"] {
+      assert!(invalid.parse::<Color>().is_err(), "{:?}", invalid);
+    }
+
+    assert_eq!("150%".parse(), Ok(LengthOrPercent::Percent(Percent(150.0))));
+    assert_eq!(
+      "12pt".parse::<LengthOrPercent>().unwrap().length(),
+      Some(Length::new(12.0, LengthUnit::Pt))
+    );
+
+    let normal = Parsed::<LengthOrPercent>::from("normal");
+    assert_eq!(normal, Parsed::Raw("normal".to_string()));
+    assert_eq!(normal.value(), None);
+    assert_eq!(normal.to_string(), "normal");
+    assert_eq!(Parsed::<Length>::from("0.5in").to_string(), "0.5in");
+
+    // Parsed values are written as they were read.
+    let size = Parsed::<Length>::from("12.50pt");
+    assert_eq!(size.value(), Some(&Length::new(12.5, LengthUnit::Pt)));
+    assert_eq!(String::from(size), "12.50pt");
+    assert_eq!(Parsed::<Color>::from("#FF8000").to_string(), "#FF8000");
+    assert_eq!(Parsed::new(Percent(120.0)).text(), "120%");
+
+    assert_eq!("4822*".parse(), Ok(RelativeWidth(4822)));
+    for invalid in ["4822", "*", "+1*", "1.5*"] {
+      assert!(invalid.parse::<RelativeWidth>().is_err(), "{:?}", invalid);
+    }
+  }
+}
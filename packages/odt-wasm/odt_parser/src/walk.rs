@@ -0,0 +1,488 @@
+use serde::Serialize;
+
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::NoteBodyItem;
+use crate::ods_structs::NoteClass;
+use crate::ods_structs::OfficeAnnotation;
+use crate::ods_structs::OfficeAnnotationEnd;
+use crate::ods_structs::OfficeText;
+use crate::ods_structs::OfficeTextItem;
+use crate::ods_structs::TableCellItem;
+use crate::ods_structs::TableRowItem;
+use crate::ods_structs::TableTable;
+use crate::ods_structs::TextChange;
+use crate::ods_structs::TextChangeEnd;
+use crate::ods_structs::TextChangeStart;
+use crate::ods_structs::TextHeading;
+use crate::ods_structs::TextLink;
+use crate::ods_structs::TextLinkItem;
+use crate::ods_structs::TextList;
+use crate::ods_structs::TextListItemEnum;
+use crate::ods_structs::TextNote;
+use crate::ods_structs::TextParagraph;
+use crate::ods_structs::TextParagraphItem;
+use crate::ods_structs::TextSpanItem;
+
+/// A paragraph or heading, the blocks that hold inline content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextBlock<'a> {
+  Paragraph(&'a TextParagraph),
+  Heading(&'a TextHeading),
+}
+
+impl<'a> TextBlock<'a> {
+  pub fn items(&self) -> &'a [TextParagraphItem] {
+    match self {
+      TextBlock::Paragraph(paragraph) => &paragraph.list,
+      TextBlock::Heading(heading) => &heading.list,
+    }
+  }
+
+  pub fn style_name(&self) -> Option<&'a str> {
+    match self {
+      TextBlock::Paragraph(paragraph) => paragraph.style_name.as_deref(),
+      TextBlock::Heading(heading) => heading.style_name.as_deref(),
+    }
+  }
+
+  /// The text of the block, with spaces expanded, tabs and line breaks as
+  /// `\t` and `\n`, and fields as their value. [`TextPosition`] offsets
+  /// count its chars.
+  pub fn text(&self) -> String {
+    let mut text = String::new();
+    for item in self.items() {
+      paragraph_text(item, &mut text);
+    }
+    text
+  }
+}
+
+fn paragraph_text(item: &TextParagraphItem, text: &mut String) {
+  match item {
+    TextParagraphItem::Text(value) => text.push_str(value),
+    TextParagraphItem::TextSpace(space) => {
+      text.extend(std::iter::repeat_n(' ', space.count()))
+    }
+    TextParagraphItem::TextTab => text.push('\t'),
+    TextParagraphItem::TextLineBreak => text.push('\n'),
+    TextParagraphItem::TextSpan(span) => {
+      span.list.iter().for_each(|item| span_text(item, text))
+    }
+    TextParagraphItem::TextLink(link) => {
+      link.list.iter().for_each(|item| link_text(item, text))
+    }
+    item => text.extend(item.field().map(|field| field.value())),
+  }
+}
+
+fn span_text(item: &TextSpanItem, text: &mut String) {
+  match item {
+    TextSpanItem::Text(value) => text.push_str(value),
+    TextSpanItem::TextSpace(space) => {
+      text.extend(std::iter::repeat_n(' ', space.count()))
+    }
+    TextSpanItem::TextTab => text.push('\t'),
+    TextSpanItem::TextLineBreak => text.push('\n'),
+    item => text.extend(item.field().map(|field| field.value())),
+  }
+}
+
+fn link_text(item: &TextLinkItem, text: &mut String) {
+  match item {
+    TextLinkItem::Text(value) => text.push_str(value),
+    TextLinkItem::TextSpace(space) => {
+      text.extend(std::iter::repeat_n(' ', space.count()))
+    }
+    TextLinkItem::TextTab => text.push('\t'),
+    TextLinkItem::TextSpan(span) => {
+      span.list.iter().for_each(|item| span_text(item, text))
+    }
+    item => text.extend(item.field().map(|field| field.value())),
+  }
+}
+
+/// Every paragraph and heading of `text` in document order, including those
+/// in lists, tables, indexes, sections and note bodies. A note body comes
+/// right after the block that cites it.
+pub fn text_blocks(text: &OfficeText) -> Vec<TextBlock<'_>> {
+  blocks(&text.list)
+}
+
+/// Like [`text_blocks`], for content that has the block model of the body,
+/// such as headers and footers.
+pub fn blocks(items: &[OfficeTextItem]) -> Vec<TextBlock<'_>> {
+  let mut blocks = Vec::new();
+  for item in items {
+    office_text_item(item, &mut blocks);
+  }
+  blocks
+}
+
+fn office_text_item<'a>(
+  item: &'a OfficeTextItem,
+  out: &mut Vec<TextBlock<'a>>,
+) {
+  match item {
+    OfficeTextItem::TextParagraph(paragraph) => {
+      block(TextBlock::Paragraph(paragraph), out)
+    }
+    OfficeTextItem::TextHeading(heading) => {
+      block(TextBlock::Heading(heading), out)
+    }
+    OfficeTextItem::TableTable(table) => table_blocks(table, out),
+    OfficeTextItem::TextList(list) => list_blocks(list, out),
+    OfficeTextItem::TextSection(section) => {
+      for item in &section.list {
+        office_text_item(item, out);
+      }
+    }
+    item => {
+      for paragraph in
+        item.index_body().iter().flat_map(|body| body.paragraphs())
+      {
+        block(TextBlock::Paragraph(paragraph), out);
+      }
+    }
+  }
+}
+
+fn block<'a>(block: TextBlock<'a>, out: &mut Vec<TextBlock<'a>>) {
+  out.push(block);
+  for item in block.items() {
+    if let TextParagraphItem::TextNote(note) = item {
+      for item in note.note_body.iter().flat_map(|body| &body.list) {
+        match item {
+          NoteBodyItem::TextParagraph(paragraph) => {
+            self::block(TextBlock::Paragraph(paragraph), out)
+          }
+          NoteBodyItem::TextHeading(heading) => {
+            self::block(TextBlock::Heading(heading), out)
+          }
+          NoteBodyItem::TextList(list) => list_blocks(list, out),
+          NoteBodyItem::TableTable(table) => table_blocks(table, out),
+          _ => {}
+        }
+      }
+    }
+  }
+}
+
+fn table_blocks<'a>(table: &'a TableTable, out: &mut Vec<TextBlock<'a>>) {
+  let header_rows = table.header_rows.iter().flat_map(|header| &header.rows);
+  for row in header_rows.chain(&table.rows) {
+    for item in &row.list {
+      let (TableRowItem::TableCell(cell)
+      | TableRowItem::CoveredTableCell(cell)) = item
+      else {
+        continue;
+      };
+      for item in &cell.list {
+        match item {
+          TableCellItem::TextParagraph(paragraph) => {
+            block(TextBlock::Paragraph(paragraph), out)
+          }
+          TableCellItem::TextHeading(heading) => {
+            block(TextBlock::Heading(heading), out)
+          }
+          TableCellItem::TableTable(table) => table_blocks(table, out),
+          TableCellItem::TextList(list) => list_blocks(list, out),
+          _ => {}
+        }
+      }
+    }
+  }
+}
+
+fn list_blocks<'a>(list: &'a TextList, out: &mut Vec<TextBlock<'a>>) {
+  for item in list.list.iter().flat_map(|item| &item.list) {
+    match item {
+      TextListItemEnum::TextParagraph(paragraph) => {
+        block(TextBlock::Paragraph(paragraph), out)
+      }
+      TextListItemEnum::TextHeading(heading) => {
+        block(TextBlock::Heading(heading), out)
+      }
+      TextListItemEnum::TextList(list) => list_blocks(list, out),
+      _ => {}
+    }
+  }
+}
+
+/// A point in the text of the document body.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TextPosition {
+  /// Index of the paragraph or heading in [`text_blocks`] order.
+  pub block: usize,
+  /// Characters (Unicode scalar values) before the point, as in
+  /// [`TextBlock::text`]. Spaces, tabs and line breaks count as one
+  /// character each, fields as the characters of their value, other inline
+  /// objects as none.
+  pub offset: usize,
+}
+
+/// An inline element that marks a point or the bounds of a range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Marker<'a> {
+  AnnotationStart(&'a OfficeAnnotation),
+  AnnotationEnd(&'a OfficeAnnotationEnd),
+  ChangeStart(&'a TextChangeStart),
+  ChangeEnd(&'a TextChangeEnd),
+  Change(&'a TextChange),
+  /// `text:bookmark` or `text:bookmark-start`, by name.
+  Bookmark(&'a str),
+  /// The start of a hyperlink.
+  Link(&'a TextLink),
+}
+
+/// Every marker of `text` in document order with its position.
+pub fn markers(text: &OfficeText) -> Vec<(TextPosition, Marker<'_>)> {
+  let mut walker = MarkerWalker {
+    position: TextPosition {
+      block: 0,
+      offset: 0,
+    },
+    markers: Vec::new(),
+  };
+  for (index, block) in text_blocks(text).iter().enumerate() {
+    walker.position = TextPosition {
+      block: index,
+      offset: 0,
+    };
+    for item in block.items() {
+      walker.paragraph_item(item);
+    }
+  }
+  walker.markers
+}
+
+struct MarkerWalker<'a> {
+  position: TextPosition,
+  markers: Vec<(TextPosition, Marker<'a>)>,
+}
+
+impl<'a> MarkerWalker<'a> {
+  fn paragraph_item(&mut self, item: &'a TextParagraphItem) {
+    match item {
+      TextParagraphItem::Text(text) => self.advance(text.chars().count()),
+      TextParagraphItem::TextSpace(space) => self.advance(space.count()),
+      TextParagraphItem::TextTab | TextParagraphItem::TextLineBreak => {
+        self.advance(1)
+      }
+      TextParagraphItem::TextSpan(span) => {
+        span.list.iter().for_each(|item| self.span_item(item))
+      }
+      TextParagraphItem::TextLink(link) => {
+        self.mark(Marker::Link(link));
+        link.list.iter().for_each(|item| self.link_item(item))
+      }
+      TextParagraphItem::TextBookmark(bookmark) => {
+        self.mark(Marker::Bookmark(&bookmark.name))
+      }
+      TextParagraphItem::TextBookmarkStart(bookmark) => {
+        self.mark(Marker::Bookmark(&bookmark.name))
+      }
+      TextParagraphItem::OfficeAnnotation(annotation) => {
+        self.mark(Marker::AnnotationStart(annotation))
+      }
+      TextParagraphItem::OfficeAnnotationEnd(end) => {
+        self.mark(Marker::AnnotationEnd(end))
+      }
+      TextParagraphItem::TextChangeStart(start) => {
+        self.mark(Marker::ChangeStart(start))
+      }
+      TextParagraphItem::TextChangeEnd(end) => {
+        self.mark(Marker::ChangeEnd(end))
+      }
+      TextParagraphItem::TextChange(change) => {
+        self.mark(Marker::Change(change))
+      }
+      item => {
+        if let Some(field) = item.field() {
+          self.advance(field.value().chars().count())
+        }
+      }
+    }
+  }
+
+  fn span_item(&mut self, item: &'a TextSpanItem) {
+    match item {
+      TextSpanItem::Text(text) => self.advance(text.chars().count()),
+      TextSpanItem::TextSpace(space) => self.advance(space.count()),
+      TextSpanItem::TextTab | TextSpanItem::TextLineBreak => self.advance(1),
+      TextSpanItem::OfficeAnnotation(annotation) => {
+        self.mark(Marker::AnnotationStart(annotation))
+      }
+      TextSpanItem::OfficeAnnotationEnd(end) => {
+        self.mark(Marker::AnnotationEnd(end))
+      }
+      TextSpanItem::TextChangeStart(start) => {
+        self.mark(Marker::ChangeStart(start))
+      }
+      TextSpanItem::TextChangeEnd(end) => self.mark(Marker::ChangeEnd(end)),
+      TextSpanItem::TextChange(change) => self.mark(Marker::Change(change)),
+      item => {
+        if let Some(field) = item.field() {
+          self.advance(field.value().chars().count())
+        }
+      }
+    }
+  }
+
+  fn link_item(&mut self, item: &'a TextLinkItem) {
+    match item {
+      TextLinkItem::Text(text) => self.advance(text.chars().count()),
+      TextLinkItem::TextSpace(space) => self.advance(space.count()),
+      TextLinkItem::TextTab => self.advance(1),
+      TextLinkItem::TextSpan(span) => {
+        span.list.iter().for_each(|item| self.span_item(item))
+      }
+      TextLinkItem::OfficeAnnotation(annotation) => {
+        self.mark(Marker::AnnotationStart(annotation))
+      }
+      TextLinkItem::OfficeAnnotationEnd(end) => {
+        self.mark(Marker::AnnotationEnd(end))
+      }
+      TextLinkItem::TextChangeStart(start) => {
+        self.mark(Marker::ChangeStart(start))
+      }
+      TextLinkItem::TextChangeEnd(end) => self.mark(Marker::ChangeEnd(end)),
+      TextLinkItem::TextChange(change) => self.mark(Marker::Change(change)),
+      item => {
+        if let Some(field) = item.field() {
+          self.advance(field.value().chars().count())
+        }
+      }
+    }
+  }
+
+  fn advance(&mut self, chars: usize) {
+    self.position.offset += chars;
+  }
+
+  fn mark(&mut self, marker: Marker<'a>) {
+    self.markers.push((self.position, marker));
+  }
+}
+
+/// A footnote or endnote with its position among the notes of its class.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Note<'a> {
+  /// 1 based, counted separately for footnotes and endnotes.
+  pub number: usize,
+  pub note: &'a TextNote,
+}
+
+impl<'a> Note<'a> {
+  pub fn id(&self) -> Option<&'a str> {
+    self.note.id.as_deref()
+  }
+
+  pub fn class(&self) -> NoteClass {
+    self.note.note_class
+  }
+}
+
+impl DocumentContent {
+  /// Footnotes and endnotes in citation order.
+  pub fn notes(&self) -> Vec<Note<'_>> {
+    let mut footnotes = 0;
+    let mut endnotes = 0;
+    text_blocks(&self.body.text)
+      .into_iter()
+      .flat_map(|block| block.items())
+      .filter_map(|item| match item {
+        TextParagraphItem::TextNote(note) => Some(note),
+        _ => None,
+      })
+      .map(|note| {
+        let count = match note.note_class {
+          NoteClass::Footnote => &mut footnotes,
+          NoteClass::Endnote => &mut endnotes,
+        };
+        *count += 1;
+        Note {
+          number: *count,
+          note,
+        }
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::parse_content;
+  use crate::core::tests::content_xml;
+  use crate::writer::write_content;
+
+  const BODY: &str = r#"
+    <text:p>One<text:note text:id="ftn1" text:note-class="footnote">
+      <text:note-citation>1</text:note-citation>
+      <text:note-body>
+        <text:p>First</text:p>
+        <text:list><text:list-item><text:p>Item</text:p></text:list-item></text:list>
+        <table:table><table:table-column/><table:table-row>
+          <table:table-cell><text:p>Cell</text:p></table:table-cell>
+        </table:table-row></table:table>
+      </text:note-body>
+    </text:note></text:p>
+    <table:table><table:table-column/><table:table-row><table:table-cell>
+      <text:p>Two<text:note text:id="edn1" text:note-class="endnote">
+        <text:note-citation text:label="*">*</text:note-citation>
+        <text:note-body><text:p>Second</text:p></text:note-body>
+      </text:note></text:p>
+    </table:table-cell></table:table-row></table:table>
+    <text:list><text:list-item>
+      <text:h>Three<text:note text:id="ftn2" text:note-class="footnote">
+        <text:note-citation>2</text:note-citation>
+        <text:note-body><text:p>Third</text:p></text:note-body>
+      </text:note></text:h>
+    </text:list-item></text:list>"#;
+
+  #[test]
+  fn collects_notes() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let notes = content.notes();
+    let summary: Vec<_> = notes
+      .iter()
+      .map(|note| (note.id(), note.class(), note.number))
+      .collect();
+    assert_eq!(
+      summary,
+      vec![
+        (Some("ftn1"), NoteClass::Footnote, 1),
+        (Some("edn1"), NoteClass::Endnote, 1),
+        (Some("ftn2"), NoteClass::Footnote, 2),
+      ]
+    );
+
+    let body = &notes[0].note.note_body.as_ref().unwrap().list;
+    assert!(matches!(body[1], NoteBodyItem::TextList(_)));
+    assert!(matches!(body[2], NoteBodyItem::TableTable(_)));
+    let citation = notes[1].note.note_citation.as_ref().unwrap();
+    assert_eq!(citation.label.as_deref(), Some("*"));
+
+    let xml = String::from_utf8(write_content(&content).unwrap()).unwrap();
+    assert!(
+      xml.contains(r#"<text:note text:id="edn1" text:note-class="endnote">"#)
+    );
+    assert_eq!(parse_content(xml.into_bytes()).unwrap(), content);
+  }
+
+  #[test]
+  fn walks_blocks_in_order() {
+    let content = parse_content(content_xml(BODY)).unwrap();
+    let text: Vec<String> = text_blocks(&content.body.text)
+      .iter()
+      .map(|block| match &block.items()[0] {
+        TextParagraphItem::Text(text) => text.clone(),
+        item => format!("{:?}", item),
+      })
+      .collect();
+    assert_eq!(
+      text,
+      ["One", "First", "Item", "Cell", "Two", "Second", "Three", "Third"]
+    );
+  }
+}
@@ -0,0 +1,536 @@
+use std::io::Cursor;
+use std::io::Write;
+
+use quick_xml::events::BytesDecl;
+use quick_xml::events::BytesEnd;
+use quick_xml::events::BytesStart;
+use quick_xml::events::BytesText;
+use quick_xml::events::Event;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+use zip::ZipWriter;
+
+use crate::document::OdtDocument;
+use crate::error::OdtError;
+use crate::manifest::guess_media_type;
+use crate::manifest::FileEntry;
+use crate::manifest::Manifest;
+use crate::manifest::MANIFEST_PATH;
+use crate::namespaces::declare_namespaces;
+use crate::ods_structs::DocumentContent;
+use crate::ods_structs::DocumentMeta;
+use crate::ods_structs::DocumentStyles;
+use crate::ods_structs::Extra;
+use crate::ods_structs::RawElement;
+
+pub const ODT_MIMETYPE: &str = "application/vnd.oasis.opendocument.text";
+const ODF_VERSION: &str = "1.3";
+
+type XmlWriter = quick_xml::Writer<Vec<u8>>;
+
+/// Zips a document into an .odt package: `mimetype` first and stored, then
+/// content, styles, meta, settings, every media part and a manifest listing
+/// them.
+pub fn write(document: &OdtDocument) -> Result<Vec<u8>, OdtError> {
+  let mimetype = document.mimetype.as_deref().unwrap_or(ODT_MIMETYPE);
+  let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+  let mut manifest = Manifest {
+    version: Some(ODF_VERSION.to_string()),
+    entries: vec![FileEntry {
+      version: Some(ODF_VERSION.to_string()),
+      ..FileEntry::new("/", mimetype)
+    }],
+  };
+  // Directories, eg. embedded objects, only exist in the manifest.
+  if let Some(source) = &document.manifest {
+    manifest.entries.extend(
+      source
+        .entries
+        .iter()
+        .filter(|entry| entry.full_path != "/" && !entry.is_file())
+        .map(|entry| FileEntry {
+          full_path: entry.full_path.clone(),
+          media_type: entry.media_type.clone(),
+          version: entry.version.clone(),
+          ..Default::default()
+        }),
+    );
+  }
+
+  let stored =
+    SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+  zip.start_file("mimetype", stored)?;
+  zip.write_all(mimetype.as_bytes()).map_err(invalid_zip)?;
+
+  let mut parts = vec![
+    ("content.xml".to_string(), write_content(&document.content)?),
+    ("styles.xml".to_string(), write_styles(&document.styles)?),
+  ];
+  if let Some(meta) = &document.meta {
+    parts.push(("meta.xml".to_string(), write_meta(meta)?));
+  }
+  if let Some(settings) = &document.settings {
+    parts.push(("settings.xml".to_string(), settings.as_bytes().to_vec()));
+  }
+  for name in document.part_names() {
+    let bytes = document.media[&name].clone();
+    parts.push((name, bytes));
+  }
+
+  let deflated = SimpleFileOptions::default()
+    .compression_method(CompressionMethod::Deflated);
+  for (name, bytes) in &parts {
+    zip.start_file(name.as_str(), deflated)?;
+    zip.write_all(bytes).map_err(invalid_zip)?;
+    let media_type = document
+      .manifest
+      .as_ref()
+      .and_then(|source| source.entry(name))
+      .map(|entry| entry.media_type.as_str())
+      .unwrap_or_else(|| guess_media_type(name));
+    manifest.entries.push(FileEntry::new(name, media_type));
+  }
+
+  zip.start_file(MANIFEST_PATH, deflated)?;
+  zip
+    .write_all(&write_manifest(&manifest)?)
+    .map_err(invalid_zip)?;
+
+  Ok(zip.finish()?.into_inner())
+}
+
+pub fn write_content(content: &DocumentContent) -> Result<Vec<u8>, OdtError> {
+  to_xml("office:document-content", content)
+}
+
+pub fn write_styles(styles: &DocumentStyles) -> Result<Vec<u8>, OdtError> {
+  to_xml("office:document-styles", styles)
+}
+
+pub fn write_meta(meta: &DocumentMeta) -> Result<Vec<u8>, OdtError> {
+  to_xml("office:document-meta", meta)
+}
+
+/// `META-INF/manifest.xml`. Sizes and encryption data are not written, the
+/// writer does not encrypt parts.
+pub fn write_manifest(manifest: &Manifest) -> Result<Vec<u8>, OdtError> {
+  let mut writer = xml_writer()?;
+
+  let mut root = BytesStart::new("manifest:manifest");
+  root.push_attribute((
+    "xmlns:manifest",
+    "urn:oasis:names:tc:opendocument:xmlns:manifest:1.0",
+  ));
+  if let Some(version) = &manifest.version {
+    root.push_attribute(("manifest:version", version.as_str()));
+  }
+  write_event(&mut writer, Event::Start(root))?;
+
+  for entry in &manifest.entries {
+    let mut start = BytesStart::new("manifest:file-entry");
+    start.push_attribute(("manifest:full-path", entry.full_path.as_str()));
+    if let Some(version) = &entry.version {
+      start.push_attribute(("manifest:version", version.as_str()));
+    }
+    start.push_attribute(("manifest:media-type", entry.media_type.as_str()));
+    write_event(&mut writer, Event::Empty(start))?;
+  }
+
+  write_event(&mut writer, Event::End(BytesEnd::new("manifest:manifest")))?;
+  Ok(writer.into_inner())
+}
+
+/// Writes a model struct as XML, following the serde naming used in
+/// ods_structs.rs: `@` fields are attributes, `$value` holds the children in
+/// document order, `$text` is character data and every other field is a
+/// child element. Elements the model does not know are dropped (`Unknown`) or
+/// written verbatim when the lossless parser kept them (`odt:raw`, `$extra`).
+pub(crate) fn to_xml<T: Serialize>(
+  root: &str,
+  value: &T,
+) -> Result<Vec<u8>, OdtError> {
+  let value =
+    serde_json::to_value(value).map_err(|err| OdtError::Serialize {
+      message: err.to_string(),
+    })?;
+  let Value::Object(map) = value else {
+    return Err(OdtError::Serialize {
+      message: format!("{} is not a struct", root),
+    });
+  };
+
+  let mut writer = xml_writer()?;
+  let mut start = BytesStart::new(root);
+  declare_namespaces(&mut start);
+  // A version kept by the lossless parser is written in its place.
+  let version = map
+    .get("$extra")
+    .and_then(|extra| extra.get("attributes"))
+    .and_then(Value::as_array)
+    .is_some_and(|attributes| {
+      attributes.iter().any(|attr| attr[0] == "office:version")
+    });
+  if !version {
+    start.push_attribute(("office:version", ODF_VERSION));
+  }
+  write_object(&mut writer, start, &map)?;
+
+  Ok(writer.into_inner())
+}
+
+fn xml_writer() -> Result<XmlWriter, OdtError> {
+  let mut writer = quick_xml::Writer::new(Vec::new());
+  write_event(
+    &mut writer,
+    Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)),
+  )?;
+  Ok(writer)
+}
+
+fn write_event(writer: &mut XmlWriter, event: Event) -> Result<(), OdtError> {
+  writer
+    .write_event(event)
+    .map_err(|err| OdtError::Serialize {
+      message: err.to_string(),
+    })
+}
+
+fn write_element(
+  writer: &mut XmlWriter,
+  name: &str,
+  value: &Value,
+) -> Result<(), OdtError> {
+  match value {
+    Value::Null => Ok(()),
+    // Repeated field
+    Value::Array(items) => items
+      .iter()
+      .try_for_each(|item| write_element(writer, name, item)),
+    Value::Object(map) => write_object(writer, BytesStart::new(name), map),
+    scalar => {
+      write_event(writer, Event::Start(BytesStart::new(name)))?;
+      write_text(writer, scalar)?;
+      write_event(writer, Event::End(BytesEnd::new(name)))
+    }
+  }
+}
+
+fn write_object(
+  writer: &mut XmlWriter,
+  mut start: BytesStart,
+  map: &Map<String, Value>,
+) -> Result<(), OdtError> {
+  let extra: Extra = match map.get("$extra") {
+    Some(extra) => serde_json::from_value(extra.clone()).map_err(|err| {
+      OdtError::Serialize {
+        message: err.to_string(),
+      }
+    })?,
+    None => Extra::default(),
+  };
+
+  for (key, value) in map {
+    if let Some(name) = key.strip_prefix('@') {
+      if let Some(value) = scalar_string(value) {
+        start.push_attribute((name, value.as_str()));
+      }
+    }
+  }
+  for (name, value) in &extra.attributes {
+    // The root already declares the namespaces.
+    if !matches!(start.try_get_attribute(name.as_str()), Ok(Some(_))) {
+      start.push_attribute((name.as_str(), value.as_str()));
+    }
+  }
+
+  let has_children = !extra.elements.is_empty()
+    || map.iter().any(|(key, value)| {
+      !key.starts_with('@') && key != "$extra" && !is_empty(value)
+    });
+  if !has_children {
+    return write_event(writer, Event::Empty(start));
+  }
+
+  let end = start.to_end().into_owned();
+  write_event(writer, Event::Start(start))?;
+  let mut raws = RawElements {
+    elements: &extra.elements,
+    written: vec![false; extra.elements.len()],
+  };
+  for (key, value) in map {
+    match key.as_str() {
+      key if key.starts_with('@') => {}
+      "$extra" => {}
+      "$text" => write_text(writer, value)?,
+      "$value" => write_children(writer, value)?,
+      // Repeated field
+      name if value.is_array() => {
+        for (index, item) in value.as_array().into_iter().flatten().enumerate()
+        {
+          raws.write_before(writer, name, index)?;
+          write_element(writer, name, item)?;
+        }
+      }
+      name => {
+        raws.write_before(writer, name, 0)?;
+        write_element(writer, name, value)?;
+      }
+    }
+  }
+  // After the last known element, or before one that is gone.
+  raws.write_rest(writer)?;
+  write_event(writer, Event::End(end))
+}
+
+// The unknown child elements of a node, written back before the known
+// element that followed them.
+struct RawElements<'a> {
+  elements: &'a [RawElement],
+  written: Vec<bool>,
+}
+
+impl RawElements<'_> {
+  fn write_before(
+    &mut self,
+    writer: &mut XmlWriter,
+    name: &str,
+    index: usize,
+  ) -> Result<(), OdtError> {
+    for (raw, written) in self.elements.iter().zip(&mut self.written) {
+      let before = raw.before.as_ref();
+      if !*written && before.is_some_and(|(n, i)| n == name && *i == index) {
+        write_raw(writer, &raw.xml)?;
+        *written = true;
+      }
+    }
+    Ok(())
+  }
+
+  fn write_rest(&mut self, writer: &mut XmlWriter) -> Result<(), OdtError> {
+    for (raw, written) in self.elements.iter().zip(&mut self.written) {
+      if !*written {
+        write_raw(writer, &raw.xml)?;
+        *written = true;
+      }
+    }
+    Ok(())
+  }
+}
+
+// `$value` is either a list of enum variants or plain text.
+fn write_children(
+  writer: &mut XmlWriter,
+  value: &Value,
+) -> Result<(), OdtError> {
+  let Value::Array(items) = value else {
+    return write_text(writer, value);
+  };
+
+  for item in items {
+    match item {
+      Value::String(variant) if variant == "Unknown" => {}
+      // Unit variant, eg. `text:tab`
+      Value::String(variant) => {
+        write_event(writer, Event::Empty(BytesStart::new(variant.as_str())))?
+      }
+      Value::Object(map) => {
+        for (name, value) in map {
+          match name.as_str() {
+            "$text" => write_text(writer, value)?,
+            "odt:raw" => write_raw(writer, value.as_str().unwrap_or(""))?,
+            name => write_element(writer, name, value)?,
+          }
+        }
+      }
+      value => write_text(writer, value)?,
+    }
+  }
+
+  Ok(())
+}
+
+// XML kept by the lossless parser, written as is.
+fn write_raw(writer: &mut XmlWriter, raw: &str) -> Result<(), OdtError> {
+  write_event(writer, Event::Text(BytesText::from_escaped(raw)))
+}
+
+fn write_text(writer: &mut XmlWriter, value: &Value) -> Result<(), OdtError> {
+  match scalar_string(value) {
+    Some(text) => write_event(writer, Event::Text(BytesText::new(&text))),
+    None => Ok(()),
+  }
+}
+
+fn scalar_string(value: &Value) -> Option<String> {
+  match value {
+    Value::String(s) => Some(s.clone()),
+    Value::Bool(b) => Some(b.to_string()),
+    Value::Number(n) => Some(n.to_string()),
+    _ => None,
+  }
+}
+
+fn is_empty(value: &Value) -> bool {
+  match value {
+    Value::Null => true,
+    Value::Array(items) => items
+      .iter()
+      .all(|item| matches!(item, Value::String(s) if s == "Unknown")),
+    _ => false,
+  }
+}
+
+fn invalid_zip(err: std::io::Error) -> OdtError {
+  OdtError::InvalidZip {
+    message: err.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::open;
+  use crate::core::parse_content;
+  use crate::core::parse_meta;
+  use crate::core::parse_styles;
+  use crate::core::unzip;
+  use crate::ods_structs::DocumentStatistic;
+  use crate::ods_structs::UserDefined;
+
+  fn fixtures() -> Vec<std::path::PathBuf> {
+    let mut paths = vec![std::path::PathBuf::from("src/example.odt")];
+    for entry in std::fs::read_dir("../../extension-odt/test/wgd").unwrap() {
+      let path = entry.unwrap().path();
+      if path.extension().is_some_and(|ext| ext == "odt") {
+        paths.push(path);
+      }
+    }
+    paths
+  }
+
+  // The serialized model without its `Unknown` items.
+  fn known<T: Serialize>(model: &T) -> Value {
+    fn strip(value: &mut Value) {
+      match value {
+        Value::Array(items) => {
+          items
+            .retain(|item| !matches!(item, Value::String(s) if s == "Unknown"));
+          items.iter_mut().for_each(strip);
+        }
+        Value::Object(map) => map.values_mut().for_each(strip),
+        _ => {}
+      }
+    }
+    let mut value = serde_json::to_value(model).unwrap();
+    strip(&mut value);
+    value
+  }
+
+  #[test]
+  fn writes_package() {
+    let document = open(std::fs::read("src/example.odt").unwrap()).unwrap();
+    let bytes = write(&document).unwrap();
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(&bytes)).unwrap();
+    let mimetype = archive.by_index(0).unwrap();
+    assert_eq!(mimetype.name(), "mimetype");
+    assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+    drop(mimetype);
+
+    let reopened = open(bytes).unwrap();
+    assert_eq!(reopened.mimetype.as_deref(), Some(ODT_MIMETYPE));
+    assert_eq!(reopened.meta, document.meta);
+    assert_eq!(reopened.part_names(), document.part_names());
+
+    let manifest = reopened.manifest.unwrap();
+    assert_eq!(
+      manifest.entry("/").unwrap(),
+      &FileEntry {
+        version: Some("1.3".to_string()),
+        ..FileEntry::new("/", ODT_MIMETYPE)
+      }
+    );
+    assert_eq!(
+      manifest.entry("content.xml").unwrap().media_type,
+      "text/xml"
+    );
+    // Taken from the source manifest, not guessed.
+    let source = document.manifest.unwrap();
+    for path in ["Object 4/", "ObjectReplacements/Object 1"] {
+      assert_eq!(manifest.entry(path), source.entry(path), "{}", path);
+    }
+  }
+
+  #[test]
+  fn round_trips_fixtures() {
+    for path in fixtures() {
+      let mut parts = unzip(std::fs::read(&path).unwrap()).unwrap();
+      let content = parse_content(parts.remove("content.xml").unwrap())
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+      let styles = parse_styles(parts.remove("styles.xml").unwrap())
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+
+      // Whatever the parser keeps of the original, the writer keeps too.
+      // Only the `Unknown` placeholders are dropped.
+      let written = write_content(&content).unwrap();
+      let reparsed = parse_content(written)
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+      assert_eq!(known(&reparsed), known(&content), "{:?}", path);
+      let written = write_styles(&styles).unwrap();
+      let reparsed = parse_styles(written)
+        .unwrap_or_else(|err| panic!("{:?}: {}", path, err));
+      assert_eq!(known(&reparsed), known(&styles), "{:?}", path);
+    }
+  }
+
+  #[test]
+  fn writes_meta_xml() {
+    let mut meta = DocumentMeta::default();
+    meta.meta.title = Some("Q3 & Q4".to_string());
+    meta.meta.keywords = vec!["a".to_string(), "b".to_string()];
+    meta.meta.document_statistic = Some(DocumentStatistic {
+      word_count: Some(42),
+      ..Default::default()
+    });
+    meta.meta.user_defined.push(UserDefined {
+      name: "Status".to_string(),
+      value_type: None,
+      value: "Draft".to_string(),
+      extra: Default::default(),
+    });
+
+    let xml = String::from_utf8(write_meta(&meta).unwrap()).unwrap();
+    assert!(xml.contains(
+      r#"<office:meta><dc:title>Q3 &amp; Q4</dc:title><meta:keyword>a</meta:keyword><meta:keyword>b</meta:keyword><meta:document-statistic meta:word-count="42"/><meta:user-defined meta:name="Status">Draft</meta:user-defined></office:meta>"#
+    ));
+    assert_eq!(parse_meta(xml.into_bytes()).unwrap(), meta);
+  }
+
+  #[test]
+  fn writes_content_xml() {
+    let content = parse_content(
+      br#"<office:document-content
+  xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:t="urn:oasis:names:tc:opendocument:xmlns:text:1.0">
+  <office:font-face-decls/>
+  <office:automatic-styles/>
+  <office:body><office:text>
+    <t:p t:style-name="P1">a &amp; <t:s t:c="2"/>b<t:tab/><t:unknown/></t:p>
+  </office:text></office:body>
+</office:document-content>"#
+        .to_vec(),
+    )
+    .unwrap();
+
+    let xml = String::from_utf8(write_content(&content).unwrap()).unwrap();
+    assert!(xml.starts_with(
+      r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="#
+    ));
+    assert!(xml.ends_with(
+      r#"<office:body><office:text><text:p text:style-name="P1">a &amp; <text:s text:c="2"/>b<text:tab/></text:p></office:text></office:body></office:document-content>"#
+    ));
+  }
+}
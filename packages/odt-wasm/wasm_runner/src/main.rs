@@ -1,21 +1,17 @@
-use odt_parser::core::parse_content;
-use odt_parser::core::parse_styles;
-use odt_parser::core::unzip;
+use odt_parser::open;
+use std::error::Error;
 use std::fs;
 
-fn main() -> std::io::Result<()> {
+fn main() -> Result<(), Box<dyn Error>> {
   let data: Vec<u8> =
     fs::read("../extension-odt/test/odt_md/example-document.odt")?;
   println!("Read {} bytes", data.len());
 
-  let files = unzip(data);
+  let document = open(data)?;
 
-  // println!("files {:#?} ", files);
+  // println!("files {:#?} ", document.part_names());
 
-  let content = parse_content(files.get("content.xml").unwrap().to_vec());
-  let styles = parse_styles(files.get("styles.xml").unwrap().to_vec());
-
-  println!("content {:#?} ", content.body.text.list);
+  println!("content {:#?} ", document.content.body.text.list);
 
   Ok(())
 }